@@ -10,9 +10,11 @@ use codedpedia::{
     harray,
     location::{Id, InternalPath, Location},
     render::{DynFullComponent, FullRender, Html, Render},
+    serve_site_main,
     site::{Entry, Site},
     static_site_main,
 };
+use std::env;
 
 fn default_assets(
 ) -> impl Render<Html, Kind = AssetComponent> + Send + Sync + 'static {
@@ -27,11 +29,11 @@ fn banner() -> impl FullRender<Kind = BlockComponent> + Send + Sync + 'static {
 }
 
 fn index() -> impl FullRender<Kind = PageComponent> + Send + Sync + 'static {
-    Page {
-        banner: banner(),
-        title: String::from("Simple Pedia"),
-        assets: default_assets(),
-        body: harray![
+    Page::new(
+        banner(),
+        String::from("Simple Pedia"),
+        default_assets(),
+        harray![
             Paragraph(
                 "This is the initial page of the simple pedia. You can dive \
                  down into the following:"
@@ -47,7 +49,7 @@ fn index() -> impl FullRender<Kind = PageComponent> + Send + Sync + 'static {
                 }),
             ]),
         ],
-        children: harray![
+        harray![
             Section {
                 title: "Random Section",
                 id: Some(Id::new("random")),
@@ -78,45 +80,45 @@ fn index() -> impl FullRender<Kind = PageComponent> + Send + Sync + 'static {
                 children: harray![],
             }
         ],
-    }
+    )
 }
 
 fn foo_page() -> impl FullRender<Kind = PageComponent> + Send + Sync + 'static {
-    Page {
-        banner: banner(),
-        title: String::from("Foo"),
-        assets: default_assets(),
-        body: harray![Paragraph("Foo is a metavariable."),],
-        children: harray![],
-    }
+    Page::new(
+        banner(),
+        String::from("Foo"),
+        default_assets(),
+        harray![Paragraph("Foo is a metavariable."),],
+        harray![],
+    )
 }
 
 fn bar_page() -> impl FullRender<Kind = PageComponent> + Send + Sync + 'static {
-    Page {
-        banner: banner(),
-        title: String::from("Bar"),
-        assets: default_assets(),
-        body: harray![Paragraph(harray![
+    Page::new(
+        banner(),
+        String::from("Bar"),
+        default_assets(),
+        harray![Paragraph(harray![
             "Bar is a metavariable. ",
             Link { location: Location::internal("bar/baz"), target: "Baz" },
             " is also a metavariable."
         ])],
-        children: harray![],
-    }
+        harray![],
+    )
 }
 
 fn baz_page() -> impl FullRender<Kind = PageComponent> + Send + Sync + 'static {
-    Page {
-        banner: banner(),
-        title: String::from("Baz"),
-        assets: default_assets(),
-        body: harray![Paragraph(harray![
+    Page::new(
+        banner(),
+        String::from("Baz"),
+        default_assets(),
+        harray![Paragraph(harray![
             "Baz is a metavariable, similar to ",
             Link { location: Location::internal("bar"), target: "Bar" },
             "."
         ])],
-        children: harray![],
-    }
+        harray![],
+    )
 }
 
 fn simple_pedia_site() -> Site<DynFullComponent<'static, PageComponent>> {
@@ -145,5 +147,9 @@ fn simple_pedia_site() -> Site<DynFullComponent<'static, PageComponent>> {
 fn main() {
     let site = simple_pedia_site();
 
-    static_site_main(&site, &mut Html, "examples/build", "examples/assets");
+    if env::args().nth(1).as_deref() == Some("serve") {
+        serve_site_main(&site, &mut Html, "127.0.0.1:8080", "examples/assets");
+    } else {
+        static_site_main(&site, &mut Html, "examples/build", "examples/assets");
+    }
 }
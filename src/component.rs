@@ -23,14 +23,14 @@ pub trait Component: fmt::Debug {
     type Kind: ComponentKind + ?Sized;
 }
 
-impl<'this, T> Component for &'this T
+impl<T> Component for &T
 where
     T: Component + ?Sized,
 {
     type Kind = T::Kind;
 }
 
-impl<'this, T> Component for &'this mut T
+impl<T> Component for &mut T
 where
     T: Component + ?Sized,
 {
@@ -102,9 +102,9 @@ where
     type Kind = A::Kind;
 }
 
-impl<'this, K> ComponentKind for &'this K where K: ComponentKind + ?Sized {}
+impl<K> ComponentKind for &K where K: ComponentKind + ?Sized {}
 
-impl<'this, K> ComponentKind for &'this mut K where K: ComponentKind + ?Sized {}
+impl<K> ComponentKind for &mut K where K: ComponentKind + ?Sized {}
 
 impl<K> ComponentKind for Box<K> where K: ComponentKind + ?Sized {}
 
@@ -4,6 +4,7 @@ use super::{Component, ComponentKind, InlineComponent};
 use crate::{
     location::Location,
     render::{Context, Html, Render, Renderer},
+    visit::{visit_node, Node, Visit, Visitor},
 };
 use std::fmt::{self, Write};
 
@@ -43,6 +44,15 @@ impl Render<Html> for Stylesheet {
     }
 }
 
+impl Visit for Stylesheet {
+    fn visit<V>(&self, visitor: &mut V, ctx: Context<Self::Kind>)
+    where
+        V: Visitor + ?Sized,
+    {
+        visit_node(visitor, Node::Stylesheet(&self.location), ctx, |_| ());
+    }
+}
+
 /// JavaScript script asset.
 ///
 /// # HTML Classes
@@ -71,6 +81,15 @@ impl Render<Html> for Script {
     }
 }
 
+impl Visit for Script {
+    fn visit<V>(&self, visitor: &mut V, ctx: Context<Self::Kind>)
+    where
+        V: Visitor + ?Sized,
+    {
+        visit_node(visitor, Node::Script(&self.location), ctx, |_| ());
+    }
+}
+
 #[cfg(test)]
 mod test {
     use super::{AssetComponent, Script, Stylesheet};
@@ -88,7 +107,7 @@ mod test {
     fn stylesheet_is_valid_html() {
         let rendered = RenderAsDisplay::new(
             Stylesheet { location: Location::internal("styles/main.css") },
-            &mut Html::default(),
+            &mut Html,
             Context::new(&InternalPath::default(), &AssetComponent),
         )
         .to_string();
@@ -100,7 +119,7 @@ mod test {
     fn script_is_valid_html() {
         let rendered = RenderAsDisplay::new(
             Script { location: Location::internal("js/main.js") },
-            &mut Html::default(),
+            &mut Html,
             Context::new(&InternalPath::default(), &AssetComponent),
         )
         .to_string();
@@ -1,13 +1,23 @@
 //! This module exports components that are of type block, as well their kind.
 
 use super::{Component, ComponentKind, InlineComponent};
-use crate::render::{Context, Html, Markdown, Render, Renderer, Text};
+use crate::{
+    render::{Context, Gemtext, Html, Latex, Markdown, Render, Renderer, Text},
+    visit::{visit_node, Node, Visit, Visitor},
+};
 use std::fmt::{self, Write};
 
 pub mod text;
 pub mod media;
 pub mod list;
 pub mod table;
+pub mod toc;
+pub mod footnote;
+pub mod bibliography;
+pub mod code;
+pub mod math;
+pub mod infobox;
+pub mod breadcrumbs;
 
 /// A block component. Such component is one that cannot appear in the middle of
 /// reading text and can appear directly in the body of a section.
@@ -77,3 +87,43 @@ where
         self.0.render(renderer, ctx.with_kind(&InlineComponent))
     }
 }
+
+impl<C> Render<Latex> for InlineBlock<C>
+where
+    C: Render<Latex, Kind = InlineComponent>,
+{
+    fn render(
+        &self,
+        renderer: &mut Renderer<Latex>,
+        ctx: Context<Self::Kind>,
+    ) -> fmt::Result {
+        self.0.render(renderer, ctx.with_kind(&InlineComponent))
+    }
+}
+
+impl<C> Render<Gemtext> for InlineBlock<C>
+where
+    C: Render<Gemtext, Kind = InlineComponent>,
+{
+    fn render(
+        &self,
+        renderer: &mut Renderer<Gemtext>,
+        ctx: Context<Self::Kind>,
+    ) -> fmt::Result {
+        self.0.render(renderer, ctx.with_kind(&InlineComponent))
+    }
+}
+
+impl<C> Visit for InlineBlock<C>
+where
+    C: Visit<Kind = InlineComponent>,
+{
+    fn visit<V>(&self, visitor: &mut V, ctx: Context<Self::Kind>)
+    where
+        V: Visitor + ?Sized,
+    {
+        visit_node(visitor, Node::InlineBlock, ctx, |visitor| {
+            self.0.visit(visitor, ctx.with_kind(&InlineComponent))
+        });
+    }
+}
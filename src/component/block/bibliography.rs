@@ -0,0 +1,477 @@
+//! This module exports the bibliography component.
+
+use super::BlockComponent;
+use crate::{
+    component::{
+        inline::{
+            cite::{CitationStyle, Source},
+            text::{Italic, Link},
+        },
+        Component,
+        ComponentKind,
+        InlineComponent,
+    },
+    location::Location,
+    render::{
+        gemtext,
+        latex,
+        Context,
+        Format,
+        Gemtext,
+        Html,
+        Latex,
+        Markdown,
+        Render,
+        Renderer,
+        Text,
+    },
+    visit::{visit_node, Node, Visit, Visitor},
+};
+use std::{
+    fmt::{self, Write},
+    sync::Arc,
+};
+
+/// A bibliography, listing sources in the citation style of the page (see
+/// [`Context::citations`]). Each entry has the ID generated from its source
+/// (see [`Source::entry_id`]), to which citations link.
+///
+/// In the author-year style, entries are sorted by authors, year and title.
+/// In the numeric style, entries are sorted by the numbers of their sources
+/// in the citations of the page, sources that were not cited being numbered
+/// after the cited ones.
+///
+/// # HTML Classes
+///
+/// - `pedia-bibliography` attached to an `<ol>` element in the numeric style,
+///   or to an `<ul>` element in the author-year style.
+/// - `pedia-source` attached to a `<li>` element, whose ID is the one of the
+///   entry.
+#[derive(Debug, Clone, PartialEq, Eq, PartialOrd, Ord, Hash, Default)]
+pub struct Bibliography {
+    /// Sources to be listed, e.g. every source of the site. If `None`, the
+    /// sources cited on the page so far are listed.
+    pub sources: Option<Vec<Arc<Source>>>,
+}
+
+impl Bibliography {
+    /// Yields the citation style and the numbered entries to be listed, in
+    /// order.
+    fn entries<K>(
+        &self,
+        ctx: Context<K>,
+    ) -> (CitationStyle, Vec<(u32, Arc<Source>)>)
+    where
+        K: ComponentKind + ?Sized,
+    {
+        let citations = ctx.citations();
+        let style = citations.map_or(CitationStyle::default(), |c| c.style());
+        let sources = match &self.sources {
+            Some(sources) => sources.clone(),
+            None => citations.map(|c| c.cited()).unwrap_or_default(),
+        };
+        let mut entries = sources
+            .into_iter()
+            .enumerate()
+            .map(|(i, source)| match citations {
+                Some(citations) => (citations.cite(&source), source),
+                None => (i as u32 + 1, source),
+            })
+            .collect::<Vec<_>>();
+        match style {
+            CitationStyle::AuthorYear => {
+                entries.sort_by(|(_, left), (_, right)| {
+                    (left.full_authors(), left.year, &left.title).cmp(&(
+                        right.full_authors(),
+                        right.year,
+                        &right.title,
+                    ))
+                })
+            },
+            CitationStyle::Numeric => {
+                entries.sort_by_key(|(number, _)| *number)
+            },
+        }
+        (style, entries)
+    }
+}
+
+impl Component for Bibliography {
+    type Kind = BlockComponent;
+}
+
+impl Render<Html> for Bibliography {
+    fn render(
+        &self,
+        renderer: &mut Renderer<Html>,
+        ctx: Context<Self::Kind>,
+    ) -> fmt::Result {
+        let (style, entries) = self.entries(ctx);
+        if entries.is_empty() {
+            return Ok(());
+        }
+        let tag = match style {
+            CitationStyle::AuthorYear => "ul",
+            CitationStyle::Numeric => "ol",
+        };
+        write!(renderer, "<{} class=\"pedia-bibliography\">", tag)?;
+        for (number, source) in entries {
+            let id = source.entry_id();
+            ctx.define(&id);
+            write!(renderer, "<li class=\"pedia-source\" id=\"{}\"", id)?;
+            if style == CitationStyle::Numeric {
+                write!(renderer, " value=\"{}\"", number)?;
+            }
+            renderer.write_str(">")?;
+            render_entry(&source, style, renderer, ctx)?;
+            renderer.write_str("</li>")?;
+        }
+        write!(renderer, "</{}>", tag)?;
+        Ok(())
+    }
+}
+
+impl Render<Markdown> for Bibliography {
+    fn render(
+        &self,
+        renderer: &mut Renderer<Markdown>,
+        ctx: Context<Self::Kind>,
+    ) -> fmt::Result {
+        let (style, entries) = self.entries(ctx);
+        if entries.is_empty() {
+            return Ok(());
+        }
+        for (number, source) in entries {
+            let id = source.entry_id();
+            ctx.define(&id);
+            match style {
+                CitationStyle::AuthorYear => renderer.write_str("- ")?,
+                CitationStyle::Numeric => write!(renderer, "{}. ", number)?,
+            }
+            write!(renderer, "<span id=\"{}\"></span>", id)?;
+            render_entry(&source, style, renderer, ctx)?;
+            renderer.write_str("\n")?;
+        }
+        renderer.write_str("\n")?;
+        Ok(())
+    }
+}
+
+impl Render<Text> for Bibliography {
+    fn render(
+        &self,
+        renderer: &mut Renderer<Text>,
+        ctx: Context<Self::Kind>,
+    ) -> fmt::Result {
+        let (style, entries) = self.entries(ctx);
+        if entries.is_empty() {
+            return Ok(());
+        }
+        for (number, source) in entries {
+            ctx.define(&source.entry_id());
+            if style == CitationStyle::Numeric {
+                write!(renderer, "[{}] ", number)?;
+            }
+            render_entry(&source, style, renderer, ctx)?;
+            renderer.write_str("\n")?;
+        }
+        renderer.write_str("\n")?;
+        Ok(())
+    }
+}
+
+impl Render<Latex> for Bibliography {
+    fn render(
+        &self,
+        renderer: &mut Renderer<Latex>,
+        ctx: Context<Self::Kind>,
+    ) -> fmt::Result {
+        let (style, entries) = self.entries(ctx);
+        if entries.is_empty() {
+            return Ok(());
+        }
+        let env = match style {
+            CitationStyle::AuthorYear => "itemize",
+            CitationStyle::Numeric => "enumerate",
+        };
+        writeln!(renderer, "\\begin{{{}}}", env)?;
+        for (_, source) in entries {
+            let id = source.entry_id();
+            ctx.define(&id);
+            let label = latex::label(ctx.location(), Some(&id));
+            write!(renderer, "\\item\\label{{{}}} ", label)?;
+            render_entry(&source, style, renderer, ctx)?;
+            renderer.write_str("\n")?;
+        }
+        write!(renderer, "\\end{{{}}}\n\n", env)?;
+        Ok(())
+    }
+}
+
+impl Render<Gemtext> for Bibliography {
+    fn render(
+        &self,
+        renderer: &mut Renderer<Gemtext>,
+        ctx: Context<Self::Kind>,
+    ) -> fmt::Result {
+        let (style, entries) = self.entries(ctx);
+        if entries.is_empty() {
+            return Ok(());
+        }
+        for (number, source) in entries {
+            ctx.define(&source.entry_id());
+            match style {
+                CitationStyle::AuthorYear => renderer.write_str("* ")?,
+                CitationStyle::Numeric => write!(renderer, "* [{}] ", number)?,
+            }
+            renderer.scoped(gemtext::Line, |renderer| {
+                render_entry(&source, style, renderer, ctx)
+            })?;
+            renderer.write_str("\n")?;
+        }
+        renderer.write_str("\n")?;
+        Ok(())
+    }
+}
+
+impl Visit for Bibliography {
+    fn visit<V>(&self, visitor: &mut V, ctx: Context<Self::Kind>)
+    where
+        V: Visitor + ?Sized,
+    {
+        visit_node(visitor, Node::Bibliography, ctx, |_| ());
+    }
+}
+
+/// Renders the bibliography entry of a source in the given style, e.g.
+/// "Knuth (1984). *The TeXbook*. Addison-Wesley." in the author-year style,
+/// or "Knuth. *The TeXbook*. Addison-Wesley, 1984." in the numeric style,
+/// followed by the URL of the source, if any.
+fn render_entry<W>(
+    source: &Source,
+    style: CitationStyle,
+    renderer: &mut Renderer<W>,
+    ctx: Context<BlockComponent>,
+) -> fmt::Result
+where
+    W: Format + ?Sized,
+    str: Render<W, Kind = InlineComponent>,
+    for<'a> Italic<&'a str>: Render<W, Kind = InlineComponent>,
+    for<'a> Link<&'a str>: Render<W, Kind = InlineComponent>,
+{
+    let ctx = ctx.with_kind(&InlineComponent);
+    let authors = source.full_authors();
+    let title = Italic(source.title.as_str());
+    match style {
+        CitationStyle::AuthorYear => {
+            if authors.is_empty() {
+                title.render(renderer, ctx)?;
+            } else {
+                authors.as_str().render(renderer, ctx)?;
+            }
+            renderer.write_str(" (")?;
+            source.year_label().as_str().render(renderer, ctx)?;
+            renderer.write_str(").")?;
+            if !authors.is_empty() {
+                renderer.write_str(" ")?;
+                title.render(renderer, ctx)?;
+                renderer.write_str(".")?;
+            }
+            if let Some(publisher) = &source.publisher {
+                renderer.write_str(" ")?;
+                publisher.as_str().render(renderer, ctx)?;
+                renderer.write_str(".")?;
+            }
+        },
+        CitationStyle::Numeric => {
+            if !authors.is_empty() {
+                authors.as_str().render(renderer, ctx)?;
+                renderer.write_str(". ")?;
+            }
+            title.render(renderer, ctx)?;
+            renderer.write_str(".")?;
+            if let Some(publisher) = &source.publisher {
+                renderer.write_str(" ")?;
+                publisher.as_str().render(renderer, ctx)?;
+                renderer.write_str(",")?;
+            }
+            renderer.write_str(" ")?;
+            source.year_label().as_str().render(renderer, ctx)?;
+            renderer.write_str(".")?;
+        },
+    }
+    if let Some(url) = &source.url {
+        renderer.write_str(" ")?;
+        Link { target: url.as_str(), location: Location::Url(url.clone()) }
+            .render(renderer, ctx)?;
+    }
+    Ok(())
+}
+
+#[cfg(test)]
+mod test {
+    use super::Bibliography;
+    use crate::{
+        component::{
+            block::text::Paragraph,
+            inline::cite::{Citation, CitationStyle, Citations, Cite, Source},
+            page::{Page, PageComponent},
+            section::Section,
+        },
+        harray,
+        location::{Id, InternalPath},
+        render::{
+            html::test::validate_html_document,
+            Context,
+            FullRender,
+            Html,
+            Markdown,
+            RenderAsDisplay,
+        },
+    };
+    use std::sync::Arc;
+
+    fn source(key: &str, author: &str, year: i32) -> Arc<Source> {
+        Arc::new(Source {
+            key: Id::new(key),
+            authors: vec![String::from(author)],
+            title: format!("On {}", key),
+            year: Some(year),
+            publisher: None,
+            url: None,
+        })
+    }
+
+    fn page(
+        sources: Option<Vec<Arc<Source>>>,
+    ) -> impl FullRender<Kind = PageComponent> {
+        let zeta = source("zeta", "Zeller", 1990);
+        let alpha = source("alpha", "Abel", 2001);
+        let cite = |source: &Arc<Source>, pages: Option<&str>| {
+            Cite(Citation {
+                source: source.clone(),
+                pages: pages.map(String::from),
+            })
+        };
+        Page::new(
+            harray![],
+            String::from("Foo"),
+            harray![],
+            Paragraph(harray![
+                "Foo ",
+                cite(&zeta, Some("p. 4")),
+                " ",
+                cite(&alpha, None),
+                " ",
+                cite(&zeta, None),
+            ]),
+            harray![Section {
+                title: "References",
+                id: None,
+                body: Bibliography { sources },
+                children: harray![],
+            }],
+        )
+    }
+
+    #[test]
+    fn html_author_year() {
+        let location = InternalPath::root();
+        let rendered = RenderAsDisplay::new(
+            page(None),
+            &mut Html,
+            Context::new(&location, &PageComponent),
+        )
+        .to_string();
+
+        validate_html_document(&rendered).unwrap();
+        assert!(rendered.contains(
+            "Foo <a class=\"pedia-cite\" href=\"#pedia-source-zeta\">\
+             (Zeller 1990, p. 4)</a> <a class=\"pedia-cite\" \
+             href=\"#pedia-source-alpha\">(Abel 2001)</a>"
+        ));
+        assert!(rendered.contains(
+            "<ul class=\"pedia-bibliography\"><li class=\"pedia-source\" \
+             id=\"pedia-source-alpha\">Abel (2001). <span \
+             class=\"pedia-italic\">On alpha</span>.</li><li \
+             class=\"pedia-source\" id=\"pedia-source-zeta\">Zeller (1990). \
+             <span class=\"pedia-italic\">On zeta</span>.</li></ul>"
+        ));
+    }
+
+    #[test]
+    fn html_numeric() {
+        let location = InternalPath::root();
+        let citations = Citations::new(CitationStyle::Numeric);
+        let rendered = RenderAsDisplay::new(
+            page(None),
+            &mut Html,
+            Context::new(&location, &PageComponent).with_citations(&citations),
+        )
+        .to_string();
+
+        validate_html_document(&rendered).unwrap();
+        assert!(rendered.contains(
+            "Foo <a class=\"pedia-cite\" href=\"#pedia-source-zeta\">\
+             [1, p. 4]</a> <a class=\"pedia-cite\" \
+             href=\"#pedia-source-alpha\">[2]</a> <a class=\"pedia-cite\" \
+             href=\"#pedia-source-zeta\">[1]</a>"
+        ));
+        assert!(rendered.contains(
+            "<ol class=\"pedia-bibliography\"><li class=\"pedia-source\" \
+             id=\"pedia-source-zeta\" value=\"1\">Zeller. <span \
+             class=\"pedia-italic\">On zeta</span>. 1990.</li><li \
+             class=\"pedia-source\" id=\"pedia-source-alpha\" \
+             value=\"2\">Abel. <span class=\"pedia-italic\">On alpha</span>. \
+             2001.</li></ol>"
+        ));
+    }
+
+    #[test]
+    fn html_numeric_explicit_sources() {
+        let location = InternalPath::root();
+        let citations = Citations::new(CitationStyle::Numeric);
+        let sources = vec![
+            source("alpha", "Abel", 2001),
+            source("omega", "Ohm", 1827),
+            source("zeta", "Zeller", 1990),
+        ];
+        let rendered = RenderAsDisplay::new(
+            page(Some(sources)),
+            &mut Html,
+            Context::new(&location, &PageComponent).with_citations(&citations),
+        )
+        .to_string();
+
+        validate_html_document(&rendered).unwrap();
+        assert!(rendered.contains(
+            "<ol class=\"pedia-bibliography\"><li class=\"pedia-source\" \
+             id=\"pedia-source-zeta\" value=\"1\">Zeller. <span \
+             class=\"pedia-italic\">On zeta</span>. 1990.</li><li \
+             class=\"pedia-source\" id=\"pedia-source-alpha\" \
+             value=\"2\">Abel. <span class=\"pedia-italic\">On alpha</span>. \
+             2001.</li><li class=\"pedia-source\" id=\"pedia-source-omega\" \
+             value=\"3\">Ohm. <span class=\"pedia-italic\">On omega</span>. \
+             1827.</li></ol>"
+        ));
+    }
+
+    #[test]
+    fn markdown_numeric() {
+        let location = InternalPath::root();
+        let citations = Citations::new(CitationStyle::Numeric);
+        let rendered = RenderAsDisplay::new(
+            page(None),
+            &mut Markdown::default(),
+            Context::new(&location, &PageComponent).with_citations(&citations),
+        )
+        .to_string();
+
+        assert!(rendered.contains("Foo [\\[1, p. 4\\]](#pedia-source-zeta)"));
+        assert!(rendered.contains(
+            "1. <span id=\"pedia-source-zeta\"></span>Zeller. _On zeta_. \
+             1990.\n2. <span id=\"pedia-source-alpha\"></span>Abel. \
+             _On alpha_. 2001."
+        ));
+    }
+}
@@ -0,0 +1,290 @@
+//! This module exports the breadcrumbs component.
+
+use super::BlockComponent;
+use crate::{
+    component::{inline::text::Link, Component, InlineComponent},
+    location::{InternalLoc, InternalPath, Location, INDEX_NAME},
+    render::{
+        Context,
+        Format,
+        Gemtext,
+        Html,
+        Latex,
+        Markdown,
+        Render,
+        Renderer,
+        Text,
+    },
+    visit::{visit_node, Node, Visit, Visitor},
+};
+use std::fmt::{self, Write};
+
+/// Shows where the page being rendered is in the site: links to the index
+/// pages of the directories containing the page, from the root, followed by
+/// the page itself, each one labelled with the title of its page. Titles are
+/// looked up in the registry of the site (see [`Context::registry`]), and
+/// directories without a registered index page are left out. Renders nothing
+/// without a registry, or if the page is the only one to show.
+///
+/// # HTML Classes
+///
+/// - `pedia-breadcrumbs` attached to a `<nav>` element, surrounding an `<ol>`
+///   element.
+/// - `pedia-breadcrumb` attached to `<li>` elements, the last one being the
+///   current page.
+#[derive(Debug, Clone, Copy, PartialEq, Eq, PartialOrd, Ord, Hash, Default)]
+pub struct Breadcrumbs;
+
+impl Component for Breadcrumbs {
+    type Kind = BlockComponent;
+}
+
+impl Render<Html> for Breadcrumbs {
+    fn render(
+        &self,
+        renderer: &mut Renderer<Html>,
+        ctx: Context<Self::Kind>,
+    ) -> fmt::Result {
+        let Some((ancestors, current)) = crumbs(ctx) else {
+            return Ok(());
+        };
+        renderer.write_str(
+            "<nav class=\"pedia-breadcrumbs\" aria-label=\"Breadcrumbs\"><ol>",
+        )?;
+        for link in &ancestors {
+            renderer.write_str("<li class=\"pedia-breadcrumb\">")?;
+            link.render(renderer, ctx.with_kind(&InlineComponent))?;
+            renderer.write_str("</li>")?;
+        }
+        renderer.write_str(
+            "<li class=\"pedia-breadcrumb\" aria-current=\"page\">",
+        )?;
+        current.render(renderer, ctx.with_kind(&InlineComponent))?;
+        renderer.write_str("</li></ol></nav>")?;
+        Ok(())
+    }
+}
+
+impl Render<Markdown> for Breadcrumbs {
+    fn render(
+        &self,
+        renderer: &mut Renderer<Markdown>,
+        ctx: Context<Self::Kind>,
+    ) -> fmt::Result {
+        render_trail(renderer, ctx, " > ")
+    }
+}
+
+impl Render<Text> for Breadcrumbs {
+    fn render(
+        &self,
+        renderer: &mut Renderer<Text>,
+        ctx: Context<Self::Kind>,
+    ) -> fmt::Result {
+        render_trail(renderer, ctx, " > ")
+    }
+}
+
+impl Render<Latex> for Breadcrumbs {
+    fn render(
+        &self,
+        renderer: &mut Renderer<Latex>,
+        ctx: Context<Self::Kind>,
+    ) -> fmt::Result {
+        render_trail(renderer, ctx, " \\textgreater{} ")
+    }
+}
+
+impl Render<Gemtext> for Breadcrumbs {
+    fn render(
+        &self,
+        renderer: &mut Renderer<Gemtext>,
+        ctx: Context<Self::Kind>,
+    ) -> fmt::Result {
+        render_trail(renderer, ctx, " > ")
+    }
+}
+
+impl Visit for Breadcrumbs {
+    fn visit<V>(&self, visitor: &mut V, ctx: Context<Self::Kind>)
+    where
+        V: Visitor + ?Sized,
+    {
+        visit_node(visitor, Node::Breadcrumbs, ctx, |_| ());
+    }
+}
+
+/// Renders the breadcrumbs as a paragraph of links separated by the given
+/// separator, for formats without a dedicated markup.
+fn render_trail<W>(
+    renderer: &mut Renderer<W>,
+    ctx: Context<BlockComponent>,
+    separator: &str,
+) -> fmt::Result
+where
+    W: Format + ?Sized,
+    for<'title> Link<&'title str>: Render<W, Kind = InlineComponent>,
+    str: Render<W, Kind = InlineComponent>,
+{
+    let Some((ancestors, current)) = crumbs(ctx) else {
+        return Ok(());
+    };
+    for link in &ancestors {
+        link.render(renderer, ctx.with_kind(&InlineComponent))?;
+        renderer.write_str(separator)?;
+    }
+    current.render(renderer, ctx.with_kind(&InlineComponent))?;
+    renderer.write_str("\n\n")?;
+    Ok(())
+}
+
+/// Computes the breadcrumbs of the page being rendered: links to the index
+/// pages of its ancestor directories, and the title of the page itself.
+/// Yields nothing if there is no registry, or nothing but the page to show.
+fn crumbs<'loc>(
+    ctx: Context<'loc, '_, BlockComponent>,
+) -> Option<(Vec<Link<&'loc str>>, &'loc str)> {
+    let registry = ctx.registry()?;
+    let location = ctx.location();
+    let current = registry.title(location)?;
+
+    // The index page of the directory of the page is the page itself when
+    // the page is an index, otherwise, it is an ancestor.
+    let fragments = &location.fragments;
+    let is_index = fragments
+        .last()
+        .is_some_and(|fragment| fragment.as_str() == INDEX_NAME);
+    let depth = match fragments.len() {
+        0 => 0,
+        len if is_index => len - 1,
+        len => len,
+    };
+
+    let ancestors = (0 .. depth)
+        .filter_map(|len| {
+            let fragments = fragments[.. len].to_vec();
+            let path = InternalPath { fragments }.append(INDEX_NAME);
+            let title = registry.title(&path)?;
+            let location = Location::Internal(InternalLoc::from(path));
+            Some(Link { target: title, location })
+        })
+        .collect::<Vec<_>>();
+
+    if ancestors.is_empty() {
+        None
+    } else {
+        Some((ancestors, current))
+    }
+}
+
+#[cfg(test)]
+mod test {
+    use super::Breadcrumbs;
+    use crate::{
+        component::BlockComponent,
+        location::InternalPath,
+        render::{
+            html::test::validate_html_fragment,
+            Context,
+            Gemtext,
+            Html,
+            Markdown,
+            RenderAsDisplay,
+            Text,
+        },
+        site::registry::Registry,
+    };
+
+    fn registry() -> Registry {
+        let mut registry = Registry::new();
+        registry.insert_title(
+            InternalPath::parse("index.html"),
+            String::from("Home"),
+        );
+        registry.insert_title(
+            InternalPath::parse("bar/index.html"),
+            String::from("Bar"),
+        );
+        registry.insert_title(
+            InternalPath::parse("bar/baz/index.html"),
+            String::from("Baz"),
+        );
+        registry.insert_title(
+            InternalPath::parse("bar/baz/qux/quux.html"),
+            String::from("Quux"),
+        );
+        registry
+    }
+
+    #[test]
+    fn html_links_ancestors() {
+        let registry = registry();
+        let location = InternalPath::parse("bar/baz/index.html");
+        let rendered = RenderAsDisplay::new(
+            Breadcrumbs,
+            &mut Html,
+            Context::new(&location, &BlockComponent).with_registry(&registry),
+        )
+        .to_string();
+
+        validate_html_fragment(&rendered).unwrap();
+        assert_eq!(
+            rendered,
+            "<nav class=\"pedia-breadcrumbs\" aria-label=\"Breadcrumbs\"><ol>\
+             <li class=\"pedia-breadcrumb\"><a class=\"pedia-link\" \
+             href=\"../../index.html\">Home</a></li><li \
+             class=\"pedia-breadcrumb\"><a class=\"pedia-link\" \
+             href=\"../../bar&#47;index.html\">Bar</a></li><li \
+             class=\"pedia-breadcrumb\" aria-current=\"page\">Baz</li></ol>\
+             </nav>"
+        );
+    }
+
+    #[test]
+    fn missing_index_skipped() {
+        let registry = registry();
+        let location = InternalPath::parse("bar/baz/qux/quux.html");
+        let rendered = RenderAsDisplay::new(
+            Breadcrumbs,
+            &mut Text::default(),
+            Context::new(&location, &BlockComponent).with_registry(&registry),
+        )
+        .to_string();
+
+        assert_eq!(rendered, "Home > Bar > Baz > Quux\n");
+    }
+
+    #[test]
+    fn markdown_and_gemtext_links() {
+        let registry = registry();
+        let location = InternalPath::parse("bar/index.html");
+        let context =
+            Context::new(&location, &BlockComponent).with_registry(&registry);
+
+        let markdown = RenderAsDisplay::new(
+            Breadcrumbs,
+            &mut Markdown::default(),
+            context,
+        )
+        .to_string();
+        assert_eq!(markdown, "[Home](../index.md) > Bar\n");
+
+        let gemtext =
+            RenderAsDisplay::new(Breadcrumbs, &mut Gemtext::default(), context)
+                .to_string();
+        assert_eq!(gemtext, "Home > Bar\n=> ../index.gmi Home\n\n");
+    }
+
+    #[test]
+    fn nothing_without_registry() {
+        let location = InternalPath::parse("bar/index.html");
+        let rendered = RenderAsDisplay::new(
+            Breadcrumbs,
+            &mut Html,
+            Context::new(&location, &BlockComponent),
+        )
+        .to_string();
+
+        assert_eq!(rendered, "");
+    }
+}
@@ -0,0 +1,478 @@
+//! This module exports the code block component, as well as syntax
+//! highlighting utilities.
+
+use super::BlockComponent;
+use crate::{
+    component::{Component, InlineComponent},
+    render::{
+        gemtext,
+        Context,
+        Gemtext,
+        Html,
+        Latex,
+        Markdown,
+        Render,
+        Renderer,
+        Text,
+    },
+    visit::{visit_node, Node, Visit, Visitor},
+};
+use std::{
+    fmt::{self, Write},
+    ops::RangeInclusive,
+};
+
+pub mod rust;
+
+/// Kind of a highlighted token of code.
+#[derive(Debug, Clone, Copy, PartialEq, Eq, PartialOrd, Ord, Hash, Default)]
+pub enum TokenKind {
+    /// Code that is not highlighted, such as whitespace and punctuation.
+    #[default]
+    Plain,
+    /// A keyword, e.g. `fn`.
+    Keyword,
+    /// A type name, e.g. `String`.
+    Type,
+    /// A name of a called function.
+    Function,
+    /// A name of an invoked macro, including the `!`.
+    Macro,
+    /// A lifetime or a label, e.g. `'a`.
+    Lifetime,
+    /// A string or a character literal.
+    String,
+    /// A number literal.
+    Number,
+    /// A comment.
+    Comment,
+    /// An attribute, e.g. `#[derive(Debug)]`.
+    Attribute,
+}
+
+impl TokenKind {
+    /// HTML class of tokens of this kind, `None` for plain code.
+    pub fn class(self) -> Option<&'static str> {
+        match self {
+            Self::Plain => None,
+            Self::Keyword => Some("pedia-code-keyword"),
+            Self::Type => Some("pedia-code-type"),
+            Self::Function => Some("pedia-code-function"),
+            Self::Macro => Some("pedia-code-macro"),
+            Self::Lifetime => Some("pedia-code-lifetime"),
+            Self::String => Some("pedia-code-string"),
+            Self::Number => Some("pedia-code-number"),
+            Self::Comment => Some("pedia-code-comment"),
+            Self::Attribute => Some("pedia-code-attribute"),
+        }
+    }
+}
+
+/// A highlighted token of code, borrowing its text from the code.
+#[derive(Debug, Clone, Copy, PartialEq, Eq, PartialOrd, Ord, Hash)]
+pub struct Token<'code> {
+    /// Kind of the token.
+    pub kind: TokenKind,
+    /// Text of the token.
+    pub text: &'code str,
+}
+
+/// A syntax highlighter, splitting code into tokens.
+pub trait Highlighter: fmt::Debug {
+    /// Splits the given code, written in the given language, into tokens
+    /// which, once concatenated, give the code back. Code in an unsupported
+    /// language should be yielded as plain tokens.
+    fn highlight<'code>(
+        &self,
+        language: &str,
+        code: &'code str,
+    ) -> Vec<Token<'code>>;
+}
+
+impl<H> Highlighter for &H
+where
+    H: Highlighter + ?Sized,
+{
+    fn highlight<'code>(
+        &self,
+        language: &str,
+        code: &'code str,
+    ) -> Vec<Token<'code>> {
+        (**self).highlight(language, code)
+    }
+}
+
+/// The builtin syntax highlighter. Supports Rust (as `rust` or `rs`), see
+/// [`rust::tokenize`].
+#[derive(Debug, Clone, Copy, PartialEq, Eq, PartialOrd, Ord, Hash, Default)]
+pub struct BuiltinHighlighter;
+
+impl Highlighter for BuiltinHighlighter {
+    fn highlight<'code>(
+        &self,
+        language: &str,
+        code: &'code str,
+    ) -> Vec<Token<'code>> {
+        match language {
+            "rust" | "rs" => rust::tokenize(code),
+            _ => vec![Token { kind: TokenKind::Plain, text: code }],
+        }
+    }
+}
+
+/// A block of source code, highlighted by the given highlighter.
+///
+/// In HTML, every line is wrapped in its own element, and each highlighted
+/// token is wrapped in an element whose class depends on its kind (see
+/// [`TokenKind::class`]). In Markdown and Gemtext, code is output in a fenced
+/// block tagged with the language, without line numbers. In plaintext, code
+/// is output as is, its indentation included.
+///
+/// # HTML Classes
+///
+/// - `pedia-code-block` attached to a `<pre>` element, whose `data-language`
+///   attribute is the language, if any.
+/// - `pedia-code-line` attached to a `<span>` element wrapping a line.
+/// - `pedia-code-highlighted` attached to the `<span>` element of a line in a
+///   highlighted range.
+/// - `pedia-code-line-number` attached to a `<span>` element, if line numbers
+///   are shown.
+/// - `pedia-code-*` attached to a `<span>` element wrapping a token, e.g.
+///   `pedia-code-keyword` (see [`TokenKind::class`]).
+#[derive(Debug, Clone, PartialEq, Eq, Hash, Default)]
+pub struct CodeBlock<H = BuiltinHighlighter>
+where
+    H: Highlighter,
+{
+    /// Language in which the code is written, e.g. `rust`. Empty if unknown.
+    pub language: String,
+    /// The code, with lines separated by `\n`.
+    pub code: String,
+    /// Whether line numbers are shown.
+    pub line_numbers: bool,
+    /// Ranges of lines to be highlighted, numbered from 1.
+    pub highlighted_lines: Vec<RangeInclusive<u32>>,
+    /// The syntax highlighter.
+    pub highlighter: H,
+}
+
+impl<H> CodeBlock<H>
+where
+    H: Highlighter,
+{
+    /// Yields the tokens of each line, a trailing newline being ignored.
+    fn lines(&self) -> Vec<Vec<Token<'_>>> {
+        let code = self.code.strip_suffix('\n').unwrap_or(&self.code);
+        let mut lines = vec![Vec::new()];
+        for token in self.highlighter.highlight(&self.language, code) {
+            for (i, text) in token.text.split('\n').enumerate() {
+                if i > 0 {
+                    lines.push(Vec::new());
+                }
+                if !text.is_empty() {
+                    if let Some(line) = lines.last_mut() {
+                        line.push(Token { kind: token.kind, text });
+                    }
+                }
+            }
+        }
+        lines
+    }
+
+    /// Tests whether the line of the given number is highlighted.
+    fn is_highlighted(&self, number: u32) -> bool {
+        self.highlighted_lines.iter().any(|range| range.contains(&number))
+    }
+
+    /// Yields the raw lines of the code, a trailing newline being ignored.
+    fn raw_lines(&self) -> impl Iterator<Item = &str> {
+        let code = self.code.strip_suffix('\n').unwrap_or(&self.code);
+        code.split('\n')
+    }
+
+    /// Yields the fence of the code in Markdown: three backticks, or more if
+    /// the code contains a run of three backticks or more.
+    fn fence(&self) -> String {
+        let longest = self
+            .code
+            .split(|ch| ch != '`')
+            .map(str::len)
+            .max()
+            .unwrap_or_default();
+        "`".repeat(longest.max(2) + 1)
+    }
+}
+
+impl<H> Component for CodeBlock<H>
+where
+    H: Highlighter,
+{
+    type Kind = BlockComponent;
+}
+
+impl<H> Render<Html> for CodeBlock<H>
+where
+    H: Highlighter,
+{
+    fn render(
+        &self,
+        renderer: &mut Renderer<Html>,
+        ctx: Context<Self::Kind>,
+    ) -> fmt::Result {
+        let ctx = ctx.with_kind(&InlineComponent);
+        renderer.write_str("<pre class=\"pedia-code-block\"")?;
+        if !self.language.is_empty() {
+            renderer.write_str(" data-language=\"")?;
+            self.language.render(renderer, ctx)?;
+            renderer.write_str("\"")?;
+        }
+        renderer.write_str("><code>")?;
+        let lines = self.lines();
+        let count = lines.len();
+        for (line, number) in lines.into_iter().zip(1 ..) {
+            renderer.write_str("<span class=\"pedia-code-line")?;
+            if self.is_highlighted(number) {
+                renderer.write_str(" pedia-code-highlighted")?;
+            }
+            renderer.write_str("\">")?;
+            if self.line_numbers {
+                write!(
+                    renderer,
+                    "<span class=\"pedia-code-line-number\">{}</span>",
+                    number
+                )?;
+            }
+            for token in line {
+                match token.kind.class() {
+                    Some(class) => {
+                        write!(renderer, "<span class=\"{}\">", class)?;
+                        token.text.render(renderer, ctx)?;
+                        renderer.write_str("</span>")?;
+                    },
+                    None => token.text.render(renderer, ctx)?,
+                }
+            }
+            if number as usize != count {
+                renderer.write_str("\n")?;
+            }
+            renderer.write_str("</span>")?;
+        }
+        renderer.write_str("</code></pre>")?;
+        Ok(())
+    }
+}
+
+impl<H> Render<Markdown> for CodeBlock<H>
+where
+    H: Highlighter,
+{
+    fn render(
+        &self,
+        renderer: &mut Renderer<Markdown>,
+        _ctx: Context<Self::Kind>,
+    ) -> fmt::Result {
+        let fence = self.fence();
+        writeln!(renderer, "{}{}", fence, self.language)?;
+        for line in self.raw_lines() {
+            writeln!(renderer, "{}", line)?;
+        }
+        write!(renderer, "{}\n\n", fence)?;
+        Ok(())
+    }
+}
+
+impl<H> Render<Text> for CodeBlock<H>
+where
+    H: Highlighter,
+{
+    fn render(
+        &self,
+        renderer: &mut Renderer<Text>,
+        _ctx: Context<Self::Kind>,
+    ) -> fmt::Result {
+        let lines = self.raw_lines().collect::<Vec<_>>();
+        let width = lines.len().to_string().len();
+        for (line, number) in lines.into_iter().zip(1 ..) {
+            if !self.highlighted_lines.is_empty() {
+                let marker =
+                    if self.is_highlighted(number) { ">" } else { " " };
+                write!(renderer, "{} ", marker)?;
+            }
+            if self.line_numbers {
+                write!(renderer, "{:>width$} | ", number, width = width)?;
+            }
+            writeln!(renderer, "{}", line)?;
+        }
+        renderer.write_str("\n")?;
+        Ok(())
+    }
+}
+
+impl<H> Render<Latex> for CodeBlock<H>
+where
+    H: Highlighter,
+{
+    fn render(
+        &self,
+        renderer: &mut Renderer<Latex>,
+        ctx: Context<Self::Kind>,
+    ) -> fmt::Result {
+        let ctx = ctx.with_kind(&InlineComponent);
+        renderer.write_str("{\\ttfamily\\obeyspaces\\obeylines ")?;
+        for (line, number) in self.lines().into_iter().zip(1 ..) {
+            if number > 1 {
+                renderer.write_str("\n")?;
+            }
+            if self.line_numbers {
+                write!(renderer, "{} ", number)?;
+            }
+            for token in line {
+                let command = match token.kind {
+                    TokenKind::Keyword => Some("textbf"),
+                    TokenKind::Comment => Some("textit"),
+                    _ => None,
+                };
+                match command {
+                    Some(command) => {
+                        write!(renderer, "\\{}{{", command)?;
+                        token.text.render(renderer, ctx)?;
+                        renderer.write_str("}")?;
+                    },
+                    None => token.text.render(renderer, ctx)?,
+                }
+            }
+        }
+        renderer.write_str("}\n\n")?;
+        Ok(())
+    }
+}
+
+impl<H> Render<Gemtext> for CodeBlock<H>
+where
+    H: Highlighter,
+{
+    fn render(
+        &self,
+        renderer: &mut Renderer<Gemtext>,
+        _ctx: Context<Self::Kind>,
+    ) -> fmt::Result {
+        writeln!(renderer, "```{}", self.language)?;
+        renderer.scoped(gemtext::Preformatted, |renderer| {
+            for line in self.raw_lines() {
+                writeln!(renderer, "{}", line)?;
+            }
+            Ok(())
+        })?;
+        renderer.write_str("```\n\n")?;
+        Ok(())
+    }
+}
+
+impl<H> Visit for CodeBlock<H>
+where
+    H: Highlighter,
+{
+    fn visit<V>(&self, visitor: &mut V, ctx: Context<Self::Kind>)
+    where
+        V: Visitor + ?Sized,
+    {
+        visit_node(visitor, Node::CodeBlock(&self.language), ctx, |_| ());
+    }
+}
+
+#[cfg(test)]
+mod test {
+    use super::CodeBlock;
+    use crate::{
+        component::BlockComponent,
+        location::InternalPath,
+        render::{
+            html::test::validate_html_fragment,
+            Context,
+            Html,
+            Markdown,
+            RenderAsDisplay,
+            Text,
+        },
+    };
+
+    fn code_block() -> CodeBlock {
+        CodeBlock {
+            language: String::from("rust"),
+            code: String::from(
+                "fn main() {\n    // <Hi>\n\n    println!(\"Hi\");\n}\n",
+            ),
+            line_numbers: true,
+            highlighted_lines: vec![2 ..= 3],
+            ..CodeBlock::default()
+        }
+    }
+
+    #[test]
+    fn html_tokens_and_lines() {
+        let location = InternalPath::root();
+        let rendered = RenderAsDisplay::new(
+            code_block(),
+            &mut Html,
+            Context::new(&location, &BlockComponent),
+        )
+        .to_string();
+
+        validate_html_fragment(&rendered).unwrap();
+        assert_eq!(
+            rendered,
+            "<pre class=\"pedia-code-block\" data-language=\"rust\"><code>\
+             <span class=\"pedia-code-line\"><span \
+             class=\"pedia-code-line-number\">1</span><span \
+             class=\"pedia-code-keyword\">fn</span> <span \
+             class=\"pedia-code-function\">main</span>() {\n</span>\
+             <span class=\"pedia-code-line pedia-code-highlighted\"><span \
+             class=\"pedia-code-line-number\">2</span>    <span \
+             class=\"pedia-code-comment\">&#47;&#47; &lt;Hi&gt;</span>\n\
+             </span><span class=\"pedia-code-line \
+             pedia-code-highlighted\"><span \
+             class=\"pedia-code-line-number\">3</span>\n</span><span \
+             class=\"pedia-code-line\"><span \
+             class=\"pedia-code-line-number\">4</span>    <span \
+             class=\"pedia-code-macro\">println!</span>(<span \
+             class=\"pedia-code-string\">&quot;Hi&quot;</span>);\n</span>\
+             <span class=\"pedia-code-line\"><span \
+             class=\"pedia-code-line-number\">5</span>}</span></code></pre>"
+        );
+    }
+
+    #[test]
+    fn markdown_fenced() {
+        let location = InternalPath::root();
+        let code_block: CodeBlock = CodeBlock {
+            language: String::from("md"),
+            code: String::from("```\n*a*\n```"),
+            ..CodeBlock::default()
+        };
+        let rendered = RenderAsDisplay::new(
+            code_block,
+            &mut Markdown::default(),
+            Context::new(&location, &BlockComponent),
+        )
+        .to_string();
+
+        assert_eq!(rendered, "````md\n```\n*a*\n```\n````\n");
+    }
+
+    #[test]
+    fn text_keeps_indentation() {
+        let location = InternalPath::root();
+        let rendered = RenderAsDisplay::new(
+            code_block(),
+            &mut Text::default(),
+            Context::new(&location, &BlockComponent),
+        )
+        .to_string();
+
+        assert_eq!(
+            rendered,
+            "  1 | fn main() {\n> 2 |     // <Hi>\n> 3 | \n  \
+             4 |     println!(\"Hi\");\n  5 | }\n"
+        );
+    }
+}
@@ -0,0 +1,356 @@
+//! This module provides the builtin tokenizer of Rust code.
+
+use super::{Token, TokenKind};
+
+/// Keywords of Rust, strict and reserved ones.
+const KEYWORDS: &[&str] = &[
+    "Self", "abstract", "as", "async", "await", "become", "box", "break",
+    "const", "continue", "crate", "do", "dyn", "else", "enum", "extern",
+    "false", "final", "fn", "for", "if", "impl", "in", "let", "loop", "macro",
+    "match", "mod", "move", "mut", "override", "priv", "pub", "ref", "return",
+    "self", "static", "struct", "super", "trait", "true", "try", "type",
+    "typeof", "union", "unsafe", "unsized", "use", "virtual", "where", "while",
+    "yield",
+];
+
+/// Primitive types of Rust.
+const PRIMITIVES: &[&str] = &[
+    "bool", "char", "f32", "f64", "i128", "i16", "i32", "i64", "i8", "isize",
+    "str", "u128", "u16", "u32", "u64", "u8", "usize",
+];
+
+/// Splits Rust code into tokens, recognizing keywords, types (primitive ones
+/// and capitalized names), called functions, macros, lifetimes, string,
+/// character and number literals, comments and attributes. Adjacent plain
+/// tokens are merged. Invalid code is tokenized on a best effort basis.
+pub fn tokenize(code: &str) -> Vec<Token<'_>> {
+    let mut lexer = Lexer { code, pos: 0, tokens: Vec::new() };
+    lexer.run();
+    lexer.tokens
+}
+
+/// State of the tokenization of some code.
+#[derive(Debug)]
+struct Lexer<'code> {
+    code: &'code str,
+    pos: usize,
+    tokens: Vec<Token<'code>>,
+}
+
+impl<'code> Lexer<'code> {
+    fn run(&mut self) {
+        while let Some(ch) = self.peek() {
+            let start = self.pos;
+            let kind = if ch.is_whitespace() {
+                self.eat_while(char::is_whitespace);
+                TokenKind::Plain
+            } else if self.rest().starts_with("//") {
+                self.eat_while(|ch| ch != '\n');
+                TokenKind::Comment
+            } else if self.rest().starts_with("/*") {
+                self.block_comment();
+                TokenKind::Comment
+            } else if self.rest().starts_with("#[")
+                || self.rest().starts_with("#![")
+            {
+                self.attribute();
+                TokenKind::Attribute
+            } else if let Some(kind) = self.literal() {
+                kind
+            } else if ch.is_ascii_digit() {
+                self.number();
+                TokenKind::Number
+            } else if is_ident_start(ch) {
+                self.identifier()
+            } else {
+                self.bump();
+                TokenKind::Plain
+            };
+            self.push(kind, start);
+        }
+    }
+
+    fn rest(&self) -> &'code str {
+        &self.code[self.pos ..]
+    }
+
+    fn peek(&self) -> Option<char> {
+        self.rest().chars().next()
+    }
+
+    fn peek_nth(&self, n: usize) -> Option<char> {
+        self.rest().chars().nth(n)
+    }
+
+    fn bump(&mut self) -> Option<char> {
+        let ch = self.peek()?;
+        self.pos += ch.len_utf8();
+        Some(ch)
+    }
+
+    fn eat_while<F>(&mut self, mut predicate: F)
+    where
+        F: FnMut(char) -> bool,
+    {
+        while self.peek().is_some_and(&mut predicate) {
+            self.bump();
+        }
+    }
+
+    /// Pushes a token from the given start to the current position, merging
+    /// it with the previous one if both are plain.
+    fn push(&mut self, kind: TokenKind, start: usize) {
+        if let Some(last) = self.tokens.last_mut() {
+            if kind == TokenKind::Plain && last.kind == TokenKind::Plain {
+                let last_start = start - last.text.len();
+                last.text = &self.code[last_start .. self.pos];
+                return;
+            }
+        }
+        self.tokens.push(Token { kind, text: &self.code[start .. self.pos] });
+    }
+
+    /// Tests whether the last tokens are the keyword `fn` and whitespace, i.e.
+    /// whether an identifier at the current position is a function name.
+    fn follows_fn(&self) -> bool {
+        match &self.tokens[..] {
+            [.., Token { kind: TokenKind::Keyword, text: "fn" }, space] => {
+                space.kind == TokenKind::Plain && space.text.trim().is_empty()
+            },
+            _ => false,
+        }
+    }
+
+    /// Consumes a possibly nested block comment.
+    fn block_comment(&mut self) {
+        let mut depth = 0u32;
+        while !self.rest().is_empty() {
+            if self.rest().starts_with("/*") {
+                self.pos += 2;
+                depth += 1;
+            } else if self.rest().starts_with("*/") {
+                self.pos += 2;
+                depth -= 1;
+                if depth == 0 {
+                    break;
+                }
+            } else {
+                self.bump();
+            }
+        }
+    }
+
+    /// Consumes an attribute, up to its closing bracket.
+    fn attribute(&mut self) {
+        let mut depth = 0u32;
+        while let Some(ch) = self.bump() {
+            match ch {
+                '[' => depth += 1,
+                ']' => {
+                    depth -= 1;
+                    if depth == 0 {
+                        break;
+                    }
+                },
+                _ => (),
+            }
+        }
+    }
+
+    /// Consumes a string, character or byte literal, or a lifetime, if any.
+    fn literal(&mut self) -> Option<TokenKind> {
+        let rest = self.rest();
+        let unprefixed = rest
+            .strip_prefix('b')
+            .or_else(|| rest.strip_prefix('c'))
+            .unwrap_or(rest);
+        let prefix_len = rest.len() - unprefixed.len();
+
+        if let Some(raw) = unprefixed.strip_prefix('r') {
+            let hashes = raw.len() - raw.trim_start_matches('#').len();
+            if raw[hashes ..].starts_with('"') {
+                self.pos += prefix_len + 1 + hashes + 1;
+                let terminator = format!("\"{}", "#".repeat(hashes));
+                match self.rest().find(&terminator) {
+                    Some(end) => self.pos += end + terminator.len(),
+                    None => self.pos = self.code.len(),
+                }
+                return Some(TokenKind::String);
+            }
+        }
+
+        if unprefixed.starts_with('"') {
+            self.pos += prefix_len + 1;
+            self.quoted('"');
+            return Some(TokenKind::String);
+        }
+
+        if unprefixed.starts_with('\'') {
+            let next = self.peek_nth(prefix_len + 1)?;
+            let after = self.peek_nth(prefix_len + 2);
+            if next == '\\' || after == Some('\'') {
+                self.pos += prefix_len + 1;
+                self.quoted('\'');
+                return Some(TokenKind::String);
+            }
+            if prefix_len == 0 && is_ident_start(next) {
+                self.bump();
+                self.eat_while(is_ident_continue);
+                return Some(TokenKind::Lifetime);
+            }
+        }
+
+        None
+    }
+
+    /// Consumes the rest of a quoted literal, handling escapes.
+    fn quoted(&mut self, quote: char) {
+        while let Some(ch) = self.bump() {
+            if ch == '\\' {
+                self.bump();
+            } else if ch == quote {
+                break;
+            }
+        }
+    }
+
+    /// Consumes a number literal, including its suffix, if any.
+    fn number(&mut self) {
+        loop {
+            self.eat_while(|ch| ch.is_ascii_alphanumeric() || ch == '_');
+            let is_fraction = self.peek() == Some('.')
+                && self.peek_nth(1).is_some_and(|ch| ch.is_ascii_digit());
+            if !is_fraction {
+                break;
+            }
+            self.bump();
+        }
+    }
+
+    /// Consumes an identifier, classifying it.
+    fn identifier(&mut self) -> TokenKind {
+        let start = self.pos;
+        self.eat_while(is_ident_continue);
+        let word = &self.code[start .. self.pos];
+        if KEYWORDS.contains(&word) {
+            TokenKind::Keyword
+        } else if self.peek() == Some('!') && self.peek_nth(1) != Some('=') {
+            self.bump();
+            TokenKind::Macro
+        } else if PRIMITIVES.contains(&word)
+            || word.starts_with(|ch: char| ch.is_uppercase())
+        {
+            TokenKind::Type
+        } else if self.peek() == Some('(')
+            || self.rest().starts_with("::<")
+            || self.follows_fn()
+        {
+            TokenKind::Function
+        } else {
+            TokenKind::Plain
+        }
+    }
+}
+
+fn is_ident_start(ch: char) -> bool {
+    ch == '_' || ch.is_alphabetic()
+}
+
+fn is_ident_continue(ch: char) -> bool {
+    ch == '_' || ch.is_alphanumeric()
+}
+
+#[cfg(test)]
+mod test {
+    use super::tokenize;
+    use crate::component::block::code::{Token, TokenKind};
+
+    fn kinds(code: &str) -> Vec<(TokenKind, &str)> {
+        tokenize(code)
+            .into_iter()
+            .map(|token| (token.kind, token.text))
+            .collect()
+    }
+
+    #[test]
+    fn tokens_give_code_back() {
+        let code = "#[derive(Debug)]\nstruct Foo<'a> {\n    bar: &'a str, \
+                    /* a /* nested */ comment */\n}\n\nfn main() {\n    \
+                    let x = r#\"raw \"string\"\"#;\n    println!(\"{}\\\"\", \
+                    b'\\'', 'c', 1.5e3, 0..10);\n}\n";
+        let tokens = tokenize(code);
+        let concatenated =
+            tokens.iter().map(|token| token.text).collect::<String>();
+        assert_eq!(concatenated, code);
+    }
+
+    #[test]
+    fn classifies_tokens() {
+        use TokenKind::*;
+
+        assert_eq!(
+            kinds("pub fn foo<'a>(x: &'a str) -> Vec<u8> { bar(x) }"),
+            [
+                (Keyword, "pub"),
+                (Plain, " "),
+                (Keyword, "fn"),
+                (Plain, " "),
+                (Function, "foo"),
+                (Plain, "<"),
+                (Lifetime, "'a"),
+                (Plain, ">(x: &"),
+                (Lifetime, "'a"),
+                (Plain, " "),
+                (Type, "str"),
+                (Plain, ") -> "),
+                (Type, "Vec"),
+                (Plain, "<"),
+                (Type, "u8"),
+                (Plain, "> { "),
+                (Function, "bar"),
+                (Plain, "(x) }"),
+            ]
+        );
+        assert_eq!(
+            kinds("#[test] // done\nvec![1_000u32, 0x1F, 2.5]"),
+            [
+                (Attribute, "#[test]"),
+                (Plain, " "),
+                (Comment, "// done"),
+                (Plain, "\n"),
+                (Macro, "vec!"),
+                (Plain, "["),
+                (Number, "1_000u32"),
+                (Plain, ", "),
+                (Number, "0x1F"),
+                (Plain, ", "),
+                (Number, "2.5"),
+                (Plain, "]"),
+            ]
+        );
+        assert_eq!(
+            kinds("let c = '\\n'; let s = b\"a\\\"b\";"),
+            [
+                (Keyword, "let"),
+                (Plain, " c = "),
+                (String, "'\\n'"),
+                (Plain, "; "),
+                (Keyword, "let"),
+                (Plain, " s = "),
+                (String, "b\"a\\\"b\""),
+                (Plain, ";"),
+            ]
+        );
+    }
+
+    #[test]
+    fn ranges_are_not_fractions() {
+        assert_eq!(
+            tokenize("0..n"),
+            [
+                Token { kind: TokenKind::Number, text: "0" },
+                Token { kind: TokenKind::Plain, text: "..n" },
+            ]
+        );
+    }
+}
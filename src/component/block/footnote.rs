@@ -0,0 +1,244 @@
+//! This module exports the footnote list component.
+
+use super::BlockComponent;
+use crate::{
+    component::Component,
+    render::{Context, Gemtext, Html, Latex, Markdown, Render, Renderer, Text},
+    visit::{visit_node, Node, Visit, Visitor},
+};
+use std::fmt::{self, Write};
+
+/// Lists the footnotes of the page (see
+/// [`Footnote`](crate::component::inline::footnote::Footnote)) rendered so
+/// far and not listed yet, e.g. as the body of a "Notes" section. Footnotes
+/// still unlisted at the end of a page are listed there. Renders nothing if
+/// there are no footnotes to list, or in LaTeX, where footnotes are listed by
+/// LaTeX itself.
+///
+/// # HTML Classes
+///
+/// - `pedia-footnotes` attached to an `<ol>` element.
+/// - `pedia-footnote` attached to a `<li>` element, whose ID is
+///   `pedia-footnote-` followed by the number.
+/// - `pedia-footnote-back` attached to an `<a>` element, linking back to the
+///   reference of the footnote.
+#[derive(Debug, Clone, Copy, PartialEq, Eq, PartialOrd, Ord, Hash, Default)]
+pub struct FootnoteList;
+
+impl Component for FootnoteList {
+    type Kind = BlockComponent;
+}
+
+impl Render<Html> for FootnoteList {
+    fn render(
+        &self,
+        renderer: &mut Renderer<Html>,
+        ctx: Context<Self::Kind>,
+    ) -> fmt::Result {
+        let footnotes = take(ctx);
+        if footnotes.is_empty() {
+            return Ok(());
+        }
+        renderer.write_str("<ol class=\"pedia-footnotes\">")?;
+        for (number, contents) in footnotes {
+            write!(
+                renderer,
+                "<li class=\"pedia-footnote\" id=\"pedia-footnote-{0}\" \
+                 value=\"{0}\"><a class=\"pedia-footnote-back\" \
+                 href=\"#pedia-footnote-ref-{0}\">^</a> {1}</li>",
+                number, contents
+            )?;
+        }
+        renderer.write_str("</ol>")?;
+        Ok(())
+    }
+}
+
+impl Render<Markdown> for FootnoteList {
+    fn render(
+        &self,
+        renderer: &mut Renderer<Markdown>,
+        ctx: Context<Self::Kind>,
+    ) -> fmt::Result {
+        let footnotes = take(ctx);
+        if footnotes.is_empty() {
+            return Ok(());
+        }
+        for (number, contents) in footnotes {
+            writeln!(renderer, "[^{}]: {}", number, contents)?;
+        }
+        renderer.write_str("\n")?;
+        Ok(())
+    }
+}
+
+impl Render<Text> for FootnoteList {
+    fn render(
+        &self,
+        renderer: &mut Renderer<Text>,
+        ctx: Context<Self::Kind>,
+    ) -> fmt::Result {
+        let footnotes = take(ctx);
+        if footnotes.is_empty() {
+            return Ok(());
+        }
+        for (number, contents) in footnotes {
+            writeln!(renderer, "[{}] {}", number, contents)?;
+        }
+        renderer.write_str("\n")?;
+        Ok(())
+    }
+}
+
+impl Render<Latex> for FootnoteList {
+    fn render(
+        &self,
+        _renderer: &mut Renderer<Latex>,
+        _ctx: Context<Self::Kind>,
+    ) -> fmt::Result {
+        Ok(())
+    }
+}
+
+impl Render<Gemtext> for FootnoteList {
+    fn render(
+        &self,
+        renderer: &mut Renderer<Gemtext>,
+        ctx: Context<Self::Kind>,
+    ) -> fmt::Result {
+        let footnotes = take(ctx);
+        if footnotes.is_empty() {
+            return Ok(());
+        }
+        for (number, contents) in footnotes {
+            writeln!(renderer, "[{}] {}", number, contents)?;
+        }
+        renderer.write_str("\n")?;
+        Ok(())
+    }
+}
+
+impl Visit for FootnoteList {
+    fn visit<V>(&self, visitor: &mut V, ctx: Context<Self::Kind>)
+    where
+        V: Visitor + ?Sized,
+    {
+        visit_node(visitor, Node::FootnoteList, ctx, |_| ());
+    }
+}
+
+/// Takes the footnotes to be listed from the context, if any.
+fn take(ctx: Context<BlockComponent>) -> Vec<(u32, String)> {
+    ctx.footnotes().map(|footnotes| footnotes.take()).unwrap_or_default()
+}
+
+#[cfg(test)]
+mod test {
+    use super::FootnoteList;
+    use crate::{
+        component::{
+            block::text::Paragraph,
+            inline::footnote::Footnote,
+            page::{Page, PageComponent},
+            section::Section,
+        },
+        harray,
+        location::InternalPath,
+        render::{
+            html::test::validate_html_document,
+            Context,
+            FullRender,
+            Html,
+            Markdown,
+            RenderAsDisplay,
+            Text,
+        },
+    };
+
+    fn page() -> impl FullRender<Kind = PageComponent> {
+        Page::new(
+            harray![],
+            String::from("Foo"),
+            harray![],
+            Paragraph(harray!["Foo", Footnote("First."), "."]),
+            harray![
+                Section {
+                    title: "Bar",
+                    id: None,
+                    body: Paragraph(harray!["Bar", Footnote("Second.")]),
+                    children: harray![],
+                },
+                Section {
+                    title: "Notes",
+                    id: None,
+                    body: FootnoteList,
+                    children: harray![],
+                },
+                Section {
+                    title: "Baz",
+                    id: None,
+                    body: Paragraph(harray!["Baz", Footnote("Third.")]),
+                    children: harray![],
+                },
+            ],
+        )
+    }
+
+    #[test]
+    fn html_links_both_ways() {
+        let location = InternalPath::root();
+        let rendered = RenderAsDisplay::new(
+            page(),
+            &mut Html,
+            Context::new(&location, &PageComponent),
+        )
+        .to_string();
+
+        validate_html_document(&rendered).unwrap();
+        assert!(rendered.contains(
+            "Foo<sup class=\"pedia-footnote-ref\" id=\"pedia-footnote-ref-1\">\
+             <a href=\"#pedia-footnote-1\">[1]</a></sup>."
+        ));
+        assert!(rendered.contains(
+            "<li class=\"pedia-footnote\" id=\"pedia-footnote-2\" \
+             value=\"2\"><a class=\"pedia-footnote-back\" \
+             href=\"#pedia-footnote-ref-2\">^</a> Second.</li></ol>"
+        ));
+        assert!(rendered.ends_with(
+            "href=\"#pedia-footnote-ref-3\">^</a> Third.</li></ol>\
+             </div></div></body></html>"
+        ));
+    }
+
+    #[test]
+    fn markdown_footnotes() {
+        let location = InternalPath::root();
+        let rendered = RenderAsDisplay::new(
+            page(),
+            &mut Markdown::default(),
+            Context::new(&location, &PageComponent),
+        )
+        .to_string();
+
+        assert_eq!(
+            rendered,
+            "\n\n# Foo\n\nFoo[^1].\n\n## Bar\n\nBar[^2]\n\n\
+             ## Notes\n\n[^1]: First.\n[^2]: Second.\n\n\
+             ## Baz\n\nBaz[^3]\n\n[^3]: Third.\n"
+        );
+    }
+
+    #[test]
+    fn text_footnotes() {
+        let location = InternalPath::root();
+        let rendered = RenderAsDisplay::new(
+            page(),
+            &mut Text::default(),
+            Context::new(&location, &PageComponent),
+        )
+        .to_string();
+
+        assert!(rendered.contains("Foo[1]."));
+        assert!(rendered.ends_with("Baz[3]\n\n[3] Third.\n"));
+    }
+}
@@ -0,0 +1,1022 @@
+//! This module exports the infobox component, as well as its rows.
+
+use super::{
+    media::Image,
+    table::{Cell, CellAttrs, Row, RowComponent},
+    BlockComponent,
+    InlineBlock,
+};
+use crate::{
+    component::{Component, ComponentKind, InlineComponent},
+    harray,
+    hseq::IntoIterRef,
+    render::{
+        gemtext,
+        Context,
+        Format,
+        Gemtext,
+        Html,
+        Latex,
+        Markdown,
+        Render,
+        Renderer,
+        Text,
+    },
+    visit::{visit_node, Node, Visit, Visitor},
+};
+use std::{
+    cmp::Ordering,
+    fmt::{self, Write},
+    hash::{Hash, Hasher},
+};
+
+/// Separates the label from the content in the plaintext rendering of a row,
+/// before labels are aligned by the infobox.
+const LABEL_END: char = '\u{1f}';
+
+/// Infobox row component kind. Components of this kind are usable as rows in
+/// an infobox.
+#[derive(Debug, Clone, Copy, PartialEq, Eq, PartialOrd, Ord, Hash, Default)]
+pub struct InfoboxRowComponent;
+
+impl ComponentKind for InfoboxRowComponent {}
+
+/// Where an infobox is placed relative to the text around it.
+#[derive(Debug, Clone, Copy, PartialEq, Eq, PartialOrd, Ord, Hash, Default)]
+pub enum InfoboxFloat {
+    /// Floated to the right of the text.
+    #[default]
+    Right,
+    /// Floated to the left of the text.
+    Left,
+    /// Not floated, i.e. a block of its own.
+    None,
+}
+
+impl InfoboxFloat {
+    /// HTML class of infoboxes placed this way, if any.
+    pub fn class(self) -> Option<&'static str> {
+        match self {
+            Self::Right => Some("pedia-infobox-float-right"),
+            Self::Left => Some("pedia-infobox-float-left"),
+            Self::None => None,
+        }
+    }
+}
+
+/// A row of an infobox, labelling some content. Inline content can be
+/// wrapped in an [`InlineBlock`].
+///
+/// # HTML Classes
+///
+/// - `pedia-infobox-row` attached to a `<tr>` element.
+/// - `pedia-infobox-label` attached to a `<th>` element.
+/// - `pedia-infobox-content` attached to a `<td>` element.
+#[derive(Debug, Clone, Copy, PartialEq, Eq, PartialOrd, Ord, Hash, Default)]
+pub struct InfoboxRow<L, C>
+where
+    L: Component<Kind = InlineComponent>,
+    C: Component<Kind = BlockComponent>,
+{
+    /// The label, e.g. "Born".
+    pub label: L,
+    /// The labelled content.
+    pub content: C,
+}
+
+impl<L, C> Component for InfoboxRow<L, C>
+where
+    L: Component<Kind = InlineComponent>,
+    C: Component<Kind = BlockComponent>,
+{
+    type Kind = InfoboxRowComponent;
+}
+
+impl<L, C> Render<Html> for InfoboxRow<L, C>
+where
+    L: Render<Html, Kind = InlineComponent>,
+    C: Render<Html, Kind = BlockComponent>,
+{
+    fn render(
+        &self,
+        renderer: &mut Renderer<Html>,
+        ctx: Context<Self::Kind>,
+    ) -> fmt::Result {
+        renderer.write_str(
+            "<tr class=\"pedia-infobox-row\"><th class=\"pedia-infobox-label\" \
+             scope=\"row\">",
+        )?;
+        self.label.render(renderer, ctx.with_kind(&InlineComponent))?;
+        renderer.write_str("</th><td class=\"pedia-infobox-content\">")?;
+        self.content.render(renderer, ctx.with_kind(&BlockComponent))?;
+        renderer.write_str("</td></tr>")?;
+        Ok(())
+    }
+}
+
+impl<L, C> Render<Markdown> for InfoboxRow<L, C>
+where
+    L: Render<Markdown, Kind = InlineComponent>,
+    C: Render<Markdown, Kind = BlockComponent>,
+{
+    fn render(
+        &self,
+        renderer: &mut Renderer<Markdown>,
+        ctx: Context<Self::Kind>,
+    ) -> fmt::Result {
+        let label = render_lines::<_, Markdown, _>(
+            &self.label,
+            ctx.with_kind(&InlineComponent),
+        )?;
+        let content = render_lines::<_, Markdown, _>(
+            &self.content,
+            ctx.with_kind(&BlockComponent),
+        )?;
+        writeln!(
+            renderer,
+            "| {} | {} |",
+            markdown_cell(&label),
+            markdown_cell(&content)
+        )
+    }
+}
+
+impl<L, C> Render<Text> for InfoboxRow<L, C>
+where
+    L: Render<Text, Kind = InlineComponent>,
+    C: Render<Text, Kind = BlockComponent>,
+{
+    fn render(
+        &self,
+        renderer: &mut Renderer<Text>,
+        ctx: Context<Self::Kind>,
+    ) -> fmt::Result {
+        let label = render_lines::<_, Text, _>(
+            &self.label,
+            ctx.with_kind(&InlineComponent),
+        )?
+        .join(" ");
+        let content = render_lines::<_, Text, _>(
+            &self.content,
+            ctx.with_kind(&BlockComponent),
+        )?;
+        write!(renderer, "{}{}", label, LABEL_END)?;
+        if content.is_empty() {
+            renderer.write_str("\n")?;
+        }
+        for (i, line) in content.iter().enumerate() {
+            if i > 0 {
+                renderer.write_char(LABEL_END)?;
+            }
+            writeln!(renderer, "{}", line)?;
+        }
+        Ok(())
+    }
+}
+
+impl<L, C> Render<Latex> for InfoboxRow<L, C>
+where
+    L: Render<Latex, Kind = InlineComponent>,
+    C: Render<Latex, Kind = BlockComponent>,
+{
+    fn render(
+        &self,
+        renderer: &mut Renderer<Latex>,
+        ctx: Context<Self::Kind>,
+    ) -> fmt::Result {
+        let row = Row(harray![
+            Cell {
+                child: InlineBlock(&self.label),
+                attrs: CellAttrs { header: true, ..CellAttrs::default() },
+            },
+            Cell::from(&self.content),
+        ]);
+        row.render(renderer, ctx.with_kind(&RowComponent))
+    }
+}
+
+impl<L, C> Render<Gemtext> for InfoboxRow<L, C>
+where
+    L: Render<Gemtext, Kind = InlineComponent>,
+    C: Render<Gemtext, Kind = BlockComponent>,
+{
+    fn render(
+        &self,
+        renderer: &mut Renderer<Gemtext>,
+        ctx: Context<Self::Kind>,
+    ) -> fmt::Result {
+        renderer.write_str("* ")?;
+        renderer.scoped(gemtext::Line, |renderer| {
+            self.label.render(renderer, ctx.with_kind(&InlineComponent))?;
+            renderer.write_str(": ")?;
+            self.content.render(renderer, ctx.with_kind(&BlockComponent))
+        })?;
+        renderer.write_str("\n")?;
+        Ok(())
+    }
+}
+
+impl<L, C> Visit for InfoboxRow<L, C>
+where
+    L: Visit<Kind = InlineComponent>,
+    C: Visit<Kind = BlockComponent>,
+{
+    fn visit<V>(&self, visitor: &mut V, ctx: Context<Self::Kind>)
+    where
+        V: Visitor + ?Sized,
+    {
+        visit_node(visitor, Node::InfoboxRow, ctx, |visitor| {
+            self.label.visit(visitor, ctx.with_kind(&InlineComponent));
+            self.content.visit(visitor, ctx.with_kind(&BlockComponent));
+        });
+    }
+}
+
+/// A group of rows of an infobox under a subheading. The rows could be an
+/// array, a vec, or anything that iterates by ref yielding infobox row
+/// components.
+///
+/// # HTML Classes
+///
+/// - `pedia-infobox-heading` attached to a `<th>` element spanning the whole
+///   width of the infobox, inside a `<tr>` element.
+pub struct InfoboxGroup<H, R>
+where
+    H: Component<Kind = InlineComponent>,
+    R: IntoIterRef,
+    <R as IntoIterRef>::Item: Component<Kind = InfoboxRowComponent>,
+{
+    /// The subheading of the group.
+    pub heading: H,
+    /// Rows of the group.
+    pub rows: R,
+}
+
+impl<H, R> fmt::Debug for InfoboxGroup<H, R>
+where
+    H: Component<Kind = InlineComponent>,
+    R: IntoIterRef,
+    <R as IntoIterRef>::Item: Component<Kind = InfoboxRowComponent>,
+{
+    fn fmt(&self, fmtr: &mut fmt::Formatter) -> fmt::Result {
+        let mut debug_fmtr = fmtr.debug_struct("InfoboxGroup");
+        debug_fmtr.field("heading", &self.heading);
+        for (i, element) in self.rows.iter().enumerate() {
+            debug_fmtr.field(&format!("rows[{}]", i), &element);
+        }
+        debug_fmtr.finish()
+    }
+}
+
+impl<H, R> Clone for InfoboxGroup<H, R>
+where
+    H: Component<Kind = InlineComponent> + Clone,
+    R: IntoIterRef + Clone,
+    <R as IntoIterRef>::Item: Component<Kind = InfoboxRowComponent>,
+{
+    fn clone(&self) -> Self {
+        Self { heading: self.heading.clone(), rows: self.rows.clone() }
+    }
+}
+
+impl<H, R> Copy for InfoboxGroup<H, R>
+where
+    H: Component<Kind = InlineComponent> + Copy,
+    R: IntoIterRef + Copy,
+    <R as IntoIterRef>::Item: Component<Kind = InfoboxRowComponent>,
+{
+}
+
+impl<H, R> PartialEq for InfoboxGroup<H, R>
+where
+    H: Component<Kind = InlineComponent> + PartialEq,
+    R: IntoIterRef,
+    <R as IntoIterRef>::Item: Component<Kind = InfoboxRowComponent> + PartialEq,
+{
+    fn eq(&self, other: &Self) -> bool {
+        self.heading == other.heading && self.rows.iter().eq(other.rows.iter())
+    }
+}
+
+impl<H, R> Eq for InfoboxGroup<H, R>
+where
+    H: Component<Kind = InlineComponent> + Eq,
+    R: IntoIterRef,
+    <R as IntoIterRef>::Item: Component<Kind = InfoboxRowComponent> + Eq,
+{
+}
+
+impl<H, R> PartialOrd for InfoboxGroup<H, R>
+where
+    H: Component<Kind = InlineComponent> + PartialOrd,
+    R: IntoIterRef,
+    <R as IntoIterRef>::Item:
+        Component<Kind = InfoboxRowComponent> + PartialOrd,
+{
+    fn partial_cmp(&self, other: &Self) -> Option<Ordering> {
+        let ordering = self
+            .heading
+            .partial_cmp(&other.heading)?
+            .then(self.rows.iter().partial_cmp(other.rows.iter())?);
+        Some(ordering)
+    }
+}
+
+impl<H, R> Ord for InfoboxGroup<H, R>
+where
+    H: Component<Kind = InlineComponent> + Ord,
+    R: IntoIterRef,
+    <R as IntoIterRef>::Item: Component<Kind = InfoboxRowComponent> + Ord,
+{
+    fn cmp(&self, other: &Self) -> Ordering {
+        self.heading
+            .cmp(&other.heading)
+            .then_with(|| self.rows.iter().cmp(other.rows.iter()))
+    }
+}
+
+impl<H, R> Hash for InfoboxGroup<H, R>
+where
+    H: Component<Kind = InlineComponent> + Hash,
+    R: IntoIterRef,
+    <R as IntoIterRef>::Item: Component<Kind = InfoboxRowComponent> + Hash,
+{
+    fn hash<S>(&self, state: &mut S)
+    where
+        S: Hasher,
+    {
+        self.heading.hash(state);
+        for (i, row) in self.rows.iter().enumerate() {
+            i.hash(state);
+            row.hash(state);
+        }
+    }
+}
+
+impl<H, R> Default for InfoboxGroup<H, R>
+where
+    H: Component<Kind = InlineComponent> + Default,
+    R: IntoIterRef + Default,
+    <R as IntoIterRef>::Item: Component<Kind = InfoboxRowComponent>,
+{
+    fn default() -> Self {
+        Self { heading: H::default(), rows: R::default() }
+    }
+}
+
+impl<H, R> Component for InfoboxGroup<H, R>
+where
+    H: Component<Kind = InlineComponent>,
+    R: IntoIterRef,
+    <R as IntoIterRef>::Item: Component<Kind = InfoboxRowComponent>,
+{
+    type Kind = InfoboxRowComponent;
+}
+
+impl<H, R> Render<Html> for InfoboxGroup<H, R>
+where
+    H: Render<Html, Kind = InlineComponent>,
+    R: IntoIterRef,
+    <R as IntoIterRef>::Item: Render<Html, Kind = InfoboxRowComponent>,
+{
+    fn render(
+        &self,
+        renderer: &mut Renderer<Html>,
+        ctx: Context<Self::Kind>,
+    ) -> fmt::Result {
+        renderer.write_str(
+            "<tr><th class=\"pedia-infobox-heading\" colspan=\"2\" \
+             scope=\"colgroup\">",
+        )?;
+        self.heading.render(renderer, ctx.with_kind(&InlineComponent))?;
+        renderer.write_str("</th></tr>")?;
+        for row in self.rows.iter() {
+            row.render(renderer, ctx)?;
+        }
+        Ok(())
+    }
+}
+
+impl<H, R> Render<Markdown> for InfoboxGroup<H, R>
+where
+    H: Render<Markdown, Kind = InlineComponent>,
+    R: IntoIterRef,
+    <R as IntoIterRef>::Item: Render<Markdown, Kind = InfoboxRowComponent>,
+{
+    fn render(
+        &self,
+        renderer: &mut Renderer<Markdown>,
+        ctx: Context<Self::Kind>,
+    ) -> fmt::Result {
+        let heading = render_lines::<_, Markdown, _>(
+            &self.heading,
+            ctx.with_kind(&InlineComponent),
+        )?;
+        writeln!(renderer, "| **{}** | |", markdown_cell(&heading))?;
+        for row in self.rows.iter() {
+            row.render(renderer, ctx)?;
+        }
+        Ok(())
+    }
+}
+
+impl<H, R> Render<Text> for InfoboxGroup<H, R>
+where
+    H: Render<Text, Kind = InlineComponent>,
+    R: IntoIterRef,
+    <R as IntoIterRef>::Item: Render<Text, Kind = InfoboxRowComponent>,
+{
+    fn render(
+        &self,
+        renderer: &mut Renderer<Text>,
+        ctx: Context<Self::Kind>,
+    ) -> fmt::Result {
+        renderer.write_str("\n")?;
+        self.heading.render(renderer, ctx.with_kind(&InlineComponent))?;
+        renderer.write_str("\n")?;
+        for row in self.rows.iter() {
+            row.render(renderer, ctx)?;
+        }
+        Ok(())
+    }
+}
+
+impl<H, R> Render<Latex> for InfoboxGroup<H, R>
+where
+    H: Render<Latex, Kind = InlineComponent>,
+    R: IntoIterRef,
+    <R as IntoIterRef>::Item: Render<Latex, Kind = InfoboxRowComponent>,
+{
+    fn render(
+        &self,
+        renderer: &mut Renderer<Latex>,
+        ctx: Context<Self::Kind>,
+    ) -> fmt::Result {
+        heading_row(&self.heading)
+            .render(renderer, ctx.with_kind(&RowComponent))?;
+        for row in self.rows.iter() {
+            row.render(renderer, ctx)?;
+        }
+        Ok(())
+    }
+}
+
+impl<H, R> Render<Gemtext> for InfoboxGroup<H, R>
+where
+    H: Render<Gemtext, Kind = InlineComponent>,
+    R: IntoIterRef,
+    <R as IntoIterRef>::Item: Render<Gemtext, Kind = InfoboxRowComponent>,
+{
+    fn render(
+        &self,
+        renderer: &mut Renderer<Gemtext>,
+        ctx: Context<Self::Kind>,
+    ) -> fmt::Result {
+        renderer.scoped(gemtext::Line, |renderer| {
+            self.heading.render(renderer, ctx.with_kind(&InlineComponent))
+        })?;
+        renderer.write_str("\n")?;
+        for row in self.rows.iter() {
+            row.render(renderer, ctx)?;
+        }
+        Ok(())
+    }
+}
+
+impl<H, R> Visit for InfoboxGroup<H, R>
+where
+    H: Visit<Kind = InlineComponent>,
+    R: IntoIterRef,
+    <R as IntoIterRef>::Item: Visit<Kind = InfoboxRowComponent>,
+{
+    fn visit<V>(&self, visitor: &mut V, ctx: Context<Self::Kind>)
+    where
+        V: Visitor + ?Sized,
+    {
+        visit_node(visitor, Node::InfoboxGroup, ctx, |visitor| {
+            self.heading.visit(visitor, ctx.with_kind(&InlineComponent));
+            for row in self.rows.iter() {
+                row.visit(visitor, ctx);
+            }
+        });
+    }
+}
+
+/// A box summarizing the subject of an article, such as the one of
+/// encyclopedias, with a title, an optional image, and rows of labelled
+/// content, possibly grouped under subheadings (see [`InfoboxGroup`]). The
+/// rows could be an array, a vec, or anything that iterates by ref yielding
+/// infobox row components.
+///
+/// In Markdown, the infobox falls back to a table, and in plaintext, to lines
+/// of labels and contents, contents being aligned.
+///
+/// # HTML Classes
+///
+/// - `pedia-infobox` attached to an `<aside>` element.
+/// - `pedia-infobox-float-right` or `pedia-infobox-float-left` attached to
+///   the same element, depending on where the infobox is floated (see
+///   [`InfoboxFloat`]).
+/// - `pedia-infobox-title` attached to a `<caption>` element, inside a
+///   `<table>` element.
+/// - `pedia-infobox-image` attached to a `<td>` element spanning the whole
+///   width of the infobox, inside a `<tr>` element.
+pub struct Infobox<T, R>
+where
+    T: Component<Kind = InlineComponent>,
+    R: IntoIterRef,
+    <R as IntoIterRef>::Item: Component<Kind = InfoboxRowComponent>,
+{
+    /// Title of the infobox, usually the subject of the article.
+    pub title: T,
+    /// Image shown below the title, if any.
+    pub image: Option<Image>,
+    /// Where the infobox is placed.
+    pub float: InfoboxFloat,
+    /// Rows of the infobox.
+    pub rows: R,
+}
+
+impl<T, R> fmt::Debug for Infobox<T, R>
+where
+    T: Component<Kind = InlineComponent>,
+    R: IntoIterRef,
+    <R as IntoIterRef>::Item: Component<Kind = InfoboxRowComponent>,
+{
+    fn fmt(&self, fmtr: &mut fmt::Formatter) -> fmt::Result {
+        let mut debug_fmtr = fmtr.debug_struct("Infobox");
+        debug_fmtr
+            .field("title", &self.title)
+            .field("image", &self.image)
+            .field("float", &self.float);
+        for (i, element) in self.rows.iter().enumerate() {
+            debug_fmtr.field(&format!("rows[{}]", i), &element);
+        }
+        debug_fmtr.finish()
+    }
+}
+
+impl<T, R> Clone for Infobox<T, R>
+where
+    T: Component<Kind = InlineComponent> + Clone,
+    R: IntoIterRef + Clone,
+    <R as IntoIterRef>::Item: Component<Kind = InfoboxRowComponent>,
+{
+    fn clone(&self) -> Self {
+        Self {
+            title: self.title.clone(),
+            image: self.image.clone(),
+            float: self.float,
+            rows: self.rows.clone(),
+        }
+    }
+}
+
+impl<T, R> PartialEq for Infobox<T, R>
+where
+    T: Component<Kind = InlineComponent> + PartialEq,
+    R: IntoIterRef,
+    <R as IntoIterRef>::Item: Component<Kind = InfoboxRowComponent> + PartialEq,
+{
+    fn eq(&self, other: &Self) -> bool {
+        self.title == other.title
+            && self.image == other.image
+            && self.float == other.float
+            && self.rows.iter().eq(other.rows.iter())
+    }
+}
+
+impl<T, R> Eq for Infobox<T, R>
+where
+    T: Component<Kind = InlineComponent> + Eq,
+    R: IntoIterRef,
+    <R as IntoIterRef>::Item: Component<Kind = InfoboxRowComponent> + Eq,
+{
+}
+
+impl<T, R> PartialOrd for Infobox<T, R>
+where
+    T: Component<Kind = InlineComponent> + PartialOrd,
+    R: IntoIterRef,
+    <R as IntoIterRef>::Item:
+        Component<Kind = InfoboxRowComponent> + PartialOrd,
+{
+    fn partial_cmp(&self, other: &Self) -> Option<Ordering> {
+        let ordering = self
+            .title
+            .partial_cmp(&other.title)?
+            .then(self.image.partial_cmp(&other.image)?)
+            .then(self.float.partial_cmp(&other.float)?)
+            .then(self.rows.iter().partial_cmp(other.rows.iter())?);
+        Some(ordering)
+    }
+}
+
+impl<T, R> Ord for Infobox<T, R>
+where
+    T: Component<Kind = InlineComponent> + Ord,
+    R: IntoIterRef,
+    <R as IntoIterRef>::Item: Component<Kind = InfoboxRowComponent> + Ord,
+{
+    fn cmp(&self, other: &Self) -> Ordering {
+        self.title
+            .cmp(&other.title)
+            .then_with(|| self.image.cmp(&other.image))
+            .then_with(|| self.float.cmp(&other.float))
+            .then_with(|| self.rows.iter().cmp(other.rows.iter()))
+    }
+}
+
+impl<T, R> Hash for Infobox<T, R>
+where
+    T: Component<Kind = InlineComponent> + Hash,
+    R: IntoIterRef,
+    <R as IntoIterRef>::Item: Component<Kind = InfoboxRowComponent> + Hash,
+{
+    fn hash<H>(&self, state: &mut H)
+    where
+        H: Hasher,
+    {
+        self.title.hash(state);
+        self.image.hash(state);
+        self.float.hash(state);
+        for (i, row) in self.rows.iter().enumerate() {
+            i.hash(state);
+            row.hash(state);
+        }
+    }
+}
+
+impl<T, R> Default for Infobox<T, R>
+where
+    T: Component<Kind = InlineComponent> + Default,
+    R: IntoIterRef + Default,
+    <R as IntoIterRef>::Item: Component<Kind = InfoboxRowComponent>,
+{
+    fn default() -> Self {
+        Self {
+            title: T::default(),
+            image: None,
+            float: InfoboxFloat::default(),
+            rows: R::default(),
+        }
+    }
+}
+
+impl<T, R> Component for Infobox<T, R>
+where
+    T: Component<Kind = InlineComponent>,
+    R: IntoIterRef,
+    <R as IntoIterRef>::Item: Component<Kind = InfoboxRowComponent>,
+{
+    type Kind = BlockComponent;
+}
+
+impl<T, R> Render<Html> for Infobox<T, R>
+where
+    T: Render<Html, Kind = InlineComponent>,
+    R: IntoIterRef,
+    <R as IntoIterRef>::Item: Render<Html, Kind = InfoboxRowComponent>,
+{
+    fn render(
+        &self,
+        renderer: &mut Renderer<Html>,
+        ctx: Context<Self::Kind>,
+    ) -> fmt::Result {
+        renderer.write_str("<aside class=\"pedia-infobox")?;
+        if let Some(class) = self.float.class() {
+            write!(renderer, " {}", class)?;
+        }
+        renderer
+            .write_str("\"><table><caption class=\"pedia-infobox-title\">")?;
+        self.title.render(renderer, ctx.with_kind(&InlineComponent))?;
+        renderer.write_str("</caption>")?;
+        if let Some(image) = &self.image {
+            renderer.write_str(
+                "<tr><td class=\"pedia-infobox-image\" colspan=\"2\">",
+            )?;
+            image.render(renderer, ctx)?;
+            renderer.write_str("</td></tr>")?;
+        }
+        for row in self.rows.iter() {
+            row.render(renderer, ctx.with_kind(&InfoboxRowComponent))?;
+        }
+        renderer.write_str("</table></aside>")?;
+        Ok(())
+    }
+}
+
+impl<T, R> Render<Markdown> for Infobox<T, R>
+where
+    T: Render<Markdown, Kind = InlineComponent>,
+    R: IntoIterRef,
+    <R as IntoIterRef>::Item: Render<Markdown, Kind = InfoboxRowComponent>,
+{
+    fn render(
+        &self,
+        renderer: &mut Renderer<Markdown>,
+        ctx: Context<Self::Kind>,
+    ) -> fmt::Result {
+        let title = render_lines::<_, Markdown, _>(
+            &self.title,
+            ctx.with_kind(&InlineComponent),
+        )?;
+        write!(renderer, "| {} | |\n| --- | --- |\n", markdown_cell(&title))?;
+        if let Some(image) = &self.image {
+            let image = render_lines::<_, Markdown, _>(image, ctx)?;
+            writeln!(renderer, "| {} | |", markdown_cell(&image))?;
+        }
+        for row in self.rows.iter() {
+            row.render(renderer, ctx.with_kind(&InfoboxRowComponent))?;
+        }
+        renderer.write_str("\n")?;
+        Ok(())
+    }
+}
+
+impl<T, R> Render<Text> for Infobox<T, R>
+where
+    T: Render<Text, Kind = InlineComponent>,
+    R: IntoIterRef,
+    <R as IntoIterRef>::Item: Render<Text, Kind = InfoboxRowComponent>,
+{
+    fn render(
+        &self,
+        renderer: &mut Renderer<Text>,
+        ctx: Context<Self::Kind>,
+    ) -> fmt::Result {
+        self.title.render(renderer, ctx.with_kind(&InlineComponent))?;
+        renderer.write_str("\n")?;
+        if let Some(image) = &self.image {
+            image.render(renderer, ctx)?;
+            renderer.write_str("\n")?;
+        }
+
+        // Labels are aligned on the longest one, which is only known after
+        // rendering every row.
+        let mut format = Text::default();
+        let mut rows = String::new();
+        let mut rows_renderer = Renderer::new(&mut format, &mut rows);
+        for row in self.rows.iter() {
+            let ctx = ctx.with_kind(&InfoboxRowComponent);
+            row.render(&mut rows_renderer, ctx)?;
+        }
+        let width = rows
+            .lines()
+            .filter_map(|line| line.split_once(LABEL_END))
+            .map(|(label, _)| label.chars().count() + 1)
+            .max()
+            .unwrap_or_default();
+        for line in rows.lines() {
+            match line.split_once(LABEL_END) {
+                Some(("", content)) => {
+                    writeln!(renderer, "{:width$} {}", "", content)?
+                },
+                Some((label, content)) => writeln!(
+                    renderer,
+                    "{:width$} {}",
+                    format!("{}:", label),
+                    content
+                )?,
+                None => writeln!(renderer, "{}", line)?,
+            }
+        }
+        renderer.write_str("\n")?;
+        Ok(())
+    }
+}
+
+impl<T, R> Render<Latex> for Infobox<T, R>
+where
+    T: Render<Latex, Kind = InlineComponent>,
+    R: IntoIterRef,
+    <R as IntoIterRef>::Item: Render<Latex, Kind = InfoboxRowComponent>,
+{
+    fn render(
+        &self,
+        renderer: &mut Renderer<Latex>,
+        ctx: Context<Self::Kind>,
+    ) -> fmt::Result {
+        if let Some(image) = &self.image {
+            image.render(renderer, ctx)?;
+        }
+        renderer.write_str("\\begin{tabular}{ll}\n")?;
+        renderer.format_mut().begin_table();
+        let result = heading_row(&self.title)
+            .render(renderer, ctx.with_kind(&RowComponent))
+            .and_then(|()| {
+                self.rows.iter().try_for_each(|row| {
+                    row.render(renderer, ctx.with_kind(&InfoboxRowComponent))
+                })
+            });
+        renderer.format_mut().end_table();
+        result?;
+        renderer.write_str("\\end{tabular}\n\n")?;
+        Ok(())
+    }
+}
+
+impl<T, R> Render<Gemtext> for Infobox<T, R>
+where
+    T: Render<Gemtext, Kind = InlineComponent>,
+    R: IntoIterRef,
+    <R as IntoIterRef>::Item: Render<Gemtext, Kind = InfoboxRowComponent>,
+{
+    fn render(
+        &self,
+        renderer: &mut Renderer<Gemtext>,
+        ctx: Context<Self::Kind>,
+    ) -> fmt::Result {
+        renderer.scoped(gemtext::Line, |renderer| {
+            self.title.render(renderer, ctx.with_kind(&InlineComponent))
+        })?;
+        renderer.write_str("\n")?;
+        if let Some(image) = &self.image {
+            image.render(renderer, ctx)?;
+        }
+        for row in self.rows.iter() {
+            row.render(renderer, ctx.with_kind(&InfoboxRowComponent))?;
+        }
+        renderer.write_str("\n")?;
+        Ok(())
+    }
+}
+
+impl<T, R> Visit for Infobox<T, R>
+where
+    T: Visit<Kind = InlineComponent>,
+    R: IntoIterRef,
+    <R as IntoIterRef>::Item: Visit<Kind = InfoboxRowComponent>,
+{
+    fn visit<V>(&self, visitor: &mut V, ctx: Context<Self::Kind>)
+    where
+        V: Visitor + ?Sized,
+    {
+        visit_node(visitor, Node::Infobox, ctx, |visitor| {
+            self.title.visit(visitor, ctx.with_kind(&InlineComponent));
+            if let Some(image) = &self.image {
+                image.visit(visitor, ctx);
+            }
+            for row in self.rows.iter() {
+                row.visit(visitor, ctx.with_kind(&InfoboxRowComponent));
+            }
+        });
+    }
+}
+
+/// Makes a table row of a heading spanning both columns of an infobox.
+fn heading_row<H>(heading: &H) -> Row<[Cell<InlineBlock<&H>>; 1]>
+where
+    H: Component<Kind = InlineComponent>,
+{
+    Row([Cell {
+        child: InlineBlock(heading),
+        attrs: CellAttrs { header: true, colspan: 2, ..CellAttrs::default() },
+    }])
+}
+
+/// Renders a component with a new format into its non-blank lines, trimmed,
+/// for it to fit in a single line of a table or of key/value lines.
+fn render_lines<C, W, K>(
+    component: &C,
+    ctx: Context<K>,
+) -> Result<Vec<String>, fmt::Error>
+where
+    C: Render<W, Kind = K> + ?Sized,
+    W: Format + Default,
+    K: ComponentKind + ?Sized,
+{
+    let mut format = W::default();
+    let mut output = String::new();
+    component.render(&mut Renderer::new(&mut format, &mut output), ctx)?;
+    let lines = output
+        .lines()
+        .map(str::trim)
+        .filter(|line| !line.is_empty())
+        .map(String::from)
+        .collect();
+    Ok(lines)
+}
+
+/// Joins rendered lines into the contents of a Markdown table cell.
+fn markdown_cell(lines: &[String]) -> String {
+    lines.join("<br>").replace('|', "\\|")
+}
+
+#[cfg(test)]
+mod test {
+    use super::{Infobox, InfoboxGroup, InfoboxRow, InfoboxRowComponent};
+    use crate::{
+        component::{
+            block::{media::Image, InlineBlock},
+            BlockComponent,
+        },
+        location::{InternalPath, Location},
+        render::{
+            html::test::validate_html_fragment,
+            Context,
+            DynFullComponent,
+            FullRender,
+            Html,
+            Markdown,
+            RenderAsDisplay,
+            Text,
+        },
+    };
+
+    type Rows = [DynFullComponent<'static, InfoboxRowComponent>; 2];
+
+    fn infobox() -> Infobox<&'static str, Rows> {
+        Infobox {
+            title: "Ferris",
+            image: Some(Image {
+                location: Location::from(InternalPath::parse("ferris.png")),
+                alt: String::from("A crab"),
+            }),
+            float: Default::default(),
+            rows: [
+                InfoboxRow { label: "Species", content: InlineBlock("Crab") }
+                    .into_dyn(),
+                InfoboxGroup {
+                    heading: "Habitat",
+                    rows: [
+                        InfoboxRow {
+                            label: "Found in",
+                            content: InlineBlock("Rust code"),
+                        },
+                        InfoboxRow {
+                            label: "Shell",
+                            content: InlineBlock("Orange"),
+                        },
+                    ],
+                }
+                .into_dyn(),
+            ],
+        }
+    }
+
+    #[test]
+    fn html_classes() {
+        let location = InternalPath::root();
+        let rendered = RenderAsDisplay::new(
+            infobox(),
+            &mut Html,
+            Context::new(&location, &BlockComponent),
+        )
+        .to_string();
+        validate_html_fragment(&rendered).unwrap();
+        assert_eq!(
+            rendered,
+            "<aside class=\"pedia-infobox pedia-infobox-float-right\"><table>\
+             <caption class=\"pedia-infobox-title\">Ferris</caption><tr><td \
+             class=\"pedia-infobox-image\" colspan=\"2\"><img \
+             class=\"pedia-image\" src=\"ferris.png\" \
+             alt=\"ferris.png\"></td></tr><tr class=\"pedia-infobox-row\"><th \
+             class=\"pedia-infobox-label\" scope=\"row\">Species</th><td \
+             class=\"pedia-infobox-content\"><span \
+             class=\"pedia-inline-block\">Crab</span></td></tr><tr><th \
+             class=\"pedia-infobox-heading\" colspan=\"2\" \
+             scope=\"colgroup\">Habitat</th></tr><tr \
+             class=\"pedia-infobox-row\"><th class=\"pedia-infobox-label\" \
+             scope=\"row\">Found in</th><td class=\"pedia-infobox-content\">\
+             <span class=\"pedia-inline-block\">Rust code</span></td></tr><tr \
+             class=\"pedia-infobox-row\"><th class=\"pedia-infobox-label\" \
+             scope=\"row\">Shell</th><td class=\"pedia-infobox-content\"><span \
+             class=\"pedia-inline-block\">Orange</span></td></tr></table>\
+             </aside>"
+        );
+    }
+
+    #[test]
+    fn markdown_table() {
+        let location = InternalPath::root();
+        let rendered = RenderAsDisplay::new(
+            infobox(),
+            &mut Markdown::default(),
+            Context::new(&location, &BlockComponent),
+        )
+        .to_string();
+        assert_eq!(
+            rendered,
+            "| Ferris | |\n| --- | --- |\n| ![A crab](ferris.png) | |\n\
+             | Species | Crab |\n| **Habitat** | |\n| Found in | Rust code |\n\
+             | Shell | Orange |\n"
+        );
+    }
+
+    #[test]
+    fn text_aligned() {
+        let location = InternalPath::root();
+        let rendered = RenderAsDisplay::new(
+            infobox(),
+            &mut Text::default(),
+            Context::new(&location, &BlockComponent),
+        )
+        .to_string();
+        assert_eq!(
+            rendered,
+            "Ferris\n[A crab]\nSpecies:  Crab\n\nHabitat\n\
+             Found in: Rust code\nShell:    Orange\n"
+        );
+    }
+}
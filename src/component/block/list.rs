@@ -4,7 +4,20 @@ use super::BlockComponent;
 use crate::{
     component::Component,
     hseq::IntoIterRef,
-    render::{markdown, text, Context, Html, Markdown, Render, Renderer, Text},
+    render::{
+        gemtext,
+        markdown,
+        text,
+        Context,
+        Gemtext,
+        Html,
+        Latex,
+        Markdown,
+        Render,
+        Renderer,
+        Text,
+    },
+    visit::{visit_node, Node, Visit, Visitor},
 };
 use std::{
     cmp::Ordering,
@@ -189,6 +202,69 @@ where
     }
 }
 
+impl<L> Render<Latex> for UnorderedList<L>
+where
+    L: IntoIterRef,
+    <L as IntoIterRef>::Item: Render<Latex, Kind = BlockComponent>,
+{
+    fn render(
+        &self,
+        renderer: &mut Renderer<Latex>,
+        ctx: Context<Self::Kind>,
+    ) -> fmt::Result {
+        if self.0.iter().next().is_none() {
+            return Ok(());
+        }
+        renderer.write_str("\\begin{itemize}\n")?;
+        for element in self.0.iter() {
+            renderer.write_str("\\item ")?;
+            element.render(renderer, ctx)?;
+            renderer.write_str("\n")?;
+        }
+        renderer.write_str("\\end{itemize}\n\n")?;
+        Ok(())
+    }
+}
+
+impl<L> Render<Gemtext> for UnorderedList<L>
+where
+    L: IntoIterRef,
+    <L as IntoIterRef>::Item: Render<Gemtext, Kind = BlockComponent>,
+{
+    fn render(
+        &self,
+        renderer: &mut Renderer<Gemtext>,
+        ctx: Context<Self::Kind>,
+    ) -> fmt::Result {
+        for element in self.0.iter() {
+            renderer.write_str("* ")?;
+            renderer.scoped(gemtext::Line, |renderer| {
+                element.render(renderer, ctx)
+            })?;
+            renderer.write_str("\n")?;
+        }
+        renderer.write_str("\n")?;
+        Ok(())
+    }
+}
+
+impl<L> Visit for UnorderedList<L>
+where
+    L: IntoIterRef,
+    <L as IntoIterRef>::Item: Visit<Kind = BlockComponent>,
+{
+    fn visit<V>(&self, visitor: &mut V, ctx: Context<Self::Kind>)
+    where
+        V: Visitor + ?Sized,
+    {
+        visit_node(visitor, Node::UnorderedList, ctx, |visitor| {
+            for element in self.0.iter() {
+                element.visit(visitor, ctx);
+            }
+        });
+    }
+}
+
 /// An ordered list (typically displayed with item numbers). The unnamed field
 /// can be a vector, an array, or anything that iterates by ref over a
 /// component.
@@ -367,6 +443,69 @@ where
     }
 }
 
+impl<L> Render<Latex> for OrderedList<L>
+where
+    L: IntoIterRef,
+    <L as IntoIterRef>::Item: Render<Latex, Kind = BlockComponent>,
+{
+    fn render(
+        &self,
+        renderer: &mut Renderer<Latex>,
+        ctx: Context<Self::Kind>,
+    ) -> fmt::Result {
+        if self.0.iter().next().is_none() {
+            return Ok(());
+        }
+        renderer.write_str("\\begin{enumerate}\n")?;
+        for element in self.0.iter() {
+            renderer.write_str("\\item ")?;
+            element.render(renderer, ctx)?;
+            renderer.write_str("\n")?;
+        }
+        renderer.write_str("\\end{enumerate}\n\n")?;
+        Ok(())
+    }
+}
+
+impl<L> Render<Gemtext> for OrderedList<L>
+where
+    L: IntoIterRef,
+    <L as IntoIterRef>::Item: Render<Gemtext, Kind = BlockComponent>,
+{
+    fn render(
+        &self,
+        renderer: &mut Renderer<Gemtext>,
+        ctx: Context<Self::Kind>,
+    ) -> fmt::Result {
+        for element in self.0.iter() {
+            renderer.write_str("* ")?;
+            renderer.scoped(gemtext::Line, |renderer| {
+                element.render(renderer, ctx)
+            })?;
+            renderer.write_str("\n")?;
+        }
+        renderer.write_str("\n")?;
+        Ok(())
+    }
+}
+
+impl<L> Visit for OrderedList<L>
+where
+    L: IntoIterRef,
+    <L as IntoIterRef>::Item: Visit<Kind = BlockComponent>,
+{
+    fn visit<V>(&self, visitor: &mut V, ctx: Context<Self::Kind>)
+    where
+        V: Visitor + ?Sized,
+    {
+        visit_node(visitor, Node::OrderedList, ctx, |visitor| {
+            for element in self.0.iter() {
+                element.visit(visitor, ctx);
+            }
+        });
+    }
+}
+
 #[cfg(test)]
 mod test {
     use super::{OrderedList, UnorderedList};
@@ -391,7 +530,7 @@ mod test {
             UnorderedList(harray![
                 (InlineBlock("abc"), Paragraph("def")): BlockComponent
             ]),
-            &mut Html::default(),
+            &mut Html,
             Context::new(&InternalPath::default(), &BlockComponent),
         )
         .to_string();
@@ -405,7 +544,7 @@ mod test {
             OrderedList(harray![
                 (InlineBlock("abc"), Paragraph("def")): BlockComponent
             ]),
-            &mut Html::default(),
+            &mut Html,
             Context::new(&InternalPath::default(), &BlockComponent),
         )
         .to_string();
@@ -0,0 +1,168 @@
+//! This module exports the block math component.
+
+use super::BlockComponent;
+use crate::{
+    component::{inline::math::Expr, Component},
+    render::{Context, Gemtext, Html, Latex, Markdown, Render, Renderer, Text},
+    visit::{visit_node, Node, Visit, Visitor},
+};
+use std::fmt::{self, Write};
+
+/// A math formula displayed as a block of its own, given as TeX (see
+/// [`Expr::parse`] for the supported subset). In HTML, it is rendered as
+/// MathML, so no script is required. In Markdown, the source is kept between
+/// double dollars, and in LaTeX, in a display math environment. In plaintext
+/// and Gemtext, the formula is linearized on a line of its own (see
+/// [`Expr::linearize`]). An invalid formula makes the rendering fail,
+/// reporting the error to the context (see [`Context::fail`]).
+///
+/// # HTML Classes
+///
+/// - `pedia-math-block` attached to a `<math>` element, displayed as a block.
+#[derive(Debug, Clone, PartialEq, Eq, PartialOrd, Ord, Hash, Default)]
+pub struct Math(pub String);
+
+impl Math {
+    /// Parses the formula, reporting a parse error to the context.
+    fn parse(&self, ctx: Context<BlockComponent>) -> Result<Expr, fmt::Error> {
+        Expr::parse(&self.0).map_err(|error| {
+            ctx.fail(format_args!("{} in `{}`", error, self.0))
+        })
+    }
+}
+
+impl Component for Math {
+    type Kind = BlockComponent;
+}
+
+impl Render<Html> for Math {
+    fn render(
+        &self,
+        renderer: &mut Renderer<Html>,
+        ctx: Context<Self::Kind>,
+    ) -> fmt::Result {
+        let expr = self.parse(ctx)?;
+        renderer
+            .write_str("<math class=\"pedia-math-block\" display=\"block\">")?;
+        expr.write_mathml(renderer)?;
+        renderer.write_str("</math>")?;
+        Ok(())
+    }
+}
+
+impl Render<Markdown> for Math {
+    fn render(
+        &self,
+        renderer: &mut Renderer<Markdown>,
+        ctx: Context<Self::Kind>,
+    ) -> fmt::Result {
+        self.parse(ctx)?;
+        write!(renderer, "$$\n{}\n$$\n\n", self.0.trim())
+    }
+}
+
+impl Render<Text> for Math {
+    fn render(
+        &self,
+        renderer: &mut Renderer<Text>,
+        ctx: Context<Self::Kind>,
+    ) -> fmt::Result {
+        write!(renderer, "{}\n\n", self.parse(ctx)?.linearize())
+    }
+}
+
+impl Render<Latex> for Math {
+    fn render(
+        &self,
+        renderer: &mut Renderer<Latex>,
+        ctx: Context<Self::Kind>,
+    ) -> fmt::Result {
+        self.parse(ctx)?;
+        write!(renderer, "\\[ {} \\]\n\n", self.0.trim())
+    }
+}
+
+impl Render<Gemtext> for Math {
+    fn render(
+        &self,
+        renderer: &mut Renderer<Gemtext>,
+        ctx: Context<Self::Kind>,
+    ) -> fmt::Result {
+        write!(renderer, "{}\n\n", self.parse(ctx)?.linearize())
+    }
+}
+
+impl Visit for Math {
+    fn visit<V>(&self, visitor: &mut V, ctx: Context<Self::Kind>)
+    where
+        V: Visitor + ?Sized,
+    {
+        visit_node(visitor, Node::Math(&self.0), ctx, |_| ());
+    }
+}
+
+#[cfg(test)]
+mod test {
+    use super::Math;
+    use crate::{
+        component::{
+            block::{text::Paragraph, InlineBlock},
+            page::{Page, PageComponent},
+        },
+        harray,
+        location::InternalPath,
+        render::{DynFullComponent, FullRender, Markdown, Text},
+        site::{Entry, Site},
+    };
+    use std::{collections::BTreeMap, io, path::PathBuf};
+
+    fn make_site(
+        source: &str,
+    ) -> Site<DynFullComponent<'static, PageComponent>> {
+        let page = Page::new(
+            InlineBlock("Banner"),
+            String::from("Home"),
+            harray![],
+            harray![Paragraph("Euler:"), Math(String::from(source))],
+            harray![],
+        );
+        let mut site = Site::default();
+        site.root
+            .insert_index(InternalPath::root(), Entry::Page(page.into_dyn()));
+        site
+    }
+
+    #[test]
+    fn markdown_and_text() {
+        let site = make_site("e^{i\\pi} + 1 = 0");
+        let mut markdown = Markdown::default();
+        let mut output = BTreeMap::new();
+        site.build_into(&mut markdown, &mut output, &mut PathBuf::new())
+            .unwrap();
+        let page = &output[&InternalPath::parse("index.md")];
+        assert!(String::from_utf8_lossy(page)
+            .contains("Euler:\n\n$$\ne^{i\\pi} + 1 = 0\n$$\n"));
+
+        let mut output = BTreeMap::new();
+        site.build_into(&mut Text::default(), &mut output, &mut PathBuf::new())
+            .unwrap();
+        let page = &output[&InternalPath::parse("index.txt")];
+        assert!(String::from_utf8_lossy(page).contains("e^(iπ) + 1 = 0\n"));
+    }
+
+    #[test]
+    fn parse_error_fails_build() {
+        let site = make_site("\\frac{1}{");
+        let mut output = BTreeMap::new();
+        let error = site
+            .build_into(&mut Text::default(), &mut output, &mut PathBuf::new())
+            .unwrap_err();
+
+        assert_eq!(error.path, InternalPath::parse("index.txt"));
+        assert_eq!(error.cause.kind(), io::ErrorKind::InvalidData);
+        assert_eq!(
+            error.cause.to_string(),
+            "invalid math at byte 9: missing '}' in `\\frac{1}{`"
+        );
+    }
+}
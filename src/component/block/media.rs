@@ -4,7 +4,19 @@ use super::BlockComponent;
 use crate::{
     component::{Component, InlineComponent},
     location::Location,
-    render::{Context, Html, Markdown, Render, Renderer, Text},
+    render::{
+        gemtext,
+        latex,
+        Context,
+        Gemtext,
+        Html,
+        Latex,
+        Markdown,
+        Render,
+        Renderer,
+        Text,
+    },
+    visit::{visit_node, Node, Visit, Visitor},
 };
 use std::fmt::{self, Write};
 
@@ -68,6 +80,55 @@ impl Render<Text> for Image {
     }
 }
 
+impl Render<Latex> for Image {
+    fn render(
+        &self,
+        renderer: &mut Renderer<Latex>,
+        ctx: Context<Self::Kind>,
+    ) -> fmt::Result {
+        match &self.location {
+            Location::Url(url) => {
+                let url = latex::escape_url(url.as_str());
+                write!(renderer, "\\href{{{}}}{{", url)?;
+                self.alt.render(renderer, ctx.with_kind(&InlineComponent))?;
+                renderer.write_str("}\n\n")?;
+            },
+            Location::Internal(loc) => {
+                ctx.reference(loc);
+                let path = latex::escape_url(&loc.path.to_string());
+                write!(
+                    renderer,
+                    "\\includegraphics[width=\\linewidth]{{{}}}\n\n",
+                    path
+                )?;
+            },
+        }
+        Ok(())
+    }
+}
+
+impl Render<Gemtext> for Image {
+    fn render(
+        &self,
+        renderer: &mut Renderer<Gemtext>,
+        ctx: Context<Self::Kind>,
+    ) -> fmt::Result {
+        let url = gemtext::location_url(&self.location, ctx);
+        renderer.write_str(&gemtext::link_line(&url, &self.alt))?;
+        renderer.write_str("\n")?;
+        Ok(())
+    }
+}
+
+impl Visit for Image {
+    fn visit<V>(&self, visitor: &mut V, ctx: Context<Self::Kind>)
+    where
+        V: Visitor + ?Sized,
+    {
+        visit_node(visitor, Node::Image(self), ctx, |_| ());
+    }
+}
+
 /// A figure component, an image with legend.
 ///
 /// # HTML Classes
@@ -149,6 +210,57 @@ where
     }
 }
 
+impl<L> Render<Latex> for Figure<L>
+where
+    L: Render<Latex, Kind = InlineComponent>,
+{
+    fn render(
+        &self,
+        renderer: &mut Renderer<Latex>,
+        ctx: Context<Self::Kind>,
+    ) -> fmt::Result {
+        renderer.write_str("\\begin{figure}[htbp]\n\\centering\n")?;
+        self.image.render(renderer, ctx)?;
+        renderer.write_str("\\caption{")?;
+        self.legend.render(renderer, ctx.with_kind(&InlineComponent))?;
+        renderer.write_str("}\n\\end{figure}\n\n")?;
+        Ok(())
+    }
+}
+
+impl<L> Render<Gemtext> for Figure<L>
+where
+    L: Render<Gemtext, Kind = InlineComponent>,
+{
+    fn render(
+        &self,
+        renderer: &mut Renderer<Gemtext>,
+        ctx: Context<Self::Kind>,
+    ) -> fmt::Result {
+        let url = gemtext::location_url(&self.image.location, ctx);
+        renderer.write_str(&gemtext::link_line(&url, &self.image.alt))?;
+        renderer.scoped(gemtext::Line, |renderer| {
+            self.legend.render(renderer, ctx.with_kind(&InlineComponent))
+        })?;
+        renderer.write_str("\n\n")?;
+        Ok(())
+    }
+}
+
+impl<L> Visit for Figure<L>
+where
+    L: Visit<Kind = InlineComponent>,
+{
+    fn visit<V>(&self, visitor: &mut V, ctx: Context<Self::Kind>)
+    where
+        V: Visitor + ?Sized,
+    {
+        visit_node(visitor, Node::Figure(&self.image), ctx, |visitor| {
+            self.legend.visit(visitor, ctx.with_kind(&InlineComponent))
+        });
+    }
+}
+
 #[cfg(test)]
 mod test {
     use super::{Figure, Image};
@@ -170,7 +282,7 @@ mod test {
                 location: Location::internal("abc/hi.png"),
                 alt: String::from("img about hi"),
             },
-            &mut Html::default(),
+            &mut Html,
             Context::new(&InternalPath::default(), &BlockComponent),
         )
         .to_string();
@@ -188,7 +300,7 @@ mod test {
                 },
                 legend: Bold("stark image"),
             },
-            &mut Html::default(),
+            &mut Html,
             Context::new(&InternalPath::default(), &BlockComponent),
         )
         .to_string();
@@ -8,7 +8,18 @@ use crate::{
         InlineComponent,
     },
     hseq::IntoIterRef,
-    render::{Context, Html, Markdown, Render, Renderer, Text},
+    render::{
+        gemtext,
+        Context,
+        Gemtext,
+        Html,
+        Latex,
+        Markdown,
+        Render,
+        Renderer,
+        Text,
+    },
+    visit::{visit_node, Node, Visit, Visitor},
 };
 use std::{
     cmp::Ordering,
@@ -155,6 +166,74 @@ where
     }
 }
 
+impl<T> Render<Latex> for Cell<T>
+where
+    T: Render<Latex, Kind = BlockComponent>,
+{
+    fn render(
+        &self,
+        renderer: &mut Renderer<Latex>,
+        ctx: Context<Self::Kind>,
+    ) -> std::fmt::Result {
+        let separators = renderer
+            .format_mut()
+            .begin_cell(self.attrs.rowspan, self.attrs.colspan);
+        for _ in 0 .. separators {
+            write!(renderer, " & ")?;
+        }
+        if self.attrs.colspan != 1 {
+            write!(renderer, "\\multicolumn{{{}}}{{l}}{{", self.attrs.colspan)?;
+        }
+        if self.attrs.rowspan != 1 {
+            write!(renderer, "\\multirow{{{}}}{{*}}{{", self.attrs.rowspan)?;
+        }
+        if self.attrs.header {
+            write!(renderer, "\\textbf{{")?;
+        }
+        self.child.render(renderer, ctx.with_kind(&BlockComponent))?;
+        if self.attrs.header {
+            write!(renderer, "}}")?;
+        }
+        if self.attrs.rowspan != 1 {
+            write!(renderer, "}}")?;
+        }
+        if self.attrs.colspan != 1 {
+            write!(renderer, "}}")?;
+        }
+        renderer.format_mut().end_cell();
+        Ok(())
+    }
+}
+
+impl<T> Render<Gemtext> for Cell<T>
+where
+    T: Render<Gemtext, Kind = BlockComponent>,
+{
+    fn render(
+        &self,
+        renderer: &mut Renderer<Gemtext>,
+        ctx: Context<Self::Kind>,
+    ) -> std::fmt::Result {
+        renderer.scoped(gemtext::Line, |renderer| {
+            self.child.render(renderer, ctx.with_kind(&BlockComponent))
+        })
+    }
+}
+
+impl<T> Visit for Cell<T>
+where
+    T: Visit<Kind = BlockComponent>,
+{
+    fn visit<V>(&self, visitor: &mut V, ctx: Context<Self::Kind>)
+    where
+        V: Visitor + ?Sized,
+    {
+        visit_node(visitor, Node::Cell(&self.attrs), ctx, |visitor| {
+            self.child.visit(visitor, ctx.with_kind(&BlockComponent))
+        });
+    }
+}
+
 /// A row in a table. The single unnamed field could be an array, a vec, or
 /// anything that iterates by ref yielding cell components.
 ///
@@ -324,6 +403,64 @@ where
     }
 }
 
+impl<C> Render<Latex> for Row<C>
+where
+    C: IntoIterRef,
+    <C as IntoIterRef>::Item: Render<Latex, Kind = CellComponent>,
+{
+    fn render(
+        &self,
+        renderer: &mut Renderer<Latex>,
+        ctx: Context<Self::Kind>,
+    ) -> fmt::Result {
+        renderer.format_mut().begin_row();
+        for cell in self.0.iter() {
+            cell.render(renderer, ctx.with_kind(&CellComponent))?;
+        }
+        renderer.format_mut().end_row();
+        renderer.write_str(" \\\\\n")?;
+        Ok(())
+    }
+}
+
+impl<C> Render<Gemtext> for Row<C>
+where
+    C: IntoIterRef,
+    <C as IntoIterRef>::Item: Render<Gemtext, Kind = CellComponent>,
+{
+    fn render(
+        &self,
+        renderer: &mut Renderer<Gemtext>,
+        ctx: Context<Self::Kind>,
+    ) -> fmt::Result {
+        for (i, cell) in self.0.iter().enumerate() {
+            if i > 0 {
+                renderer.write_str(" | ")?;
+            }
+            cell.render(renderer, ctx.with_kind(&CellComponent))?;
+        }
+        renderer.write_str("\n")?;
+        Ok(())
+    }
+}
+
+impl<C> Visit for Row<C>
+where
+    C: IntoIterRef,
+    <C as IntoIterRef>::Item: Visit<Kind = CellComponent>,
+{
+    fn visit<V>(&self, visitor: &mut V, ctx: Context<Self::Kind>)
+    where
+        V: Visitor + ?Sized,
+    {
+        visit_node(visitor, Node::Row, ctx, |visitor| {
+            for cell in self.0.iter() {
+                cell.visit(visitor, ctx.with_kind(&CellComponent));
+            }
+        });
+    }
+}
+
 /// A table. The single unnamed field could be an array, a vec, or
 /// anything that iterates by ref yielding row components.
 ///
@@ -495,6 +632,77 @@ where
     }
 }
 
+impl<L> Render<Latex> for Table<L>
+where
+    L: IntoIterRef,
+    <L as IntoIterRef>::Item: Render<Latex, Kind = RowComponent>,
+{
+    fn render(
+        &self,
+        renderer: &mut Renderer<Latex>,
+        ctx: Context<Self::Kind>,
+    ) -> fmt::Result {
+        // The column specification comes before the rows, but the number of
+        // columns is only known after rendering them.
+        let mut rows = String::new();
+        renderer.format_mut().begin_table();
+        let result = {
+            let mut rows_renderer =
+                Renderer::new(renderer.format_mut(), &mut rows);
+            self.0.iter().try_for_each(|row| {
+                row.render(&mut rows_renderer, ctx.with_kind(&RowComponent))
+            })
+        };
+        let columns = renderer.format_mut().end_table();
+        result?;
+
+        let spec = "l".repeat(columns.max(1) as usize);
+        writeln!(renderer, "\\begin{{tabular}}{{{}}}", spec)?;
+        renderer.write_str(&rows)?;
+        renderer.write_str("\\end{tabular}\n\n")?;
+        Ok(())
+    }
+}
+
+impl<L> Render<Gemtext> for Table<L>
+where
+    L: IntoIterRef,
+    <L as IntoIterRef>::Item: Render<Gemtext, Kind = RowComponent>,
+{
+    fn render(
+        &self,
+        renderer: &mut Renderer<Gemtext>,
+        ctx: Context<Self::Kind>,
+    ) -> fmt::Result {
+        renderer.write_str("```\n")?;
+        renderer.scoped(gemtext::Preformatted, |renderer| {
+            for row in self.0.iter() {
+                row.render(renderer, ctx.with_kind(&RowComponent))?;
+            }
+            renderer.write_str("```")
+        })?;
+        renderer.write_str("\n\n")?;
+        Ok(())
+    }
+}
+
+impl<L> Visit for Table<L>
+where
+    L: IntoIterRef,
+    <L as IntoIterRef>::Item: Visit<Kind = RowComponent>,
+{
+    fn visit<V>(&self, visitor: &mut V, ctx: Context<Self::Kind>)
+    where
+        V: Visitor + ?Sized,
+    {
+        visit_node(visitor, Node::Table, ctx, |visitor| {
+            for row in self.0.iter() {
+                row.visit(visitor, ctx.with_kind(&RowComponent));
+            }
+        });
+    }
+}
+
 /// A table with a title/caption at the top.
 pub struct CaptionedTable<C, L>
 where
@@ -685,6 +893,63 @@ where
     }
 }
 
+impl<C, L> Render<Latex> for CaptionedTable<C, L>
+where
+    C: Render<Latex, Kind = InlineComponent>,
+    L: IntoIterRef,
+    <L as IntoIterRef>::Item: Render<Latex, Kind = RowComponent>,
+{
+    fn render(
+        &self,
+        renderer: &mut Renderer<Latex>,
+        ctx: Context<Self::Kind>,
+    ) -> fmt::Result {
+        renderer.write_str("\\begin{table}[htbp]\n\\centering\n\\caption{")?;
+        self.caption.render(renderer, ctx.with_kind(&InlineComponent))?;
+        renderer.write_str("}\n")?;
+        self.table.render(renderer, ctx)?;
+        renderer.write_str("\\end{table}\n\n")?;
+        Ok(())
+    }
+}
+
+impl<C, L> Render<Gemtext> for CaptionedTable<C, L>
+where
+    C: Render<Gemtext, Kind = InlineComponent>,
+    L: IntoIterRef,
+    <L as IntoIterRef>::Item: Render<Gemtext, Kind = RowComponent>,
+{
+    fn render(
+        &self,
+        renderer: &mut Renderer<Gemtext>,
+        ctx: Context<Self::Kind>,
+    ) -> fmt::Result {
+        renderer.scoped(gemtext::Line, |renderer| {
+            self.caption.render(renderer, ctx.with_kind(&InlineComponent))
+        })?;
+        renderer.write_str("\n\n")?;
+        self.table.render(renderer, ctx)?;
+        Ok(())
+    }
+}
+
+impl<C, L> Visit for CaptionedTable<C, L>
+where
+    C: Visit<Kind = InlineComponent>,
+    L: IntoIterRef,
+    <L as IntoIterRef>::Item: Visit<Kind = RowComponent>,
+{
+    fn visit<V>(&self, visitor: &mut V, ctx: Context<Self::Kind>)
+    where
+        V: Visitor + ?Sized,
+    {
+        visit_node(visitor, Node::CaptionedTable, ctx, |visitor| {
+            self.caption.visit(visitor, ctx.with_kind(&InlineComponent));
+            self.table.visit(visitor, ctx);
+        });
+    }
+}
+
 #[cfg(test)]
 mod test {
     use super::{CaptionedTable, Cell, CellAttrs, Row, Table};
@@ -700,6 +965,7 @@ mod test {
             html::test::validate_html_fragment,
             Context,
             Html,
+            Latex,
             RenderAsDisplay,
         },
     };
@@ -723,7 +989,7 @@ mod test {
                     attrs: CellAttrs::default()
                 }])
             ]),
-            &mut Html::default(),
+            &mut Html,
             Context::new(&InternalPath::default(), &BlockComponent),
         )
         .to_string();
@@ -753,11 +1019,44 @@ mod test {
                     }])
                 ]),
             },
-            &mut Html::default(),
+            &mut Html,
             Context::new(&InternalPath::default(), &BlockComponent),
         )
         .to_string();
 
         validate_html_fragment(&rendered).unwrap();
     }
+
+    #[test]
+    fn spanning_cells_as_latex() {
+        let rendered = RenderAsDisplay::new(
+            Table(harray![
+                Row(harray![
+                    Cell {
+                        child: InlineBlock("a"),
+                        attrs: CellAttrs { rowspan: 2, ..CellAttrs::default() }
+                    },
+                    Cell {
+                        child: Paragraph("b"),
+                        attrs: CellAttrs {
+                            header: true,
+                            colspan: 2,
+                            ..CellAttrs::default()
+                        }
+                    },
+                ]),
+                Row(harray![Cell::from(InlineBlock("c"))])
+            ]),
+            &mut Latex::default(),
+            Context::new(&InternalPath::default(), &BlockComponent),
+        )
+        .to_string();
+
+        assert_eq!(
+            rendered,
+            "\\begin{tabular}{lll}\n\\multirow{2}{*}{a} & \
+             \\multicolumn{2}{l}{\\textbf{b  }} \\\\\n & c \\\\\n\
+             \\end{tabular}\n\n"
+        );
+    }
 }
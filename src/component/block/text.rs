@@ -3,7 +3,18 @@
 use super::BlockComponent;
 use crate::{
     component::{Component, InlineComponent},
-    render::{Context, Html, Markdown, Render, Renderer, Text},
+    render::{
+        gemtext,
+        Context,
+        Gemtext,
+        Html,
+        Latex,
+        Markdown,
+        Render,
+        Renderer,
+        Text,
+    },
+    visit::{visit_node, Node, Visit, Visitor},
 };
 use std::fmt::{self, Write};
 
@@ -69,6 +80,49 @@ where
     }
 }
 
+impl<C> Render<Latex> for Bold<C>
+where
+    C: Render<Latex, Kind = BlockComponent>,
+{
+    fn render(
+        &self,
+        renderer: &mut Renderer<Latex>,
+        ctx: Context<Self::Kind>,
+    ) -> fmt::Result {
+        renderer.write_str("{\\bfseries ")?;
+        self.0.render(renderer, ctx)?;
+        renderer.write_str("}\n\n")?;
+        Ok(())
+    }
+}
+
+impl<C> Render<Gemtext> for Bold<C>
+where
+    C: Render<Gemtext, Kind = BlockComponent>,
+{
+    fn render(
+        &self,
+        renderer: &mut Renderer<Gemtext>,
+        ctx: Context<Self::Kind>,
+    ) -> fmt::Result {
+        self.0.render(renderer, ctx)
+    }
+}
+
+impl<C> Visit for Bold<C>
+where
+    C: Visit<Kind = BlockComponent>,
+{
+    fn visit<V>(&self, visitor: &mut V, ctx: Context<Self::Kind>)
+    where
+        V: Visitor + ?Sized,
+    {
+        visit_node(visitor, Node::Bold, ctx, |visitor| {
+            self.0.visit(visitor, ctx)
+        });
+    }
+}
+
 /// This components wraps another component and its text italic.
 ///
 /// # HTML Classes
@@ -131,6 +185,49 @@ where
     }
 }
 
+impl<C> Render<Latex> for Italic<C>
+where
+    C: Render<Latex, Kind = BlockComponent>,
+{
+    fn render(
+        &self,
+        renderer: &mut Renderer<Latex>,
+        ctx: Context<Self::Kind>,
+    ) -> fmt::Result {
+        renderer.write_str("{\\itshape ")?;
+        self.0.render(renderer, ctx)?;
+        renderer.write_str("}\n\n")?;
+        Ok(())
+    }
+}
+
+impl<C> Render<Gemtext> for Italic<C>
+where
+    C: Render<Gemtext, Kind = BlockComponent>,
+{
+    fn render(
+        &self,
+        renderer: &mut Renderer<Gemtext>,
+        ctx: Context<Self::Kind>,
+    ) -> fmt::Result {
+        self.0.render(renderer, ctx)
+    }
+}
+
+impl<C> Visit for Italic<C>
+where
+    C: Visit<Kind = BlockComponent>,
+{
+    fn visit<V>(&self, visitor: &mut V, ctx: Context<Self::Kind>)
+    where
+        V: Visitor + ?Sized,
+    {
+        visit_node(visitor, Node::Italic, ctx, |visitor| {
+            self.0.visit(visitor, ctx)
+        });
+    }
+}
+
 /// This components wraps another component and its text preformatted. Suitable
 /// for code.
 ///
@@ -194,6 +291,54 @@ where
     }
 }
 
+impl<C> Render<Latex> for Preformatted<C>
+where
+    C: Render<Latex, Kind = BlockComponent>,
+{
+    fn render(
+        &self,
+        renderer: &mut Renderer<Latex>,
+        ctx: Context<Self::Kind>,
+    ) -> fmt::Result {
+        renderer.write_str("{\\ttfamily\\obeyspaces\\obeylines ")?;
+        self.0.render(renderer, ctx)?;
+        renderer.write_str("}\n\n")?;
+        Ok(())
+    }
+}
+
+impl<C> Render<Gemtext> for Preformatted<C>
+where
+    C: Render<Gemtext, Kind = BlockComponent>,
+{
+    fn render(
+        &self,
+        renderer: &mut Renderer<Gemtext>,
+        ctx: Context<Self::Kind>,
+    ) -> fmt::Result {
+        renderer.write_str("```\n")?;
+        renderer.scoped(gemtext::Preformatted, |renderer| {
+            self.0.render(renderer, ctx)
+        })?;
+        renderer.write_str("\n```\n\n")?;
+        Ok(())
+    }
+}
+
+impl<C> Visit for Preformatted<C>
+where
+    C: Visit<Kind = BlockComponent>,
+{
+    fn visit<V>(&self, visitor: &mut V, ctx: Context<Self::Kind>)
+    where
+        V: Visitor + ?Sized,
+    {
+        visit_node(visitor, Node::Preformatted, ctx, |visitor| {
+            self.0.visit(visitor, ctx)
+        });
+    }
+}
+
 /// Component that takes a portion of inline components and puts it into a
 /// paragraph.
 ///
@@ -258,6 +403,52 @@ where
     }
 }
 
+impl<C> Render<Latex> for Paragraph<C>
+where
+    C: Render<Latex, Kind = InlineComponent>,
+{
+    fn render(
+        &self,
+        renderer: &mut Renderer<Latex>,
+        ctx: Context<Self::Kind>,
+    ) -> fmt::Result {
+        self.0.render(renderer, ctx.with_kind(&InlineComponent))?;
+        renderer.write_str("\n\n")?;
+        Ok(())
+    }
+}
+
+impl<C> Render<Gemtext> for Paragraph<C>
+where
+    C: Render<Gemtext, Kind = InlineComponent>,
+{
+    fn render(
+        &self,
+        renderer: &mut Renderer<Gemtext>,
+        ctx: Context<Self::Kind>,
+    ) -> fmt::Result {
+        renderer.scoped(gemtext::Line, |renderer| {
+            self.0.render(renderer, ctx.with_kind(&InlineComponent))
+        })?;
+        renderer.write_str("\n\n")?;
+        Ok(())
+    }
+}
+
+impl<C> Visit for Paragraph<C>
+where
+    C: Visit<Kind = InlineComponent>,
+{
+    fn visit<V>(&self, visitor: &mut V, ctx: Context<Self::Kind>)
+    where
+        V: Visitor + ?Sized,
+    {
+        visit_node(visitor, Node::Paragraph, ctx, |visitor| {
+            self.0.visit(visitor, ctx.with_kind(&InlineComponent))
+        });
+    }
+}
+
 #[cfg(test)]
 mod test {
     use super::{Bold, Italic, Paragraph, Preformatted};
@@ -276,7 +467,7 @@ mod test {
     fn bold_is_valid_html() {
         let rendered = RenderAsDisplay::new(
             Bold(InlineBlock("abc")),
-            &mut Html::default(),
+            &mut Html,
             Context::new(&InternalPath::default(), &BlockComponent),
         )
         .to_string();
@@ -288,7 +479,7 @@ mod test {
     fn italic_is_valid_html() {
         let rendered = RenderAsDisplay::new(
             Italic(InlineBlock("abc")),
-            &mut Html::default(),
+            &mut Html,
             Context::new(&InternalPath::default(), &BlockComponent),
         )
         .to_string();
@@ -300,7 +491,7 @@ mod test {
     fn preformatted_is_valid_html() {
         let rendered = RenderAsDisplay::new(
             Preformatted(InlineBlock("abc")),
-            &mut Html::default(),
+            &mut Html,
             Context::new(&InternalPath::default(), &BlockComponent),
         )
         .to_string();
@@ -312,7 +503,7 @@ mod test {
     fn paragraph_is_valid_html() {
         let rendered = RenderAsDisplay::new(
             Paragraph("abc"),
-            &mut Html::default(),
+            &mut Html,
             Context::new(&InternalPath::default(), &BlockComponent),
         )
         .to_string();
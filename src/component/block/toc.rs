@@ -0,0 +1,475 @@
+//! This module exports a table of contents component.
+
+use super::BlockComponent;
+use crate::{
+    component::{
+        section::SectionComponent,
+        Component,
+        ComponentKind,
+        InlineComponent,
+    },
+    hseq::IntoIterRef,
+    location::{Id, InternalLoc, InternalPath, Location},
+    render::{
+        gemtext,
+        latex,
+        markdown,
+        text,
+        Context,
+        Gemtext,
+        Html,
+        Latex,
+        Markdown,
+        Render,
+        Renderer,
+        Text,
+    },
+    visit::{visit_node, Flow, Node, Visit, Visitor},
+};
+use std::fmt::{self, Write};
+
+/// An entry of a table of contents, corresponding to a section.
+#[derive(Debug, Clone, PartialEq, Eq, PartialOrd, Ord, Hash, Default)]
+pub struct TocEntry {
+    /// Title of the section, as plain text.
+    pub title: String,
+    /// ID of the section, if any. Entries without an ID are listed but not
+    /// linked.
+    pub id: Option<Id>,
+    /// Entries of the child sections.
+    pub children: Vec<TocEntry>,
+}
+
+/// A table of contents, listing the sections of a page, nested by section
+/// level, with links to the sections' IDs.
+///
+/// # HTML Classes
+///
+/// - `pedia-toc` attached to a `<nav>` element.
+/// - `pedia-toc-list` attached to `<ol>` elements.
+/// - `pedia-toc-entry` attached to `<li>` elements.
+/// - `pedia-link` attached to `<a>` elements of entries with an ID.
+///
+/// # Example
+///
+/// ```
+/// use codedpedia::{
+///     component::{
+///         block::{text::Paragraph, toc::TableOfContents},
+///         page::Page,
+///         section::Section,
+///     },
+///     harray,
+///     location::Id,
+/// };
+///
+/// let children = harray![Section {
+///     title: "History",
+///     id: Some(Id::new("history")),
+///     body: Paragraph("Long ago..."),
+///     children: harray![],
+/// }];
+///
+/// let page = Page::new(
+///     harray![],
+///     String::from("Article"),
+///     harray![],
+///     TableOfContents::new(&children),
+///     children,
+/// );
+///
+/// assert_eq!(page.body.entries[0].title, "History");
+/// ```
+#[derive(Debug, Clone, PartialEq, Eq, PartialOrd, Ord, Hash, Default)]
+pub struct TableOfContents {
+    /// Entries of the top-level sections.
+    pub entries: Vec<TocEntry>,
+}
+
+impl TableOfContents {
+    /// Generates the table of contents from the given (top-level) sections,
+    /// e.g. the children of a page.
+    pub fn new<L>(sections: &L) -> Self
+    where
+        L: IntoIterRef,
+        <L as IntoIterRef>::Item: Visit<Kind = SectionComponent>,
+    {
+        let mut builder = TocBuilder::default();
+        let location = InternalPath::root();
+        for section in sections.iter() {
+            section.visit(
+                &mut builder,
+                Context::new(&location, &SectionComponent),
+            );
+        }
+        Self { entries: builder.entries }
+    }
+}
+
+#[derive(Debug, Default)]
+struct TocBuilder {
+    entries: Vec<TocEntry>,
+    stack: Vec<TocEntry>,
+    in_title: bool,
+}
+
+impl Visitor for TocBuilder {
+    fn enter<K>(&mut self, node: Node, _ctx: Context<K>) -> Flow
+    where
+        K: ComponentKind + ?Sized,
+    {
+        match node {
+            Node::Section(id) => {
+                self.stack.push(TocEntry {
+                    title: String::new(),
+                    id: id.cloned(),
+                    children: Vec::new(),
+                });
+            },
+            Node::Title => self.in_title = true,
+            Node::Footnote if self.in_title => return Flow::Skip,
+            Node::Text(text) if self.in_title => {
+                if let Some(entry) = self.stack.last_mut() {
+                    entry.title.push_str(text);
+                }
+            },
+            _ => (),
+        }
+        Flow::Continue
+    }
+
+    fn leave<K>(&mut self, node: Node, _ctx: Context<K>)
+    where
+        K: ComponentKind + ?Sized,
+    {
+        match node {
+            Node::Section(_) => {
+                if let Some(entry) = self.stack.pop() {
+                    match self.stack.last_mut() {
+                        Some(parent) => parent.children.push(entry),
+                        None => self.entries.push(entry),
+                    }
+                }
+            },
+            Node::Title => self.in_title = false,
+            _ => (),
+        }
+    }
+}
+
+impl Component for TocEntry {
+    type Kind = BlockComponent;
+}
+
+impl Render<Html> for TocEntry {
+    fn render(
+        &self,
+        renderer: &mut Renderer<Html>,
+        ctx: Context<Self::Kind>,
+    ) -> fmt::Result {
+        let inline_ctx = ctx.with_kind(&InlineComponent);
+        renderer.write_str("<li class=\"pedia-toc-entry\">")?;
+        match &self.id {
+            Some(id) => {
+                renderer.write_str("<a class=\"pedia-link\" href=\"")?;
+                link(id, ctx).render(renderer, inline_ctx)?;
+                renderer.write_str("\">")?;
+                self.title.render(renderer, inline_ctx)?;
+                renderer.write_str("</a>")?;
+            },
+            None => {
+                self.title.render(renderer, inline_ctx)?;
+            },
+        }
+        if !self.children.is_empty() {
+            renderer.write_str("<ol class=\"pedia-toc-list\">")?;
+            self.children.render(renderer, ctx)?;
+            renderer.write_str("</ol>")?;
+        }
+        renderer.write_str("</li>")?;
+        Ok(())
+    }
+}
+
+impl Render<Markdown> for TocEntry {
+    fn render(
+        &self,
+        renderer: &mut Renderer<Markdown>,
+        ctx: Context<Self::Kind>,
+    ) -> fmt::Result {
+        let inline_ctx = ctx.with_kind(&InlineComponent);
+        renderer.write_str("- ")?;
+        match &self.id {
+            Some(id) => {
+                renderer.write_str("[")?;
+                self.title.render(renderer, inline_ctx)?;
+                renderer.write_str("](")?;
+                link(id, ctx).render(renderer, inline_ctx)?;
+                renderer.write_str(")")?;
+            },
+            None => {
+                self.title.render(renderer, inline_ctx)?;
+            },
+        }
+        renderer.write_str("\n")?;
+        renderer.scoped(markdown::Nest, |renderer| {
+            self.children.render(renderer, ctx)
+        })
+    }
+}
+
+impl Render<Text> for TocEntry {
+    fn render(
+        &self,
+        renderer: &mut Renderer<Text>,
+        ctx: Context<Self::Kind>,
+    ) -> fmt::Result {
+        let inline_ctx = ctx.with_kind(&InlineComponent);
+        renderer.write_str("- ")?;
+        self.title.render(renderer, inline_ctx)?;
+        renderer.write_str("\n")?;
+        renderer
+            .scoped(text::Nest, |renderer| self.children.render(renderer, ctx))
+    }
+}
+
+impl Render<Latex> for TocEntry {
+    fn render(
+        &self,
+        renderer: &mut Renderer<Latex>,
+        ctx: Context<Self::Kind>,
+    ) -> fmt::Result {
+        let inline_ctx = ctx.with_kind(&InlineComponent);
+        renderer.write_str("\\item ")?;
+        match &self.id {
+            Some(id) => {
+                let label = latex::label(ctx.location(), Some(id));
+                write!(renderer, "\\hyperref[{}]{{", label)?;
+                self.title.render(renderer, inline_ctx)?;
+                renderer.write_str("}")?;
+            },
+            None => {
+                self.title.render(renderer, inline_ctx)?;
+            },
+        }
+        renderer.write_str("\n")?;
+        if !self.children.is_empty() {
+            renderer.write_str("\\begin{enumerate}\n")?;
+            self.children.render(renderer, ctx)?;
+            renderer.write_str("\\end{enumerate}\n")?;
+        }
+        Ok(())
+    }
+}
+
+impl Render<Gemtext> for TocEntry {
+    fn render(
+        &self,
+        renderer: &mut Renderer<Gemtext>,
+        ctx: Context<Self::Kind>,
+    ) -> fmt::Result {
+        renderer.write_str("* ")?;
+        renderer.scoped(gemtext::Line, |renderer| {
+            self.title.render(renderer, ctx.with_kind(&InlineComponent))
+        })?;
+        renderer.write_str("\n")?;
+        self.children.render(renderer, ctx)
+    }
+}
+
+impl Component for TableOfContents {
+    type Kind = BlockComponent;
+}
+
+impl Render<Html> for TableOfContents {
+    fn render(
+        &self,
+        renderer: &mut Renderer<Html>,
+        ctx: Context<Self::Kind>,
+    ) -> fmt::Result {
+        renderer.write_str("<nav class=\"pedia-toc\">")?;
+        renderer.write_str("<ol class=\"pedia-toc-list\">")?;
+        self.entries.render(renderer, ctx)?;
+        renderer.write_str("</ol></nav>")?;
+        Ok(())
+    }
+}
+
+impl Render<Markdown> for TableOfContents {
+    fn render(
+        &self,
+        renderer: &mut Renderer<Markdown>,
+        ctx: Context<Self::Kind>,
+    ) -> fmt::Result {
+        renderer.scoped(markdown::Nest, |renderer| {
+            self.entries.render(renderer, ctx)
+        })?;
+        renderer.write_str("\n")?;
+        Ok(())
+    }
+}
+
+impl Render<Text> for TableOfContents {
+    fn render(
+        &self,
+        renderer: &mut Renderer<Text>,
+        ctx: Context<Self::Kind>,
+    ) -> fmt::Result {
+        renderer.scoped(text::Nest, |renderer| {
+            self.entries.render(renderer, ctx)
+        })?;
+        renderer.write_str("\n")?;
+        Ok(())
+    }
+}
+
+impl Render<Latex> for TableOfContents {
+    fn render(
+        &self,
+        renderer: &mut Renderer<Latex>,
+        ctx: Context<Self::Kind>,
+    ) -> fmt::Result {
+        if self.entries.is_empty() {
+            return Ok(());
+        }
+        renderer.write_str("\\begin{enumerate}\n")?;
+        self.entries.render(renderer, ctx)?;
+        renderer.write_str("\\end{enumerate}\n\n")?;
+        Ok(())
+    }
+}
+
+impl Render<Gemtext> for TableOfContents {
+    fn render(
+        &self,
+        renderer: &mut Renderer<Gemtext>,
+        ctx: Context<Self::Kind>,
+    ) -> fmt::Result {
+        self.entries.render(renderer, ctx)?;
+        renderer.write_str("\n")?;
+        Ok(())
+    }
+}
+
+impl Visit for TableOfContents {
+    fn visit<V>(&self, visitor: &mut V, ctx: Context<Self::Kind>)
+    where
+        V: Visitor + ?Sized,
+    {
+        visit_node(visitor, Node::TableOfContents(&self.entries), ctx, |_| ());
+    }
+}
+
+fn link(id: &Id, ctx: Context<BlockComponent>) -> Location {
+    Location::Internal(InternalLoc {
+        path: ctx.location().clone(),
+        id: Some(id.clone()),
+    })
+}
+
+#[cfg(test)]
+mod test {
+    use super::{TableOfContents, TocEntry};
+    use crate::{
+        component::{block::text::Paragraph, section::Section, BlockComponent},
+        harray,
+        location::{Id, InternalPath},
+        render::{
+            html::test::validate_html_fragment,
+            Context,
+            Html,
+            Markdown,
+            RenderAsDisplay,
+            Text,
+        },
+    };
+
+    fn make_toc() -> TableOfContents {
+        TableOfContents::new(&harray![
+            Section {
+                title: "First",
+                id: Some(Id::new("first")),
+                body: Paragraph("Not a title."),
+                children: harray![Section {
+                    title: harray!["Sub", "section"],
+                    id: None,
+                    body: Paragraph("Neither."),
+                    children: harray![],
+                }],
+            },
+            Section {
+                title: "Second",
+                id: Some(Id::new("second")),
+                body: Paragraph("Nope."),
+                children: harray![],
+            },
+        ])
+    }
+
+    #[test]
+    fn generated_from_sections() {
+        let toc = make_toc();
+        assert_eq!(
+            toc.entries,
+            [
+                TocEntry {
+                    title: String::from("First"),
+                    id: Some(Id::new("first")),
+                    children: vec![TocEntry {
+                        title: String::from("Subsection"),
+                        id: None,
+                        children: vec![],
+                    }],
+                },
+                TocEntry {
+                    title: String::from("Second"),
+                    id: Some(Id::new("second")),
+                    children: vec![],
+                },
+            ]
+        );
+    }
+
+    #[test]
+    fn toc_is_valid_html() {
+        let location = InternalPath::parse("foo/index.html");
+        let rendered = RenderAsDisplay::new(
+            make_toc(),
+            &mut Html,
+            Context::new(&location, &BlockComponent),
+        )
+        .to_string();
+
+        validate_html_fragment(&rendered).unwrap();
+        assert!(rendered.contains("href=\"#first\""));
+    }
+
+    #[test]
+    fn toc_as_markdown() {
+        let location = InternalPath::parse("foo/index.html");
+        let rendered = RenderAsDisplay::new(
+            make_toc(),
+            &mut Markdown::default(),
+            Context::new(&location, &BlockComponent),
+        )
+        .to_string();
+
+        assert_eq!(
+            rendered,
+            "- [First](#first)\n    - Subsection\n- [Second](#second)\n"
+        );
+    }
+
+    #[test]
+    fn toc_as_text() {
+        let location = InternalPath::parse("foo/index.html");
+        let rendered = RenderAsDisplay::new(
+            make_toc(),
+            &mut Text::default(),
+            Context::new(&location, &BlockComponent),
+        )
+        .to_string();
+
+        assert_eq!(rendered, "- First\n    - Subsection\n- Second\n");
+    }
+}
@@ -1,11 +1,17 @@
 //! This module exports components that are of type inline, as well their kind.
 
 use super::{Component, ComponentKind};
-use crate::render::{Context, Html, Markdown, Render, Renderer, Text};
+use crate::{
+    render::{Context, Gemtext, Html, Latex, Markdown, Render, Renderer, Text},
+    visit::{visit_node, Node, Visit, Visitor},
+};
 use std::fmt::{self, Write};
 
 pub mod text;
 pub mod media;
+pub mod footnote;
+pub mod cite;
+pub mod math;
 
 /// An inline component. Such component is one that can appear in the middle of
 /// reading text.
@@ -41,6 +47,25 @@ fn md_escape(ch: char) -> Option<&'static str> {
     }
 }
 
+fn latex_escape(ch: char) -> Option<&'static str> {
+    match ch {
+        '\\' => Some("\\textbackslash{}"),
+        '{' => Some("\\{"),
+        '}' => Some("\\}"),
+        '$' => Some("\\$"),
+        '&' => Some("\\&"),
+        '#' => Some("\\#"),
+        '%' => Some("\\%"),
+        '_' => Some("\\_"),
+        '^' => Some("\\textasciicircum{}"),
+        '~' => Some("\\textasciitilde{}"),
+        '<' => Some("\\textless{}"),
+        '>' => Some("\\textgreater{}"),
+        '|' => Some("\\textbar{}"),
+        _ => None,
+    }
+}
+
 impl Component for str {
     type Kind = InlineComponent;
 }
@@ -99,6 +124,47 @@ impl Render<Text> for str {
     }
 }
 
+impl Render<Latex> for str {
+    fn render(
+        &self,
+        renderer: &mut Renderer<Latex>,
+        _ctx: Context<Self::Kind>,
+    ) -> fmt::Result {
+        let mut start = 0;
+        let iter = self
+            .char_indices()
+            .filter_map(|(i, ch)| latex_escape(ch).map(|s| (i, s)));
+
+        for (end, escape) in iter {
+            renderer.write_str(&self[start .. end])?;
+            renderer.write_str(escape)?;
+            start = end + 1;
+        }
+
+        renderer.write_str(&self[start ..])?;
+        Ok(())
+    }
+}
+
+impl Render<Gemtext> for str {
+    fn render(
+        &self,
+        renderer: &mut Renderer<Gemtext>,
+        _ctx: Context<Self::Kind>,
+    ) -> fmt::Result {
+        renderer.write_str(self)
+    }
+}
+
+impl Visit for str {
+    fn visit<V>(&self, visitor: &mut V, ctx: Context<Self::Kind>)
+    where
+        V: Visitor + ?Sized,
+    {
+        visit_node(visitor, Node::Text(self), ctx, |_| ());
+    }
+}
+
 impl Component for String {
     type Kind = InlineComponent;
 }
@@ -133,12 +199,41 @@ impl Render<Text> for String {
     }
 }
 
+impl Render<Latex> for String {
+    fn render(
+        &self,
+        renderer: &mut Renderer<Latex>,
+        ctx: Context<Self::Kind>,
+    ) -> fmt::Result {
+        (**self).render(renderer, ctx)
+    }
+}
+
+impl Render<Gemtext> for String {
+    fn render(
+        &self,
+        renderer: &mut Renderer<Gemtext>,
+        ctx: Context<Self::Kind>,
+    ) -> fmt::Result {
+        (**self).render(renderer, ctx)
+    }
+}
+
+impl Visit for String {
+    fn visit<V>(&self, visitor: &mut V, ctx: Context<Self::Kind>)
+    where
+        V: Visitor + ?Sized,
+    {
+        (**self).visit(visitor, ctx)
+    }
+}
+
 #[cfg(test)]
 mod test {
     use super::InlineComponent;
     use crate::{
         location::InternalPath,
-        render::{Context, Html, Markdown, RenderAsDisplay, Text},
+        render::{Context, Html, Latex, Markdown, RenderAsDisplay, Text},
     };
 
     #[test]
@@ -189,6 +284,21 @@ mod test {
         assert_eq!(rendered, "abc def &quot; g &lt;&#47;&gt; &amp;");
     }
 
+    #[test]
+    fn render_str_as_latex_escape() {
+        let rendered = RenderAsDisplay::new(
+            "50% of {a_b} & \\c ~",
+            &mut Latex::default(),
+            Context::new(&InternalPath::default(), &InlineComponent),
+        )
+        .to_string();
+
+        assert_eq!(
+            rendered,
+            "50\\% of \\{a\\_b\\} \\& \\textbackslash{}c \\textasciitilde{}"
+        );
+    }
+
     #[test]
     fn render_str_as_text() {
         let rendered = RenderAsDisplay::new(
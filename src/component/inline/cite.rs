@@ -0,0 +1,293 @@
+//! This module exports the citation model and the inline citation component.
+
+use super::InlineComponent;
+use crate::{
+    component::Component,
+    location::{Id, InternalLoc, Location},
+    render::{
+        latex,
+        Context,
+        Gemtext,
+        Html,
+        Latex,
+        Markdown,
+        Render,
+        Renderer,
+        Text,
+    },
+    visit::{visit_node, Node, Visit, Visitor},
+};
+use std::{
+    cell::RefCell,
+    fmt::{self, Write},
+    sync::Arc,
+};
+use url::Url;
+
+/// Style of citations and bibliographies.
+#[derive(Debug, Clone, Copy, PartialEq, Eq, PartialOrd, Ord, Hash, Default)]
+pub enum CitationStyle {
+    /// Citations show the authors and the year of the source, e.g.
+    /// "(Knuth 1984)", and bibliographies are sorted by authors and year.
+    #[default]
+    AuthorYear,
+    /// Citations show the number of the source, e.g. "\[1\]", sources being
+    /// numbered in order of first citation.
+    Numeric,
+}
+
+/// A source that can be cited, such as a book or a paper.
+#[derive(Debug, Clone, PartialEq, Eq, PartialOrd, Ord, Hash)]
+pub struct Source {
+    /// Key identifying the source, from which the ID of its bibliography
+    /// entry is generated (see [`Source::entry_id`]).
+    pub key: Id,
+    /// Authors of the source, as they should be displayed, e.g. their
+    /// surnames.
+    pub authors: Vec<String>,
+    /// Title of the source.
+    pub title: String,
+    /// Year of publication, if known.
+    pub year: Option<i32>,
+    /// Publisher of the source, if any.
+    pub publisher: Option<String>,
+    /// URL where the source can be found, if any.
+    pub url: Option<Url>,
+}
+
+impl Source {
+    /// ID of the bibliography entry of this source: its key prefixed by
+    /// `pedia-source-`.
+    pub fn entry_id(&self) -> Id {
+        Id::new(format!("pedia-source-{}", self.key))
+    }
+
+    /// Authors as shown by author-year citations: "A", "A and B", or "A et
+    /// al." for more than two authors.
+    pub fn short_authors(&self) -> String {
+        match &self.authors[..] {
+            [] => String::new(),
+            [author] => author.clone(),
+            [first, second] => format!("{} and {}", first, second),
+            [first, ..] => format!("{} et al.", first),
+        }
+    }
+
+    /// Authors as shown by bibliography entries: "A", "A and B", or "A, B
+    /// and C" for more than two authors.
+    pub fn full_authors(&self) -> String {
+        match &self.authors[..] {
+            [] => String::new(),
+            [author] => author.clone(),
+            [init @ .., last] => format!("{} and {}", init.join(", "), last),
+        }
+    }
+
+    /// Year as shown by citations and entries, "n.d." if unknown.
+    pub fn year_label(&self) -> String {
+        match self.year {
+            Some(year) => year.to_string(),
+            None => String::from("n.d."),
+        }
+    }
+}
+
+/// Sources cited on a page being rendered, in order of first citation, along
+/// with the citation style of the page (see [`Context::citations`]).
+#[derive(Debug, Default)]
+pub struct Citations {
+    style: CitationStyle,
+    cited: RefCell<Vec<Arc<Source>>>,
+}
+
+impl Citations {
+    /// Creates an empty collection of citations in the given style.
+    pub fn new(style: CitationStyle) -> Self {
+        Self { style, cited: RefCell::default() }
+    }
+
+    /// Yields the citation style of the page.
+    pub fn style(&self) -> CitationStyle {
+        self.style
+    }
+
+    /// Records a citation of the given source, yielding the number of the
+    /// source, i.e. its position in order of first citation, starting from 1.
+    /// Sources are told apart by their keys.
+    pub fn cite(&self, source: &Arc<Source>) -> u32 {
+        let mut cited = self.cited.borrow_mut();
+        let position = cited.iter().position(|other| other.key == source.key);
+        let index = match position {
+            Some(index) => index,
+            None => {
+                cited.push(source.clone());
+                cited.len() - 1
+            },
+        };
+        index as u32 + 1
+    }
+
+    /// Yields the sources cited so far, in order of first citation.
+    pub fn cited(&self) -> Vec<Arc<Source>> {
+        self.cited.borrow().clone()
+    }
+}
+
+/// A citation of a source, possibly of specific pages of it.
+#[derive(Debug, Clone, PartialEq, Eq, PartialOrd, Ord, Hash)]
+pub struct Citation {
+    /// The cited source. Shared, since a source is usually cited many times.
+    pub source: Arc<Source>,
+    /// Cited pages, as they should be displayed, e.g. "p. 42".
+    pub pages: Option<String>,
+}
+
+impl Citation {
+    /// Label of this citation in the given style, given the number of its
+    /// source: e.g. "(Knuth 1984, p. 42)" or "\[1, p. 42\]".
+    pub fn label(&self, style: CitationStyle, number: u32) -> String {
+        let mut label = match style {
+            CitationStyle::AuthorYear => format!(
+                "({} {}",
+                self.source.short_authors(),
+                self.source.year_label()
+            ),
+            CitationStyle::Numeric => format!("[{}", number),
+        };
+        if let Some(pages) = &self.pages {
+            label.push_str(", ");
+            label.push_str(pages);
+        }
+        label.push(match style {
+            CitationStyle::AuthorYear => ')',
+            CitationStyle::Numeric => ']',
+        });
+        label
+    }
+}
+
+/// An inline citation, linking to the entry of the cited source in the
+/// bibliography of the page (see
+/// [`Bibliography`](crate::component::block::bibliography::Bibliography)).
+/// The source is recorded in the citations of the page (see
+/// [`Context::citations`]), whose style is used. Outside of a page, the
+/// author-year style is used. In a label (see [`Context::as_label`]), only
+/// the citation label is rendered, without linking.
+///
+/// # HTML Classes
+///
+/// - `pedia-cite` attached to an `<a>` element.
+#[derive(Debug, Clone, PartialEq, Eq, PartialOrd, Ord, Hash)]
+pub struct Cite(pub Citation);
+
+impl Cite {
+    /// Records the citation and yields its label, along with the location of
+    /// the bibliography entry, which is notified as referenced.
+    fn cite(&self, ctx: Context<InlineComponent>) -> (String, Location) {
+        let label = match ctx.citations() {
+            Some(citations) => {
+                let number = citations.cite(&self.0.source);
+                self.0.label(citations.style(), number)
+            },
+            None => self.0.label(CitationStyle::AuthorYear, 0),
+        };
+        let loc = InternalLoc {
+            path: ctx.location().clone(),
+            id: Some(self.0.source.entry_id()),
+        };
+        ctx.reference(&loc);
+        (label, Location::Internal(loc))
+    }
+}
+
+impl Component for Cite {
+    type Kind = InlineComponent;
+}
+
+impl Render<Html> for Cite {
+    fn render(
+        &self,
+        renderer: &mut Renderer<Html>,
+        ctx: Context<Self::Kind>,
+    ) -> fmt::Result {
+        let (label, location) = self.cite(ctx);
+        if ctx.in_label() {
+            return label.render(renderer, ctx);
+        }
+        renderer.write_str("<a class=\"pedia-cite\" href=\"")?;
+        location.render(renderer, ctx)?;
+        renderer.write_str("\">")?;
+        label.render(renderer, ctx)?;
+        renderer.write_str("</a>")?;
+        Ok(())
+    }
+}
+
+impl Render<Markdown> for Cite {
+    fn render(
+        &self,
+        renderer: &mut Renderer<Markdown>,
+        ctx: Context<Self::Kind>,
+    ) -> fmt::Result {
+        let (label, location) = self.cite(ctx);
+        if ctx.in_label() {
+            return label.render(renderer, ctx);
+        }
+        renderer.write_str("[")?;
+        label.render(renderer, ctx)?;
+        renderer.write_str("](")?;
+        location.render(renderer, ctx)?;
+        renderer.write_str(")")?;
+        Ok(())
+    }
+}
+
+impl Render<Text> for Cite {
+    fn render(
+        &self,
+        renderer: &mut Renderer<Text>,
+        ctx: Context<Self::Kind>,
+    ) -> fmt::Result {
+        let (label, _) = self.cite(ctx);
+        label.render(renderer, ctx)
+    }
+}
+
+impl Render<Latex> for Cite {
+    fn render(
+        &self,
+        renderer: &mut Renderer<Latex>,
+        ctx: Context<Self::Kind>,
+    ) -> fmt::Result {
+        let (label, _) = self.cite(ctx);
+        if ctx.in_label() {
+            return label.render(renderer, ctx);
+        }
+        let entry_id = self.0.source.entry_id();
+        let target = latex::label(ctx.location(), Some(&entry_id));
+        write!(renderer, "\\hyperref[{}]{{", target)?;
+        label.render(renderer, ctx)?;
+        renderer.write_str("}")?;
+        Ok(())
+    }
+}
+
+impl Render<Gemtext> for Cite {
+    fn render(
+        &self,
+        renderer: &mut Renderer<Gemtext>,
+        ctx: Context<Self::Kind>,
+    ) -> fmt::Result {
+        let (label, _) = self.cite(ctx);
+        label.render(renderer, ctx)
+    }
+}
+
+impl Visit for Cite {
+    fn visit<V>(&self, visitor: &mut V, ctx: Context<Self::Kind>)
+    where
+        V: Visitor + ?Sized,
+    {
+        visit_node(visitor, Node::Cite(&self.0), ctx, |_| ());
+    }
+}
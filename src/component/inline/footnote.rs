@@ -0,0 +1,206 @@
+//! This module exports the inline footnote component.
+
+use super::InlineComponent;
+use crate::{
+    component::Component,
+    render::{
+        gemtext,
+        Context,
+        Format,
+        Gemtext,
+        Html,
+        Latex,
+        Markdown,
+        Render,
+        Renderer,
+        Text,
+    },
+    visit::{visit_node, Node, Visit, Visitor},
+};
+use std::fmt::{self, Write};
+
+/// A footnote, whose contents are listed apart from the text, and which is
+/// replaced in the text by its number. Footnotes are numbered automatically
+/// per page, and are listed by a
+/// [`FootnoteList`](crate::component::block::footnote::FootnoteList), or at
+/// the end of the page.
+///
+/// Outside of a page, i.e. when the context has no
+/// [`Footnotes`](crate::render::Footnotes) collection, the contents are
+/// rendered in place, between parentheses. In LaTeX, footnotes are always
+/// rendered by LaTeX itself, through `\footnote`. In a label (see
+/// [`Context::as_label`]), footnotes are not rendered at all.
+///
+/// # HTML Classes
+///
+/// - `pedia-footnote-ref` attached to a `<sup>` element, whose ID is
+///   `pedia-footnote-ref-` followed by the number, linking to the listed
+///   footnote.
+#[derive(Debug, Clone, Copy, PartialEq, Eq, PartialOrd, Ord, Hash, Default)]
+pub struct Footnote<C>(pub C)
+where
+    C: Component<Kind = InlineComponent>;
+
+impl<C> Component for Footnote<C>
+where
+    C: Component<Kind = InlineComponent>,
+{
+    type Kind = InlineComponent;
+}
+
+impl<C> Render<Html> for Footnote<C>
+where
+    C: Render<Html, Kind = InlineComponent>,
+{
+    fn render(
+        &self,
+        renderer: &mut Renderer<Html>,
+        ctx: Context<Self::Kind>,
+    ) -> fmt::Result {
+        if ctx.in_label() {
+            return Ok(());
+        }
+        match collect(&self.0, renderer, ctx)? {
+            Some(number) => write!(
+                renderer,
+                "<sup class=\"pedia-footnote-ref\" \
+                 id=\"pedia-footnote-ref-{0}\"><a \
+                 href=\"#pedia-footnote-{0}\">[{0}]</a></sup>",
+                number
+            ),
+            None => render_in_place(&self.0, renderer, ctx),
+        }
+    }
+}
+
+impl<C> Render<Markdown> for Footnote<C>
+where
+    C: Render<Markdown, Kind = InlineComponent>,
+{
+    fn render(
+        &self,
+        renderer: &mut Renderer<Markdown>,
+        ctx: Context<Self::Kind>,
+    ) -> fmt::Result {
+        if ctx.in_label() {
+            return Ok(());
+        }
+        match collect(&self.0, renderer, ctx)? {
+            Some(number) => write!(renderer, "[^{}]", number),
+            None => render_in_place(&self.0, renderer, ctx),
+        }
+    }
+}
+
+impl<C> Render<Text> for Footnote<C>
+where
+    C: Render<Text, Kind = InlineComponent>,
+{
+    fn render(
+        &self,
+        renderer: &mut Renderer<Text>,
+        ctx: Context<Self::Kind>,
+    ) -> fmt::Result {
+        if ctx.in_label() {
+            return Ok(());
+        }
+        match collect(&self.0, renderer, ctx)? {
+            Some(number) => write!(renderer, "[{}]", number),
+            None => render_in_place(&self.0, renderer, ctx),
+        }
+    }
+}
+
+impl<C> Render<Latex> for Footnote<C>
+where
+    C: Render<Latex, Kind = InlineComponent>,
+{
+    fn render(
+        &self,
+        renderer: &mut Renderer<Latex>,
+        ctx: Context<Self::Kind>,
+    ) -> fmt::Result {
+        if ctx.in_label() {
+            return Ok(());
+        }
+        renderer.write_str("\\footnote{")?;
+        self.0.render(renderer, ctx)?;
+        renderer.write_str("}")?;
+        Ok(())
+    }
+}
+
+impl<C> Render<Gemtext> for Footnote<C>
+where
+    C: Render<Gemtext, Kind = InlineComponent>,
+{
+    fn render(
+        &self,
+        renderer: &mut Renderer<Gemtext>,
+        ctx: Context<Self::Kind>,
+    ) -> fmt::Result {
+        if ctx.in_label() {
+            return Ok(());
+        }
+        let collected = renderer.scoped(gemtext::Line, |renderer| {
+            collect(&self.0, renderer, ctx)
+        })?;
+        match collected {
+            Some(number) => write!(renderer, "[{}]", number),
+            None => render_in_place(&self.0, renderer, ctx),
+        }
+    }
+}
+
+impl<C> Visit for Footnote<C>
+where
+    C: Visit<Kind = InlineComponent>,
+{
+    fn visit<V>(&self, visitor: &mut V, ctx: Context<Self::Kind>)
+    where
+        V: Visitor + ?Sized,
+    {
+        visit_node(visitor, Node::Footnote, ctx, |visitor| {
+            self.0.visit(visitor, ctx)
+        });
+    }
+}
+
+/// Renders the contents of a footnote into the footnotes collection of the
+/// context, if any, yielding the number of the footnote.
+fn collect<C, W>(
+    contents: &C,
+    renderer: &mut Renderer<W>,
+    ctx: Context<InlineComponent>,
+) -> Result<Option<u32>, fmt::Error>
+where
+    C: Render<W, Kind = InlineComponent>,
+    W: Format + ?Sized,
+{
+    let Some(footnotes) = ctx.footnotes() else {
+        return Ok(None);
+    };
+    let number = footnotes.next_number();
+    let mut rendered = String::new();
+    let mut footnote_renderer =
+        Renderer::new(renderer.format_mut(), &mut rendered);
+    contents.render(&mut footnote_renderer, ctx)?;
+    footnotes.push(number, rendered);
+    Ok(Some(number))
+}
+
+/// Renders the contents of a footnote in place, between parentheses.
+fn render_in_place<C, W>(
+    contents: &C,
+    renderer: &mut Renderer<W>,
+    ctx: Context<InlineComponent>,
+) -> fmt::Result
+where
+    C: Render<W, Kind = InlineComponent>,
+    W: Format + ?Sized,
+{
+    renderer.write_str("(")?;
+    contents.render(renderer, ctx)?;
+    renderer.write_str(")")?;
+    Ok(())
+}
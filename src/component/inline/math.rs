@@ -0,0 +1,857 @@
+//! This module exports the math expression model and parser, as well as the
+//! inline math component.
+
+use super::InlineComponent;
+use crate::{
+    component::{Component, ComponentKind},
+    render::{Context, Gemtext, Html, Latex, Markdown, Render, Renderer, Text},
+    visit::{visit_node, Node, Visit, Visitor},
+};
+use std::{
+    error::Error,
+    fmt::{self, Write},
+};
+
+/// Greek letters, by command name.
+const GREEK: &[(&str, &str)] = &[
+    ("alpha", "α"),
+    ("beta", "β"),
+    ("gamma", "γ"),
+    ("delta", "δ"),
+    ("epsilon", "ϵ"),
+    ("varepsilon", "ε"),
+    ("zeta", "ζ"),
+    ("eta", "η"),
+    ("theta", "θ"),
+    ("vartheta", "ϑ"),
+    ("iota", "ι"),
+    ("kappa", "κ"),
+    ("lambda", "λ"),
+    ("mu", "μ"),
+    ("nu", "ν"),
+    ("xi", "ξ"),
+    ("pi", "π"),
+    ("rho", "ρ"),
+    ("sigma", "σ"),
+    ("tau", "τ"),
+    ("upsilon", "υ"),
+    ("phi", "ϕ"),
+    ("varphi", "φ"),
+    ("chi", "χ"),
+    ("psi", "ψ"),
+    ("omega", "ω"),
+    ("Gamma", "Γ"),
+    ("Delta", "Δ"),
+    ("Theta", "Θ"),
+    ("Lambda", "Λ"),
+    ("Xi", "Ξ"),
+    ("Pi", "Π"),
+    ("Sigma", "Σ"),
+    ("Upsilon", "Υ"),
+    ("Phi", "Φ"),
+    ("Psi", "Ψ"),
+    ("Omega", "Ω"),
+];
+
+/// Symbols that are not operators, by command name.
+const SYMBOLS: &[(&str, &str)] = &[
+    ("infty", "∞"),
+    ("partial", "∂"),
+    ("nabla", "∇"),
+    ("emptyset", "∅"),
+    ("ell", "ℓ"),
+];
+
+/// Operators and relations, by command name.
+const OPERATORS: &[(&str, &str)] = &[
+    ("cdot", "⋅"),
+    ("times", "×"),
+    ("div", "÷"),
+    ("pm", "±"),
+    ("mp", "∓"),
+    ("leq", "≤"),
+    ("le", "≤"),
+    ("geq", "≥"),
+    ("ge", "≥"),
+    ("neq", "≠"),
+    ("ne", "≠"),
+    ("approx", "≈"),
+    ("equiv", "≡"),
+    ("sim", "∼"),
+    ("to", "→"),
+    ("rightarrow", "→"),
+    ("leftarrow", "←"),
+    ("Rightarrow", "⇒"),
+    ("Leftarrow", "⇐"),
+    ("iff", "⟺"),
+    ("in", "∈"),
+    ("notin", "∉"),
+    ("subset", "⊂"),
+    ("subseteq", "⊆"),
+    ("cup", "∪"),
+    ("cap", "∩"),
+    ("forall", "∀"),
+    ("exists", "∃"),
+    ("neg", "¬"),
+    ("land", "∧"),
+    ("lor", "∨"),
+    ("ldots", "…"),
+    ("cdots", "⋯"),
+    ("prime", "′"),
+    ("lt", "<"),
+    ("gt", ">"),
+    ("{", "{"),
+    ("}", "}"),
+    ("|", "‖"),
+];
+
+/// Large operators, whose scripts are limits, by command name.
+const LARGE_OPERATORS: &[(&str, &str)] = &[
+    ("sum", "∑"),
+    ("prod", "∏"),
+    ("coprod", "∐"),
+    ("int", "∫"),
+    ("iint", "∬"),
+    ("oint", "∮"),
+    ("bigcup", "⋃"),
+    ("bigcap", "⋂"),
+];
+
+/// Named functions, written upright.
+const FUNCTIONS: &[&str] = &[
+    "sin", "cos", "tan", "cot", "sec", "csc", "arcsin", "arccos", "arctan",
+    "sinh", "cosh", "tanh", "log", "ln", "lg", "exp", "lim", "sup", "inf",
+    "max", "min", "det", "gcd", "deg", "dim", "ker", "arg",
+];
+
+/// Spacing commands.
+const SPACES: &[&str] = &[",", ":", ";", " ", "quad", "qquad"];
+
+/// Kind of an error in a math expression.
+#[derive(Debug, Clone, PartialEq, Eq, PartialOrd, Ord, Hash)]
+pub enum MathErrorKind {
+    /// A command that is not supported, e.g. `\foo`.
+    UnknownCommand(String),
+    /// A character that is not supported, e.g. `&`.
+    UnexpectedChar(char),
+    /// A group or an optional argument was not closed.
+    Unclosed(char),
+    /// A command or a script lacks an argument.
+    MissingArgument,
+    /// A script has no base, e.g. `^2`.
+    MissingBase,
+    /// A base has two subscripts or two superscripts, e.g. `x^2^3`.
+    DoubleScript,
+}
+
+impl fmt::Display for MathErrorKind {
+    fn fmt(&self, fmtr: &mut fmt::Formatter) -> fmt::Result {
+        match self {
+            Self::UnknownCommand(name) => {
+                write!(fmtr, "unknown command \\{}", name)
+            },
+            Self::UnexpectedChar(ch) => write!(fmtr, "unexpected {:?}", ch),
+            Self::Unclosed(ch) => write!(fmtr, "missing {:?}", ch),
+            Self::MissingArgument => write!(fmtr, "missing argument"),
+            Self::MissingBase => write!(fmtr, "script without a base"),
+            Self::DoubleScript => write!(fmtr, "double script"),
+        }
+    }
+}
+
+/// Error when an invalid math expression is given to be parsed.
+#[derive(Debug, Clone, PartialEq, Eq, PartialOrd, Ord, Hash)]
+pub struct MathError {
+    /// Offset, in bytes, where the error was found in the expression.
+    pub offset: usize,
+    /// What is wrong with the expression.
+    pub kind: MathErrorKind,
+}
+
+impl fmt::Display for MathError {
+    fn fmt(&self, fmtr: &mut fmt::Formatter) -> fmt::Result {
+        write!(fmtr, "invalid math at byte {}: {}", self.offset, self.kind)
+    }
+}
+
+impl Error for MathError {}
+
+/// A parsed math expression.
+#[derive(Debug, Clone, PartialEq, Eq, PartialOrd, Ord, Hash)]
+pub enum Expr {
+    /// A sequence of expressions, e.g. a group in braces.
+    Row(Vec<Expr>),
+    /// An identifier, e.g. `x`, `α` or `sin`.
+    Ident(String),
+    /// A number, e.g. `3.14`.
+    Number(String),
+    /// An operator, a relation or a delimiter, e.g. `+`, `≤` or `(`.
+    Operator(String),
+    /// A large operator, e.g. `∑`.
+    LargeOperator(String),
+    /// Plain text, e.g. from `\text{if}`.
+    Text(String),
+    /// A fraction, given its numerator and denominator.
+    Frac(Box<Expr>, Box<Expr>),
+    /// A square root.
+    Sqrt(Box<Expr>),
+    /// A root, given its radicand and index.
+    Root(Box<Expr>, Box<Expr>),
+    /// A base with a subscript and/or a superscript.
+    Scripts {
+        /// The base.
+        base: Box<Expr>,
+        /// The subscript, if any.
+        sub: Option<Box<Expr>>,
+        /// The superscript, if any.
+        sup: Option<Box<Expr>>,
+    },
+}
+
+impl Expr {
+    /// Parses an expression from a subset of TeX: letters, numbers, operators,
+    /// groups in braces, subscripts and superscripts (`_`, `^`), fractions
+    /// (`\frac`), roots (`\sqrt`, with an optional index), greek letters
+    /// (e.g. `\alpha`), large operators (e.g. `\sum`), named functions (e.g.
+    /// `\sin`), common operators and symbols (e.g. `\leq`, `\infty`), text
+    /// (`\text`), spacing and delimiters (`\left`, `\right`).
+    pub fn parse(source: &str) -> Result<Self, MathError> {
+        let mut parser = Parser { source, pos: 0 };
+        Ok(Self::Row(parser.row(None)?))
+    }
+
+    /// Writes this expression as MathML elements, without the enclosing
+    /// `<math>` element. Rows are written as `<mrow>` elements, except for
+    /// the outermost one.
+    pub fn write_mathml<O>(&self, output: &mut O) -> fmt::Result
+    where
+        O: Write + ?Sized,
+    {
+        match self {
+            Self::Row(items) => {
+                for item in items {
+                    item.write_mathml_element(output)?;
+                }
+                Ok(())
+            },
+            _ => self.write_mathml_element(output),
+        }
+    }
+
+    /// Linearizes this expression into plain text, e.g. `(a + 1)/b` for
+    /// `\frac{a+1}{b}`.
+    pub fn linearize(&self) -> String {
+        match self {
+            Self::Row(items) => {
+                let mut text = String::new();
+                for (i, item) in items.iter().enumerate() {
+                    let is_first = i == 0;
+                    let is_last = i + 1 == items.len();
+                    match item {
+                        Self::Operator(op) if is_spaced(op) && !is_first => {
+                            text.push(' ');
+                            text.push_str(op);
+                            if !is_last {
+                                text.push(' ');
+                            }
+                        },
+                        Self::Operator(op) if op == "," || op == ";" => {
+                            text.push_str(op);
+                            if !is_last {
+                                text.push(' ');
+                            }
+                        },
+                        _ => {
+                            text.push_str(&item.linearize());
+                            if item.is_word() && !is_last {
+                                text.push(' ');
+                            }
+                        },
+                    }
+                }
+                text
+            },
+            Self::Ident(text)
+            | Self::Number(text)
+            | Self::Operator(text)
+            | Self::LargeOperator(text)
+            | Self::Text(text) => text.clone(),
+            Self::Frac(num, den) => {
+                format!("{}/{}", num.linearize_group(), den.linearize_group())
+            },
+            Self::Sqrt(radicand) => format!("√({})", radicand.linearize()),
+            Self::Root(radicand, index) => {
+                format!("√[{}]({})", index.linearize(), radicand.linearize())
+            },
+            Self::Scripts { base, sub, sup } => {
+                let mut text = base.linearize_group();
+                if let Some(sub) = sub {
+                    text.push('_');
+                    text.push_str(&sub.linearize_group());
+                }
+                if let Some(sup) = sup {
+                    text.push('^');
+                    text.push_str(&sup.linearize_group());
+                }
+                text
+            },
+        }
+    }
+
+    /// Writes this expression as a single MathML element.
+    fn write_mathml_element<O>(&self, output: &mut O) -> fmt::Result
+    where
+        O: Write + ?Sized,
+    {
+        match self {
+            Self::Row(items) => match &items[..] {
+                [item] => item.write_mathml_element(output),
+                _ => {
+                    output.write_str("<mrow>")?;
+                    self.write_mathml(output)?;
+                    output.write_str("</mrow>")
+                },
+            },
+            Self::Ident(text) => write_leaf(output, "mi", text),
+            Self::Number(text) => write_leaf(output, "mn", text),
+            Self::Operator(text) => write_leaf(output, "mo", text),
+            Self::LargeOperator(text) => write_leaf(output, "mo", text),
+            Self::Text(text) => write_leaf(output, "mtext", text),
+            Self::Frac(num, den) => {
+                output.write_str("<mfrac>")?;
+                num.write_mathml_element(output)?;
+                den.write_mathml_element(output)?;
+                output.write_str("</mfrac>")
+            },
+            Self::Sqrt(radicand) => {
+                output.write_str("<msqrt>")?;
+                radicand.write_mathml(output)?;
+                output.write_str("</msqrt>")
+            },
+            Self::Root(radicand, index) => {
+                output.write_str("<mroot>")?;
+                radicand.write_mathml_element(output)?;
+                index.write_mathml_element(output)?;
+                output.write_str("</mroot>")
+            },
+            Self::Scripts { base, sub, sup } => {
+                let is_large = matches!(**base, Self::LargeOperator(_));
+                let tag = match (sub, sup, is_large) {
+                    (Some(_), None, false) => "msub",
+                    (None, Some(_), false) => "msup",
+                    (Some(_), Some(_), false) => "msubsup",
+                    (Some(_), None, true) => "munder",
+                    (None, Some(_), true) => "mover",
+                    (Some(_), Some(_), true) => "munderover",
+                    (None, None, _) => {
+                        return base.write_mathml_element(output)
+                    },
+                };
+                write!(output, "<{}>", tag)?;
+                base.write_mathml_element(output)?;
+                for script in [sub, sup].into_iter().flatten() {
+                    script.write_mathml_element(output)?;
+                }
+                write!(output, "</{}>", tag)
+            },
+        }
+    }
+
+    /// Linearizes this expression, between parentheses unless it is simple.
+    fn linearize_group(&self) -> String {
+        if self.is_simple() {
+            self.linearize()
+        } else {
+            format!("({})", self.linearize())
+        }
+    }
+
+    /// Tests whether this expression is linearized as a single unit.
+    fn is_simple(&self) -> bool {
+        match self {
+            Self::Row(items) => match &items[..] {
+                [item] => item.is_simple(),
+                _ => false,
+            },
+            Self::Ident(_)
+            | Self::Number(_)
+            | Self::Operator(_)
+            | Self::LargeOperator(_)
+            | Self::Sqrt(_)
+            | Self::Root(..) => true,
+            Self::Text(text) => !text.contains(' '),
+            Self::Frac(..) | Self::Scripts { .. } => false,
+        }
+    }
+
+    /// Tests whether this expression must be followed by a space when
+    /// linearized, such as a named function or a large operator.
+    fn is_word(&self) -> bool {
+        match self {
+            Self::Ident(text) => text.chars().count() > 1,
+            Self::LargeOperator(_) => true,
+            Self::Scripts { base, .. } => base.is_word(),
+            _ => false,
+        }
+    }
+}
+
+/// Tests whether an operator is surrounded by spaces when linearized.
+fn is_spaced(op: &str) -> bool {
+    !matches!(
+        op,
+        "(" | ")"
+            | "["
+            | "]"
+            | "{"
+            | "}"
+            | "|"
+            | "‖"
+            | "!"
+            | "'"
+            | "′"
+            | ","
+            | ";"
+            | "."
+            | ":"
+            | "…"
+            | "⋯"
+            | "/"
+    )
+}
+
+/// Writes a MathML token element, escaping its text.
+fn write_leaf<O>(output: &mut O, tag: &str, text: &str) -> fmt::Result
+where
+    O: Write + ?Sized,
+{
+    write!(output, "<{}>", tag)?;
+    for ch in text.chars() {
+        match ch {
+            '&' => output.write_str("&amp;")?,
+            '<' => output.write_str("&lt;")?,
+            '>' => output.write_str("&gt;")?,
+            _ => output.write_char(ch)?,
+        }
+    }
+    write!(output, "</{}>", tag)
+}
+
+/// State of the parsing of a math expression.
+#[derive(Debug)]
+struct Parser<'src> {
+    source: &'src str,
+    pos: usize,
+}
+
+impl<'src> Parser<'src> {
+    fn rest(&self) -> &'src str {
+        &self.source[self.pos ..]
+    }
+
+    fn peek(&self) -> Option<char> {
+        self.rest().chars().next()
+    }
+
+    fn bump(&mut self) -> Option<char> {
+        let ch = self.peek()?;
+        self.pos += ch.len_utf8();
+        Some(ch)
+    }
+
+    fn skip_whitespace(&mut self) {
+        while self.peek().is_some_and(char::is_whitespace) {
+            self.bump();
+        }
+    }
+
+    fn error(&self, kind: MathErrorKind) -> MathError {
+        MathError { offset: self.pos, kind }
+    }
+
+    /// Parses a sequence of expressions, up to the given closing character
+    /// (consumed), or up to the end if none.
+    fn row(&mut self, closing: Option<char>) -> Result<Vec<Expr>, MathError> {
+        let mut items = Vec::new();
+        loop {
+            self.skip_whitespace();
+            match self.peek() {
+                None => match closing {
+                    Some(closing) => {
+                        return Err(self.error(MathErrorKind::Unclosed(closing)))
+                    },
+                    None => break,
+                },
+                Some(ch) if Some(ch) == closing => {
+                    self.bump();
+                    break;
+                },
+                Some(ch @ ('^' | '_')) => {
+                    let Some(base) = items.pop() else {
+                        return Err(self.error(MathErrorKind::MissingBase));
+                    };
+                    let offset = self.pos;
+                    self.bump();
+                    let script = Box::new(self.argument()?);
+                    items.push(
+                        attach(base, ch, script)
+                            .map_err(|kind| MathError { offset, kind })?,
+                    );
+                },
+                Some(_) => items.push(self.atom()?),
+            }
+        }
+        Ok(items)
+    }
+
+    /// Parses the argument of a command or a script: a group, a single digit,
+    /// or an atom.
+    fn argument(&mut self) -> Result<Expr, MathError> {
+        self.skip_whitespace();
+        match self.peek() {
+            Some(ch) if ch.is_ascii_digit() => {
+                self.bump();
+                Ok(Expr::Number(ch.to_string()))
+            },
+            Some('^' | '_' | '}' | ']') | None => {
+                Err(self.error(MathErrorKind::MissingArgument))
+            },
+            Some(_) => self.atom(),
+        }
+    }
+
+    /// Parses a single expression without scripts.
+    fn atom(&mut self) -> Result<Expr, MathError> {
+        let start = self.pos;
+        let Some(ch) = self.bump() else {
+            return Err(self.error(MathErrorKind::MissingArgument));
+        };
+        match ch {
+            '{' => Ok(Expr::Row(self.row(Some('}'))?)),
+            '\\' => self.command(start),
+            '0' ..= '9' => {
+                while self
+                    .peek()
+                    .is_some_and(|ch| ch.is_ascii_digit() || ch == '.')
+                {
+                    self.bump();
+                }
+                Ok(Expr::Number(self.source[start .. self.pos].to_string()))
+            },
+            ch if ch.is_alphabetic() => Ok(Expr::Ident(ch.to_string())),
+            '+' | '-' | '*' | '/' | '=' | '<' | '>' | '(' | ')' | '[' | ']'
+            | '|' | ',' | ';' | '.' | ':' | '!' | '\'' => {
+                Ok(Expr::Operator(ch.to_string()))
+            },
+            _ => Err(MathError {
+                offset: start,
+                kind: MathErrorKind::UnexpectedChar(ch),
+            }),
+        }
+    }
+
+    /// Parses a command, whose backslash starts at the given offset and has
+    /// just been consumed.
+    fn command(&mut self, start: usize) -> Result<Expr, MathError> {
+        let name_start = self.pos;
+        match self.bump() {
+            Some(ch) if ch.is_ascii_alphabetic() => {
+                while self.peek().is_some_and(|ch| ch.is_ascii_alphabetic()) {
+                    self.bump();
+                }
+            },
+            Some(_) => (),
+            None => return Err(self.error(MathErrorKind::MissingArgument)),
+        }
+        let name = &self.source[name_start .. self.pos];
+        let lookup = |table: &[(&str, &str)]| {
+            table
+                .iter()
+                .find(|(command, _)| *command == name)
+                .map(|(_, symbol)| symbol.to_string())
+        };
+
+        if let Some(symbol) = lookup(GREEK).or_else(|| lookup(SYMBOLS)) {
+            return Ok(Expr::Ident(symbol));
+        }
+        if let Some(symbol) = lookup(OPERATORS) {
+            return Ok(Expr::Operator(symbol));
+        }
+        if let Some(symbol) = lookup(LARGE_OPERATORS) {
+            return Ok(Expr::LargeOperator(symbol));
+        }
+        if FUNCTIONS.contains(&name) {
+            return Ok(Expr::Ident(name.to_string()));
+        }
+        if SPACES.contains(&name) {
+            return Ok(Expr::Text(String::from(" ")));
+        }
+        match name {
+            "frac" => {
+                let num = self.argument()?;
+                let den = self.argument()?;
+                Ok(Expr::Frac(Box::new(num), Box::new(den)))
+            },
+            "sqrt" => {
+                self.skip_whitespace();
+                let index = if self.peek() == Some('[') {
+                    self.bump();
+                    Some(Expr::Row(self.row(Some(']'))?))
+                } else {
+                    None
+                };
+                let radicand = Box::new(self.argument()?);
+                Ok(match index {
+                    Some(index) => Expr::Root(radicand, Box::new(index)),
+                    None => Expr::Sqrt(radicand),
+                })
+            },
+            "text" => {
+                self.skip_whitespace();
+                if self.bump() != Some('{') {
+                    return Err(self.error(MathErrorKind::MissingArgument));
+                }
+                let text_start = self.pos;
+                let Some(length) = self.rest().find('}') else {
+                    return Err(self.error(MathErrorKind::Unclosed('}')));
+                };
+                self.pos += length + 1;
+                let text = &self.source[text_start .. text_start + length];
+                Ok(Expr::Text(text.to_string()))
+            },
+            "left" | "right" => {
+                self.skip_whitespace();
+                match self.atom()? {
+                    Expr::Operator(op) if op == "." => {
+                        Ok(Expr::Row(Vec::new()))
+                    },
+                    op @ Expr::Operator(_) => Ok(op),
+                    _ => Err(MathError {
+                        offset: start,
+                        kind: MathErrorKind::MissingArgument,
+                    }),
+                }
+            },
+            _ => Err(MathError {
+                offset: start,
+                kind: MathErrorKind::UnknownCommand(name.to_string()),
+            }),
+        }
+    }
+}
+
+/// Attaches a subscript (`_`) or a superscript (`^`) to a base.
+fn attach(
+    base: Expr,
+    script_char: char,
+    script: Box<Expr>,
+) -> Result<Expr, MathErrorKind> {
+    let (base, mut sub, mut sup) = match base {
+        Expr::Scripts { base, sub, sup } => (base, sub, sup),
+        base => (Box::new(base), None, None),
+    };
+    let slot = if script_char == '_' { &mut sub } else { &mut sup };
+    if slot.is_some() {
+        return Err(MathErrorKind::DoubleScript);
+    }
+    *slot = Some(script);
+    Ok(Expr::Scripts { base, sub, sup })
+}
+
+/// Parses the source of a math component, reporting a parse error to the
+/// context, so that the rendering fails.
+fn parse<K>(source: &str, ctx: Context<K>) -> Result<Expr, fmt::Error>
+where
+    K: ComponentKind + ?Sized,
+{
+    Expr::parse(source)
+        .map_err(|error| ctx.fail(format_args!("{} in `{}`", error, source)))
+}
+
+/// An inline math formula, given as TeX (see [`Expr::parse`] for the
+/// supported subset). In HTML, it is rendered as MathML, so no script is
+/// required. In Markdown and LaTeX, the source is kept between dollars. In
+/// plaintext and Gemtext, the formula is linearized (see [`Expr::linearize`]).
+/// An invalid formula makes the rendering fail, reporting the error to the
+/// context (see [`Context::fail`]).
+///
+/// # HTML Classes
+///
+/// - `pedia-math` attached to a `<math>` element.
+#[derive(Debug, Clone, PartialEq, Eq, PartialOrd, Ord, Hash, Default)]
+pub struct Math(pub String);
+
+impl Component for Math {
+    type Kind = InlineComponent;
+}
+
+impl Render<Html> for Math {
+    fn render(
+        &self,
+        renderer: &mut Renderer<Html>,
+        ctx: Context<Self::Kind>,
+    ) -> fmt::Result {
+        let expr = parse(&self.0, ctx)?;
+        renderer.write_str("<math class=\"pedia-math\">")?;
+        expr.write_mathml(renderer)?;
+        renderer.write_str("</math>")?;
+        Ok(())
+    }
+}
+
+impl Render<Markdown> for Math {
+    fn render(
+        &self,
+        renderer: &mut Renderer<Markdown>,
+        ctx: Context<Self::Kind>,
+    ) -> fmt::Result {
+        parse(&self.0, ctx)?;
+        write!(renderer, "${}$", self.0.trim())
+    }
+}
+
+impl Render<Text> for Math {
+    fn render(
+        &self,
+        renderer: &mut Renderer<Text>,
+        ctx: Context<Self::Kind>,
+    ) -> fmt::Result {
+        renderer.write_str(&parse(&self.0, ctx)?.linearize())
+    }
+}
+
+impl Render<Latex> for Math {
+    fn render(
+        &self,
+        renderer: &mut Renderer<Latex>,
+        ctx: Context<Self::Kind>,
+    ) -> fmt::Result {
+        parse(&self.0, ctx)?;
+        write!(renderer, "${}$", self.0.trim())
+    }
+}
+
+impl Render<Gemtext> for Math {
+    fn render(
+        &self,
+        renderer: &mut Renderer<Gemtext>,
+        ctx: Context<Self::Kind>,
+    ) -> fmt::Result {
+        renderer.write_str(&parse(&self.0, ctx)?.linearize())
+    }
+}
+
+impl Visit for Math {
+    fn visit<V>(&self, visitor: &mut V, ctx: Context<Self::Kind>)
+    where
+        V: Visitor + ?Sized,
+    {
+        visit_node(visitor, Node::Math(&self.0), ctx, |_| ());
+    }
+}
+
+#[cfg(test)]
+mod test {
+    use super::{Expr, Math, MathError, MathErrorKind};
+    use crate::{
+        component::InlineComponent,
+        location::InternalPath,
+        render::{Context, Html, Markdown, RenderAsDisplay, RenderErrors},
+    };
+    use std::fmt::Write;
+
+    fn mathml(source: &str) -> String {
+        let mut output = String::new();
+        Expr::parse(source).unwrap().write_mathml(&mut output).unwrap();
+        output
+    }
+
+    fn linear(source: &str) -> String {
+        Expr::parse(source).unwrap().linearize()
+    }
+
+    #[test]
+    fn parse_into_mathml() {
+        assert_eq!(
+            mathml("\\frac{a+1}{b} = x_i^2"),
+            "<mfrac><mrow><mi>a</mi><mo>+</mo><mn>1</mn></mrow><mi>b</mi>\
+             </mfrac><mo>=</mo><msubsup><mi>x</mi><mi>i</mi><mn>2</mn>\
+             </msubsup>"
+        );
+        assert_eq!(
+            mathml("\\sum_{i=1}^{n} \\alpha^i"),
+            "<munderover><mo>∑</mo><mrow><mi>i</mi><mo>=</mo><mn>1</mn>\
+             </mrow><mi>n</mi></munderover><msup><mi>α</mi><mi>i</mi></msup>"
+        );
+        assert_eq!(
+            mathml("\\sqrt{2} < \\sqrt[3]{x}"),
+            "<msqrt><mn>2</mn></msqrt><mo>&lt;</mo><mroot><mi>x</mi>\
+             <mn>3</mn></mroot>"
+        );
+    }
+
+    #[test]
+    fn linearize() {
+        assert_eq!(linear("\\frac{a+1}{b} = x_i^2"), "(a + 1)/b = x_i^2");
+        assert_eq!(linear("\\sum_{i=1}^{n} \\alpha^i"), "∑_(i = 1)^n α^i");
+        assert_eq!(linear("\\sin x \\leq 1"), "sin x ≤ 1");
+        assert_eq!(linear("\\sqrt{b^2-4ac}"), "√(b^2 - 4ac)");
+        assert_eq!(linear("f(x, y)"), "f(x, y)");
+    }
+
+    #[test]
+    fn parse_errors() {
+        let error = |source| Expr::parse(source).unwrap_err();
+        assert_eq!(
+            error("x + \\foo"),
+            MathError {
+                offset: 4,
+                kind: MathErrorKind::UnknownCommand(String::from("foo")),
+            }
+        );
+        assert_eq!(error("\\frac{a}{b").kind, MathErrorKind::Unclosed('}'));
+        assert_eq!(error("^2").kind, MathErrorKind::MissingBase);
+        assert_eq!(error("x^2^3").kind, MathErrorKind::DoubleScript);
+        assert_eq!(error("\\frac{a}").kind, MathErrorKind::MissingArgument);
+        assert_eq!(error("a & b").kind, MathErrorKind::UnexpectedChar('&'));
+    }
+
+    #[test]
+    fn invalid_math_fails_rendering() {
+        let location = InternalPath::root();
+        let errors = RenderErrors::new();
+        let mut output = String::new();
+        let result = write!(
+            output,
+            "{}",
+            RenderAsDisplay::new(
+                Math(String::from("\\frac{1}")),
+                &mut Markdown::default(),
+                Context::new(&location, &InlineComponent).with_errors(&errors),
+            )
+        );
+
+        assert!(result.is_err());
+        assert_eq!(
+            errors.take(),
+            ["invalid math at byte 8: missing argument in `\\frac{1}`"]
+        );
+    }
+
+    #[test]
+    fn html_math() {
+        let location = InternalPath::root();
+        let rendered = RenderAsDisplay::new(
+            Math(String::from("e^{i\\pi}")),
+            &mut Html,
+            Context::new(&location, &InlineComponent),
+        )
+        .to_string();
+
+        assert_eq!(
+            rendered,
+            "<math class=\"pedia-math\"><msup><mi>e</mi><mrow><mi>i</mi>\
+             <mi>π</mi></mrow></msup></math>"
+        );
+    }
+}
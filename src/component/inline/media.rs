@@ -4,7 +4,19 @@ use super::InlineComponent;
 use crate::{
     component::Component,
     location::Location,
-    render::{Context, Html, Markdown, Render, Renderer, Text},
+    render::{
+        gemtext,
+        latex,
+        Context,
+        Gemtext,
+        Html,
+        Latex,
+        Markdown,
+        Render,
+        Renderer,
+        Text,
+    },
+    visit::{visit_node, Node, Visit, Visitor},
 };
 use std::fmt::{self, Write};
 
@@ -68,6 +80,51 @@ impl Render<Text> for Audio {
     }
 }
 
+impl Render<Latex> for Audio {
+    fn render(
+        &self,
+        renderer: &mut Renderer<Latex>,
+        ctx: Context<Self::Kind>,
+    ) -> fmt::Result {
+        match &self.location {
+            Location::Url(url) => {
+                let url = latex::escape_url(url.as_str());
+                write!(renderer, "\\href{{{}}}{{", url)?;
+            },
+            Location::Internal(loc) => {
+                ctx.reference(loc);
+                let path = latex::escape_url(&loc.path.to_string());
+                write!(renderer, "\\href{{run:{}}}{{", path)?;
+            },
+        }
+        self.alt.render(renderer, ctx)?;
+        renderer.write_str("}")?;
+        Ok(())
+    }
+}
+
+impl Render<Gemtext> for Audio {
+    fn render(
+        &self,
+        renderer: &mut Renderer<Gemtext>,
+        ctx: Context<Self::Kind>,
+    ) -> fmt::Result {
+        self.alt.render(renderer, ctx)?;
+        let url = gemtext::location_url(&self.location, ctx);
+        renderer.format_mut().hoist_link(url, self.alt.clone());
+        Ok(())
+    }
+}
+
+impl Visit for Audio {
+    fn visit<V>(&self, visitor: &mut V, ctx: Context<Self::Kind>)
+    where
+        V: Visitor + ?Sized,
+    {
+        visit_node(visitor, Node::Audio(self), ctx, |_| ());
+    }
+}
+
 #[cfg(test)]
 mod test {
     use super::Audio;
@@ -89,7 +146,7 @@ mod test {
                 location: Location::internal("abc/def.ogg"),
                 alt: String::from("audio about def"),
             },
-            &mut Html::default(),
+            &mut Html,
             Context::new(&InternalPath::default(), &InlineComponent),
         )
         .to_string();
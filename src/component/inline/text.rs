@@ -3,8 +3,21 @@
 use super::InlineComponent;
 use crate::{
     component::Component,
-    location::Location,
-    render::{Context, Html, Markdown, Render, Renderer, Text},
+    location::{InternalLoc, Location},
+    render::{
+        gemtext,
+        latex,
+        Context,
+        Format,
+        Gemtext,
+        Html,
+        Latex,
+        Markdown,
+        Render,
+        Renderer,
+        Text,
+    },
+    visit::{visit_node, Node, Visit, Visitor},
 };
 use std::fmt::{self, Write};
 
@@ -70,6 +83,49 @@ where
     }
 }
 
+impl<C> Render<Latex> for Bold<C>
+where
+    C: Render<Latex, Kind = InlineComponent>,
+{
+    fn render(
+        &self,
+        renderer: &mut Renderer<Latex>,
+        ctx: Context<Self::Kind>,
+    ) -> fmt::Result {
+        renderer.write_str("\\textbf{")?;
+        self.0.render(renderer, ctx)?;
+        renderer.write_str("}")?;
+        Ok(())
+    }
+}
+
+impl<C> Render<Gemtext> for Bold<C>
+where
+    C: Render<Gemtext, Kind = InlineComponent>,
+{
+    fn render(
+        &self,
+        renderer: &mut Renderer<Gemtext>,
+        ctx: Context<Self::Kind>,
+    ) -> fmt::Result {
+        self.0.render(renderer, ctx)
+    }
+}
+
+impl<C> Visit for Bold<C>
+where
+    C: Visit<Kind = InlineComponent>,
+{
+    fn visit<V>(&self, visitor: &mut V, ctx: Context<Self::Kind>)
+    where
+        V: Visitor + ?Sized,
+    {
+        visit_node(visitor, Node::Bold, ctx, |visitor| {
+            self.0.visit(visitor, ctx)
+        });
+    }
+}
+
 /// This components wraps another component and its text italic.
 ///
 /// # HTML Classes
@@ -132,6 +188,49 @@ where
     }
 }
 
+impl<C> Render<Latex> for Italic<C>
+where
+    C: Render<Latex, Kind = InlineComponent>,
+{
+    fn render(
+        &self,
+        renderer: &mut Renderer<Latex>,
+        ctx: Context<Self::Kind>,
+    ) -> fmt::Result {
+        renderer.write_str("\\textit{")?;
+        self.0.render(renderer, ctx)?;
+        renderer.write_str("}")?;
+        Ok(())
+    }
+}
+
+impl<C> Render<Gemtext> for Italic<C>
+where
+    C: Render<Gemtext, Kind = InlineComponent>,
+{
+    fn render(
+        &self,
+        renderer: &mut Renderer<Gemtext>,
+        ctx: Context<Self::Kind>,
+    ) -> fmt::Result {
+        self.0.render(renderer, ctx)
+    }
+}
+
+impl<C> Visit for Italic<C>
+where
+    C: Visit<Kind = InlineComponent>,
+{
+    fn visit<V>(&self, visitor: &mut V, ctx: Context<Self::Kind>)
+    where
+        V: Visitor + ?Sized,
+    {
+        visit_node(visitor, Node::Italic, ctx, |visitor| {
+            self.0.visit(visitor, ctx)
+        });
+    }
+}
+
 /// This components wraps another component and its text preformatted. Suitable
 /// for code.
 ///
@@ -195,7 +294,51 @@ where
     }
 }
 
-/// This component is embeds a link to another resource in a component.
+impl<C> Render<Latex> for Preformatted<C>
+where
+    C: Render<Latex, Kind = InlineComponent>,
+{
+    fn render(
+        &self,
+        renderer: &mut Renderer<Latex>,
+        ctx: Context<Self::Kind>,
+    ) -> fmt::Result {
+        renderer.write_str("\\texttt{")?;
+        self.0.render(renderer, ctx)?;
+        renderer.write_str("}")?;
+        Ok(())
+    }
+}
+
+impl<C> Render<Gemtext> for Preformatted<C>
+where
+    C: Render<Gemtext, Kind = InlineComponent>,
+{
+    fn render(
+        &self,
+        renderer: &mut Renderer<Gemtext>,
+        ctx: Context<Self::Kind>,
+    ) -> fmt::Result {
+        self.0.render(renderer, ctx)
+    }
+}
+
+impl<C> Visit for Preformatted<C>
+where
+    C: Visit<Kind = InlineComponent>,
+{
+    fn visit<V>(&self, visitor: &mut V, ctx: Context<Self::Kind>)
+    where
+        V: Visitor + ?Sized,
+    {
+        visit_node(visitor, Node::Preformatted, ctx, |visitor| {
+            self.0.visit(visitor, ctx)
+        });
+    }
+}
+
+/// This component is embeds a link to another resource in a component. In a
+/// label (see [`Context::as_label`]), only the contents are rendered.
 ///
 /// # HTML Classes
 ///
@@ -227,6 +370,9 @@ where
         renderer: &mut Renderer<Html>,
         ctx: Context<Self::Kind>,
     ) -> fmt::Result {
+        if ctx.in_label() {
+            return self.target.render(renderer, ctx);
+        }
         renderer.write_str("<a class=\"pedia-link\" href=\"")?;
         self.location.render(renderer, ctx)?;
         renderer.write_str("\">")?;
@@ -245,6 +391,9 @@ where
         renderer: &mut Renderer<Markdown>,
         ctx: Context<Self::Kind>,
     ) -> fmt::Result {
+        if ctx.in_label() {
+            return self.target.render(renderer, ctx);
+        }
         renderer.write_str("[")?;
         self.target.render(renderer, ctx)?;
         renderer.write_str("](")?;
@@ -267,25 +416,292 @@ where
     }
 }
 
+impl<C> Render<Latex> for Link<C>
+where
+    C: Render<Latex, Kind = InlineComponent>,
+{
+    fn render(
+        &self,
+        renderer: &mut Renderer<Latex>,
+        ctx: Context<Self::Kind>,
+    ) -> fmt::Result {
+        if ctx.in_label() {
+            return self.target.render(renderer, ctx);
+        }
+        match &self.location {
+            Location::Url(url) => {
+                let url = latex::escape_url(url.as_str());
+                write!(renderer, "\\href{{{}}}{{", url)?;
+            },
+            Location::Internal(loc) => {
+                ctx.reference(loc);
+                let label = latex::label(&loc.path, loc.id.as_ref());
+                write!(renderer, "\\hyperref[{}]{{", label)?;
+            },
+        }
+        self.target.render(renderer, ctx)?;
+        renderer.write_str("}")?;
+        Ok(())
+    }
+}
+
+impl<C> Render<Gemtext> for Link<C>
+where
+    C: Render<Gemtext, Kind = InlineComponent>,
+{
+    fn render(
+        &self,
+        renderer: &mut Renderer<Gemtext>,
+        ctx: Context<Self::Kind>,
+    ) -> fmt::Result {
+        if ctx.in_label() {
+            return self.target.render(renderer, ctx);
+        }
+        let mut label = String::new();
+        renderer.scoped(gemtext::Line, |renderer| {
+            let mut label_renderer =
+                Renderer::new(renderer.format_mut(), &mut label);
+            self.target.render(&mut label_renderer, ctx)
+        })?;
+        renderer.write_str(&label)?;
+        let url = gemtext::location_url(&self.location, ctx);
+        renderer.format_mut().hoist_link(url, label);
+        Ok(())
+    }
+}
+
+impl<C> Visit for Link<C>
+where
+    C: Visit<Kind = InlineComponent>,
+{
+    fn visit<V>(&self, visitor: &mut V, ctx: Context<Self::Kind>)
+    where
+        V: Visitor + ?Sized,
+    {
+        visit_node(visitor, Node::Link(&self.location), ctx, |visitor| {
+            self.target.visit(visitor, ctx)
+        });
+    }
+}
+
+/// This component embeds a link to a page or section of the site, labelled
+/// with the title of its target: the title of the target page, or the title
+/// of the target section if the location has an ID. Titles are looked up in
+/// the registry of the site (see [`Context::registry`]), and rendering fails
+/// if the target is not registered. Without a registry, the location itself
+/// is displayed.
+///
+/// # HTML Classes
+///
+/// - `pedia-link` attached to an `<a>` element.
+#[derive(Debug, Clone, PartialEq, Eq, PartialOrd, Ord, Hash, Default)]
+pub struct AutoLink {
+    /// The target location.
+    pub location: InternalLoc,
+}
+
+impl AutoLink {
+    /// Resolves the title of the target, yielding a link to the target
+    /// labelled with the title, or failing the rendering if the target is
+    /// not registered.
+    fn resolve<'reg>(
+        &self,
+        ctx: Context<'reg, '_, InlineComponent>,
+    ) -> Result<Link<TargetTitle<'reg>>, fmt::Error> {
+        let location = Location::Internal(self.location.clone());
+        let Some(registry) = ctx.registry() else {
+            let target = TargetTitle::Unresolved(self.location.to_string());
+            return Ok(Link { target, location });
+        };
+
+        let fail = |reason: &str| {
+            ctx.fail(format_args!(
+                "broken auto-titled link to \"{}\": {}",
+                self.location, reason
+            ))
+        };
+        let path = &self.location.path;
+        if !registry.contains_page(path) {
+            return Err(fail("no such page"));
+        }
+        let target = match &self.location.id {
+            Some(id) => match registry.section_title(path, id) {
+                Some(title) => TargetTitle::Section(title),
+                None => return Err(fail("no such section in the page")),
+            },
+            None => match registry.title(path) {
+                Some(title) => TargetTitle::Page(title),
+                None => return Err(fail("the page has no title")),
+            },
+        };
+        Ok(Link { target, location })
+    }
+}
+
+impl Component for AutoLink {
+    type Kind = InlineComponent;
+}
+
+impl Render<Html> for AutoLink {
+    fn render(
+        &self,
+        renderer: &mut Renderer<Html>,
+        ctx: Context<Self::Kind>,
+    ) -> fmt::Result {
+        self.resolve(ctx)?.render(renderer, ctx)
+    }
+}
+
+impl Render<Markdown> for AutoLink {
+    fn render(
+        &self,
+        renderer: &mut Renderer<Markdown>,
+        ctx: Context<Self::Kind>,
+    ) -> fmt::Result {
+        self.resolve(ctx)?.render(renderer, ctx)
+    }
+}
+
+impl Render<Text> for AutoLink {
+    fn render(
+        &self,
+        renderer: &mut Renderer<Text>,
+        ctx: Context<Self::Kind>,
+    ) -> fmt::Result {
+        self.resolve(ctx)?.render(renderer, ctx)
+    }
+}
+
+impl Render<Latex> for AutoLink {
+    fn render(
+        &self,
+        renderer: &mut Renderer<Latex>,
+        ctx: Context<Self::Kind>,
+    ) -> fmt::Result {
+        self.resolve(ctx)?.render(renderer, ctx)
+    }
+}
+
+impl Render<Gemtext> for AutoLink {
+    fn render(
+        &self,
+        renderer: &mut Renderer<Gemtext>,
+        ctx: Context<Self::Kind>,
+    ) -> fmt::Result {
+        self.resolve(ctx)?.render(renderer, ctx)
+    }
+}
+
+impl Visit for AutoLink {
+    fn visit<V>(&self, visitor: &mut V, ctx: Context<Self::Kind>)
+    where
+        V: Visitor + ?Sized,
+    {
+        visit_node(visitor, Node::AutoLink(&self.location), ctx, |_| ());
+    }
+}
+
+/// Label of an auto-titled link.
+#[derive(Debug, Clone, PartialEq, Eq, PartialOrd, Ord, Hash)]
+enum TargetTitle<'reg> {
+    /// Title of a page, as plain text.
+    Page(&'reg str),
+    /// Title of a section, already rendered as a label in the current format
+    /// (see [`Context::as_label`]).
+    Section(&'reg str),
+    /// The target location, displayed when no registry is available.
+    Unresolved(String),
+}
+
+impl<'reg> Component for TargetTitle<'reg> {
+    type Kind = InlineComponent;
+}
+
+impl<'reg, W> Render<W> for TargetTitle<'reg>
+where
+    W: Format + ?Sized,
+    str: Render<W, Kind = InlineComponent>,
+{
+    fn render(
+        &self,
+        renderer: &mut Renderer<W>,
+        ctx: Context<Self::Kind>,
+    ) -> fmt::Result {
+        match self {
+            Self::Page(title) => title.render(renderer, ctx),
+            Self::Section(title) => renderer.write_str(title),
+            Self::Unresolved(location) => {
+                location.as_str().render(renderer, ctx)
+            },
+        }
+    }
+}
+
 #[cfg(test)]
 mod test {
-    use super::{Bold, Italic, Link, Preformatted};
+    use super::{AutoLink, Bold, Italic, Link, Preformatted};
     use crate::{
         component::InlineComponent,
-        location::{InternalPath, Location},
+        location::{Id, InternalLoc, InternalPath, Location},
         render::{
             html::test::validate_html_fragment,
             Context,
+            Format,
+            Gemtext,
             Html,
+            Latex,
+            Markdown,
+            Render,
             RenderAsDisplay,
+            RenderErrors,
+            Text,
         },
+        site::registry::Registry,
     };
+    use std::fmt::Write;
+
+    fn registry() -> Registry {
+        let mut registry = Registry::new();
+        registry.insert_title(
+            InternalPath::parse("bar/index.html"),
+            String::from("Bar & co"),
+        );
+        registry.insert_section(
+            InternalPath::parse("bar/index.html"),
+            Id::new("baz"),
+            String::from("*Baz*"),
+        );
+        registry
+    }
+
+    fn render_auto_link<W>(
+        location: &str,
+        format: &mut W,
+    ) -> Result<String, Vec<String>>
+    where
+        W: Format + ?Sized,
+        AutoLink: Render<W, Kind = InlineComponent>,
+    {
+        let registry = registry();
+        let errors = RenderErrors::new();
+        let path = InternalPath::parse("foo/index.html");
+        let context = Context::new(&path, &InlineComponent)
+            .with_registry(&registry)
+            .with_errors(&errors);
+        let link = AutoLink { location: InternalLoc::parse(location) };
+        let renderer = RenderAsDisplay::new(link, format, context);
+        let mut rendered = String::new();
+        match write!(rendered, "{}", renderer) {
+            Ok(()) => Ok(rendered),
+            Err(_) => Err(errors.take()),
+        }
+    }
 
     #[test]
     fn bold_is_valid_html() {
         let rendered = RenderAsDisplay::new(
             Bold("abc"),
-            &mut Html::default(),
+            &mut Html,
             Context::new(&InternalPath::default(), &InlineComponent),
         )
         .to_string();
@@ -297,7 +713,7 @@ mod test {
     fn italic_is_valid_html() {
         let rendered = RenderAsDisplay::new(
             Italic("abc"),
-            &mut Html::default(),
+            &mut Html,
             Context::new(&InternalPath::default(), &InlineComponent),
         )
         .to_string();
@@ -309,7 +725,7 @@ mod test {
     fn preformatted_is_valid_html() {
         let rendered = RenderAsDisplay::new(
             Preformatted("abc"),
-            &mut Html::default(),
+            &mut Html,
             Context::new(&InternalPath::default(), &InlineComponent),
         )
         .to_string();
@@ -321,11 +737,82 @@ mod test {
     fn link_is_valid_html() {
         let rendered = RenderAsDisplay::new(
             Link { location: Location::internal(""), target: "abc" },
-            &mut Html::default(),
+            &mut Html,
             Context::new(&InternalPath::default(), &InlineComponent),
         )
         .to_string();
 
         validate_html_fragment(&rendered).unwrap();
     }
+
+    #[test]
+    fn link_as_latex() {
+        let rendered = RenderAsDisplay::new(
+            Link { location: Location::internal("foo#bar"), target: Bold("a") },
+            &mut Latex::default(),
+            Context::new(&InternalPath::default(), &InlineComponent),
+        )
+        .to_string();
+        assert_eq!(rendered, "\\hyperref[pedia:foo:bar]{\\textbf{a}}");
+
+        let rendered = RenderAsDisplay::new(
+            Link { location: Location::url("https://a.b/#c"), target: "d" },
+            &mut Latex::default(),
+            Context::new(&InternalPath::default(), &InlineComponent),
+        )
+        .to_string();
+        assert_eq!(rendered, "\\href{https://a.b/\\#c}{d}");
+    }
+
+    #[test]
+    fn auto_link_titles() {
+        let rendered = render_auto_link("bar", &mut Html).unwrap();
+        validate_html_fragment(&rendered).unwrap();
+        assert_eq!(
+            rendered,
+            "<a class=\"pedia-link\" href=\"../bar\">Bar &amp; co</a>"
+        );
+
+        let rendered =
+            render_auto_link("bar#baz", &mut Markdown::default()).unwrap();
+        assert_eq!(rendered, "[*Baz*](../bar#baz)");
+    }
+
+    #[test]
+    fn auto_link_without_registry() {
+        let rendered = RenderAsDisplay::new(
+            AutoLink { location: InternalLoc::parse("bar#baz") },
+            &mut Text::default(),
+            Context::new(&InternalPath::default(), &InlineComponent),
+        )
+        .to_string();
+        assert_eq!(rendered, "bar#baz");
+    }
+
+    #[test]
+    fn auto_link_missing_target_fails() {
+        let page = "broken auto-titled link to \"qux\": no such page";
+        let section = "broken auto-titled link to \"bar#qux\": no such \
+                       section in the page";
+        for (location, message) in [("qux", page), ("bar#qux", section)] {
+            let expected = Err(vec![String::from(message)]);
+            assert_eq!(render_auto_link(location, &mut Html), expected);
+            assert_eq!(
+                render_auto_link(location, &mut Markdown::default()),
+                expected
+            );
+            assert_eq!(
+                render_auto_link(location, &mut Text::default()),
+                expected
+            );
+            assert_eq!(
+                render_auto_link(location, &mut Latex::default()),
+                expected
+            );
+            assert_eq!(
+                render_auto_link(location, &mut Gemtext::default()),
+                expected
+            );
+        }
+    }
 }
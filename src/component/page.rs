@@ -2,6 +2,8 @@
 
 use super::{
     asset::AssetComponent,
+    block::footnote::FootnoteList,
+    inline::cite::Citations,
     section::SectionComponent,
     BlockComponent,
     Component,
@@ -10,7 +12,21 @@ use super::{
 };
 use crate::{
     hseq::IntoIterRef,
-    render::{Context, Html, Markdown, Render, Renderer, Text},
+    location::Location,
+    render::{
+        gemtext,
+        latex::{self, PageStyle},
+        Context,
+        Footnotes,
+        Gemtext,
+        Html,
+        Latex,
+        Markdown,
+        Render,
+        Renderer,
+        Text,
+    },
+    visit::{visit_node, Node, Visit, Visitor},
 };
 use std::{
     cmp::Ordering,
@@ -18,6 +34,10 @@ use std::{
     hash::{Hash, Hasher},
 };
 
+pub mod layout;
+
+use layout::{DefaultLayout, Layout, PageSlots};
+
 /// The kind of page components. A page component is the outermost component in
 /// a page.
 #[derive(Debug, Clone, Copy, PartialEq, Eq, PartialOrd, Ord, Hash, Default)]
@@ -25,28 +45,44 @@ pub struct PageComponent;
 
 impl ComponentKind for PageComponent {}
 
-/// The page: the outermost component in an article/page.
-///
-/// # HTML IDs
-///
-/// - `pedia-page-top` in a `<div>` element wrapping the whole page.
-/// - `pedia-page-body` in a `<div>` element.
-///
-/// # HTML Classes
+/// Optional metadata of a page, describing it to search engines, social
+/// networks and other tools. In HTML, it yields `<meta>` tags (including
+/// OpenGraph ones), the language of the document and its canonical link. In
+/// Markdown, it yields YAML front matter.
+#[derive(Debug, Clone, PartialEq, Eq, PartialOrd, Ord, Hash, Default)]
+pub struct PageMetadata {
+    /// Short description of the page.
+    pub description: Option<String>,
+    /// Names of the authors of the page.
+    pub authors: Vec<String>,
+    /// Date of creation, in W3C Datetime format, e.g. `2024-01-31`.
+    pub created: Option<String>,
+    /// Date of the last update, in W3C Datetime format, e.g. `2024-01-31`.
+    pub updated: Option<String>,
+    /// Language tag of the page, e.g. `en` or `pt-BR`.
+    pub lang: Option<String>,
+    /// Keywords of the page.
+    pub keywords: Vec<String>,
+    /// Canonical location of the page, when it is also published elsewhere.
+    pub canonical: Option<Location>,
+}
+
+/// The page: the outermost component in an article/page. Footnotes of the
+/// page not listed by a [`FootnoteList`] are listed at its end. Citations of
+/// the page are recorded in the collection of the context, if any, or else in
+/// a new one in the author-year style.
 ///
-/// - `pedia-title` in a `<h1>` element, surrounding a `pedia-title-link` in an
-///   `<a>` element (whose link is `#pedia-page-root`).
-/// - `pedia-page-body-wrapper` in a `<div>` element, surrounding the ID
-///   `pedia-page-body`.
-/// - `pedia-page-children` in a `<div>` element, inside
-///   `pedia-page-body-wrapper`, but not `pedia-page-body`.
-pub struct Page<T, A, B, L>
+/// In HTML, the document skeleton around the parts of the page is written by
+/// its [`Layout`], [`DefaultLayout`] unless specified otherwise (see its HTML
+/// IDs and classes).
+pub struct Page<T, A, B, L, Y = DefaultLayout>
 where
     T: Component<Kind = BlockComponent>,
     A: Component<Kind = AssetComponent>,
     B: Component<Kind = BlockComponent>,
     L: IntoIterRef,
     <L as IntoIterRef>::Item: Component<Kind = SectionComponent>,
+    Y: Layout,
 {
     /// Banner placed above everything in the page.
     pub banner: T,
@@ -60,15 +96,81 @@ where
     /// Child sections of the page. Can be an array, a vector, or anything that
     /// iterates by reference.
     pub children: L,
+    /// Metadata of the page, if any.
+    pub metadata: Option<PageMetadata>,
+    /// Layout of the page in HTML.
+    pub layout: Y,
+}
+
+impl<T, A, B, L> Page<T, A, B, L>
+where
+    T: Component<Kind = BlockComponent>,
+    A: Component<Kind = AssetComponent>,
+    B: Component<Kind = BlockComponent>,
+    L: IntoIterRef,
+    <L as IntoIterRef>::Item: Component<Kind = SectionComponent>,
+{
+    /// Creates a page with the given parts, without metadata and with the
+    /// default layout. Unlike a struct literal, this is not affected by new
+    /// optional parts being added to pages.
+    pub fn new(
+        banner: T,
+        title: String,
+        assets: A,
+        body: B,
+        children: L,
+    ) -> Self {
+        Self {
+            banner,
+            title,
+            assets,
+            body,
+            children,
+            metadata: None,
+            layout: DefaultLayout,
+        }
+    }
 }
 
-impl<T, A, B, L> fmt::Debug for Page<T, A, B, L>
+impl<T, A, B, L, Y> Page<T, A, B, L, Y>
 where
     T: Component<Kind = BlockComponent>,
     A: Component<Kind = AssetComponent>,
     B: Component<Kind = BlockComponent>,
     L: IntoIterRef,
     <L as IntoIterRef>::Item: Component<Kind = SectionComponent>,
+    Y: Layout,
+{
+    /// Sets the metadata of the page. Returns the modified page.
+    pub fn with_metadata(self, metadata: PageMetadata) -> Self {
+        Self { metadata: Some(metadata), ..self }
+    }
+
+    /// Sets the layout of the page in HTML. Returns the modified page.
+    pub fn with_layout<Z>(self, layout: Z) -> Page<T, A, B, L, Z>
+    where
+        Z: Layout,
+    {
+        Page {
+            banner: self.banner,
+            title: self.title,
+            assets: self.assets,
+            body: self.body,
+            children: self.children,
+            metadata: self.metadata,
+            layout,
+        }
+    }
+}
+
+impl<T, A, B, L, Y> fmt::Debug for Page<T, A, B, L, Y>
+where
+    T: Component<Kind = BlockComponent>,
+    A: Component<Kind = AssetComponent>,
+    B: Component<Kind = BlockComponent>,
+    L: IntoIterRef,
+    <L as IntoIterRef>::Item: Component<Kind = SectionComponent>,
+    Y: Layout,
 {
     fn fmt(&self, fmtr: &mut fmt::Formatter) -> fmt::Result {
         let mut debug_fmtr = fmtr.debug_struct("Page");
@@ -80,17 +182,21 @@ where
         for (i, element) in self.children.iter().enumerate() {
             debug_fmtr.field(&format!("children[{}]", i), &element);
         }
+        debug_fmtr
+            .field("metadata", &self.metadata)
+            .field("layout", &self.layout);
         debug_fmtr.finish()
     }
 }
 
-impl<T, A, B, L> Clone for Page<T, A, B, L>
+impl<T, A, B, L, Y> Clone for Page<T, A, B, L, Y>
 where
     T: Component<Kind = BlockComponent> + Clone,
     A: Component<Kind = AssetComponent> + Clone,
     B: Component<Kind = BlockComponent> + Clone,
     L: IntoIterRef + Clone,
     <L as IntoIterRef>::Item: Component<Kind = SectionComponent>,
+    Y: Layout + Clone,
 {
     fn clone(&self) -> Self {
         Self {
@@ -99,17 +205,20 @@ where
             assets: self.assets.clone(),
             body: self.body.clone(),
             children: self.children.clone(),
+            metadata: self.metadata.clone(),
+            layout: self.layout.clone(),
         }
     }
 }
 
-impl<T, A, B, L> PartialEq for Page<T, A, B, L>
+impl<T, A, B, L, Y> PartialEq for Page<T, A, B, L, Y>
 where
     T: Component<Kind = BlockComponent> + PartialEq,
     A: Component<Kind = AssetComponent> + PartialEq,
     B: Component<Kind = BlockComponent> + PartialEq,
     L: IntoIterRef,
     <L as IntoIterRef>::Item: Component<Kind = SectionComponent> + PartialEq,
+    Y: Layout + PartialEq,
 {
     fn eq(&self, other: &Self) -> bool {
         self.banner == other.banner
@@ -117,26 +226,30 @@ where
             && self.assets == other.assets
             && self.body == other.body
             && self.children.iter().eq(other.children.iter())
+            && self.metadata == other.metadata
+            && self.layout == other.layout
     }
 }
 
-impl<T, A, B, L> Eq for Page<T, A, B, L>
+impl<T, A, B, L, Y> Eq for Page<T, A, B, L, Y>
 where
     T: Component<Kind = BlockComponent> + Eq,
     A: Component<Kind = AssetComponent> + Eq,
     B: Component<Kind = BlockComponent> + Eq,
     L: IntoIterRef,
     <L as IntoIterRef>::Item: Component<Kind = SectionComponent> + Eq,
+    Y: Layout + Eq,
 {
 }
 
-impl<T, A, B, L> PartialOrd for Page<T, A, B, L>
+impl<T, A, B, L, Y> PartialOrd for Page<T, A, B, L, Y>
 where
     T: Component<Kind = BlockComponent> + PartialOrd,
     A: Component<Kind = AssetComponent> + PartialOrd,
     B: Component<Kind = BlockComponent> + PartialOrd,
     L: IntoIterRef,
     <L as IntoIterRef>::Item: Component<Kind = SectionComponent> + PartialOrd,
+    Y: Layout + PartialOrd,
 {
     fn partial_cmp(&self, other: &Self) -> Option<Ordering> {
         let ordering = self
@@ -145,18 +258,21 @@ where
             .then(self.title.partial_cmp(&other.title)?)
             .then(self.assets.partial_cmp(&other.assets)?)
             .then(self.body.partial_cmp(&other.body)?)
-            .then(self.children.iter().partial_cmp(other.children.iter())?);
+            .then(self.children.iter().partial_cmp(other.children.iter())?)
+            .then(self.metadata.partial_cmp(&other.metadata)?)
+            .then(self.layout.partial_cmp(&other.layout)?);
         Some(ordering)
     }
 }
 
-impl<T, A, B, L> Ord for Page<T, A, B, L>
+impl<T, A, B, L, Y> Ord for Page<T, A, B, L, Y>
 where
     T: Component<Kind = BlockComponent> + Ord,
     A: Component<Kind = AssetComponent> + Ord,
     B: Component<Kind = BlockComponent> + Ord,
     L: IntoIterRef,
     <L as IntoIterRef>::Item: Component<Kind = SectionComponent> + Ord,
+    Y: Layout + Ord,
 {
     fn cmp(&self, other: &Self) -> Ordering {
         self.banner
@@ -165,16 +281,19 @@ where
             .then_with(|| self.assets.cmp(&other.assets))
             .then_with(|| self.body.cmp(&other.body))
             .then_with(|| self.children.iter().cmp(other.children.iter()))
+            .then_with(|| self.metadata.cmp(&other.metadata))
+            .then_with(|| self.layout.cmp(&other.layout))
     }
 }
 
-impl<T, A, B, L> Hash for Page<T, A, B, L>
+impl<T, A, B, L, Y> Hash for Page<T, A, B, L, Y>
 where
     T: Component<Kind = BlockComponent> + Hash,
     A: Component<Kind = AssetComponent> + Hash,
     B: Component<Kind = BlockComponent> + Hash,
     L: IntoIterRef,
     <L as IntoIterRef>::Item: Component<Kind = SectionComponent> + Hash,
+    Y: Layout + Hash,
 {
     fn hash<H>(&self, state: &mut H)
     where
@@ -188,15 +307,19 @@ where
             i.hash(state);
             child.hash(state);
         }
+        self.metadata.hash(state);
+        self.layout.hash(state);
     }
 }
-impl<T, A, B, L> Default for Page<T, A, B, L>
+
+impl<T, A, B, L, Y> Default for Page<T, A, B, L, Y>
 where
     T: Component<Kind = BlockComponent> + Default,
     A: Component<Kind = AssetComponent> + Default,
     B: Component<Kind = BlockComponent> + Default,
     L: IntoIterRef + Default,
     <L as IntoIterRef>::Item: Component<Kind = SectionComponent>,
+    Y: Layout + Default,
 {
     fn default() -> Self {
         Self {
@@ -205,79 +328,143 @@ where
             assets: A::default(),
             body: B::default(),
             children: L::default(),
+            metadata: None,
+            layout: Y::default(),
         }
     }
 }
 
-impl<T, A, B, L> Component for Page<T, A, B, L>
+impl<T, A, B, L, Y> Component for Page<T, A, B, L, Y>
 where
     T: Component<Kind = BlockComponent>,
     A: Component<Kind = AssetComponent>,
     B: Component<Kind = BlockComponent>,
     L: IntoIterRef,
     <L as IntoIterRef>::Item: Component<Kind = SectionComponent>,
+    Y: Layout,
 {
     type Kind = PageComponent;
 }
 
-impl<T, A, B, L> Render<Html> for Page<T, A, B, L>
+impl<T, A, B, L, Y> Render<Html> for Page<T, A, B, L, Y>
 where
     T: Render<Html, Kind = BlockComponent>,
     A: Render<Html, Kind = AssetComponent>,
     B: Render<Html, Kind = BlockComponent>,
     L: IntoIterRef,
     <L as IntoIterRef>::Item: Render<Html, Kind = SectionComponent>,
+    Y: Layout,
 {
     fn render(
         &self,
         renderer: &mut Renderer<Html>,
         ctx: Context<Self::Kind>,
     ) -> fmt::Result {
-        renderer.write_str(
-            "<!DOCTYPE html><html><head><meta charset=\"utf-8\"><meta \
-             name=\"viewport\" content=\"width=device-width, \
-             initial-scale=1.0\">",
+        ctx.title(&self.title);
+        if let Some(metadata) = &self.metadata {
+            ctx.metadata(metadata);
+        }
+        let footnotes = Footnotes::new();
+        let ctx = ctx.with_footnotes(&footnotes);
+        let citations = Citations::default();
+        let ctx = match ctx.citations() {
+            Some(_) => ctx,
+            None => ctx.with_citations(&citations),
+        };
+
+        // Parts are rendered in the order of the builtin layout, so that
+        // footnotes and citations are numbered the same whatever the layout.
+        let mut assets = String::new();
+        let mut banner = String::new();
+        let mut title = String::new();
+        let mut body = String::new();
+        let mut children = String::new();
+        let mut footnotes = String::new();
+        let mut meta = String::new();
+        if let Some(metadata) = &self.metadata {
+            render_meta_tags(
+                metadata,
+                &self.title,
+                &mut Renderer::new(renderer.format_mut(), &mut meta),
+                ctx,
+            )?;
+        }
+        self.assets.render(
+            &mut Renderer::new(renderer.format_mut(), &mut assets),
+            ctx.with_kind(&AssetComponent),
         )?;
-        self.assets.render(renderer, ctx.with_kind(&AssetComponent))?;
-        renderer.write_str("<title>")?;
-        self.title.render(renderer, ctx.with_kind(&InlineComponent))?;
-        renderer.write_str(
-            "</title></head><body><div id=\"pedia-page-top\"><div \
-             id=\"pedia-banner\">",
+        self.title.render(
+            &mut Renderer::new(renderer.format_mut(), &mut title),
+            ctx.with_kind(&InlineComponent),
         )?;
-        self.banner.render(renderer, ctx.with_kind(&BlockComponent))?;
-        renderer.write_str(
-            "</div><h1 class=\"pedia-title\"><a class=\"pedia-title-link\" \
-             href=\"#pedia-page-root\">",
+        self.banner.render(
+            &mut Renderer::new(renderer.format_mut(), &mut banner),
+            ctx.with_kind(&BlockComponent),
         )?;
-        self.title.render(renderer, ctx.with_kind(&InlineComponent))?;
-        renderer.write_str(
-            "</a></h1><div id=\"pedia-page-body-wrapper\"><div \
-             id=\"pedia-page-body\">",
+        self.body.render(
+            &mut Renderer::new(renderer.format_mut(), &mut body),
+            ctx.with_kind(&BlockComponent),
         )?;
-        self.body.render(renderer, ctx.with_kind(&BlockComponent))?;
-        renderer.write_str("</div><div id=\"pedia-page-children\">")?;
-        for child in self.children.iter() {
-            child.render(renderer, ctx.with_kind(&SectionComponent))?;
+        {
+            let mut children_renderer =
+                Renderer::new(renderer.format_mut(), &mut children);
+            for child in self.children.iter() {
+                child.render(
+                    &mut children_renderer,
+                    ctx.with_kind(&SectionComponent),
+                )?;
+            }
         }
-        renderer.write_str("</div></div></div></body></html>")?;
-        Ok(())
+        FootnoteList.render(
+            &mut Renderer::new(renderer.format_mut(), &mut footnotes),
+            ctx.with_kind(&BlockComponent),
+        )?;
+
+        let slots = PageSlots {
+            lang: self
+                .metadata
+                .as_ref()
+                .and_then(|metadata| metadata.lang.as_deref()),
+            meta: &meta,
+            assets: &assets,
+            banner: &banner,
+            title: &title,
+            body: &body,
+            children: &children,
+            footnotes: &footnotes,
+        };
+        self.layout.render_page(renderer, slots, ctx)
     }
 }
 
-impl<T, A, B, L> Render<Markdown> for Page<T, A, B, L>
+impl<T, A, B, L, Y> Render<Markdown> for Page<T, A, B, L, Y>
 where
     T: Render<Markdown, Kind = BlockComponent>,
     A: Component<Kind = AssetComponent>,
     B: Render<Markdown, Kind = BlockComponent>,
     L: IntoIterRef,
     <L as IntoIterRef>::Item: Render<Markdown, Kind = SectionComponent>,
+    Y: Layout,
 {
     fn render(
         &self,
         renderer: &mut Renderer<Markdown>,
         ctx: Context<Self::Kind>,
     ) -> fmt::Result {
+        ctx.title(&self.title);
+        if let Some(metadata) = &self.metadata {
+            ctx.metadata(metadata);
+        }
+        let footnotes = Footnotes::new();
+        let ctx = ctx.with_footnotes(&footnotes);
+        let citations = Citations::default();
+        let ctx = match ctx.citations() {
+            Some(_) => ctx,
+            None => ctx.with_citations(&citations),
+        };
+        if let Some(metadata) = &self.metadata {
+            write_front_matter(metadata, &self.title, renderer, ctx)?;
+        }
         self.banner.render(renderer, ctx.with_kind(&BlockComponent))?;
         renderer.write_str("\n\n")?;
         renderer.write_str("# ")?;
@@ -287,23 +474,36 @@ where
         for child in self.children.iter() {
             child.render(renderer, ctx.with_kind(&SectionComponent))?;
         }
+        FootnoteList.render(renderer, ctx.with_kind(&BlockComponent))?;
         Ok(())
     }
 }
 
-impl<T, A, B, L> Render<Text> for Page<T, A, B, L>
+impl<T, A, B, L, Y> Render<Text> for Page<T, A, B, L, Y>
 where
     T: Render<Text, Kind = BlockComponent>,
     A: Component<Kind = AssetComponent>,
     B: Render<Text, Kind = BlockComponent>,
     L: IntoIterRef,
     <L as IntoIterRef>::Item: Render<Text, Kind = SectionComponent>,
+    Y: Layout,
 {
     fn render(
         &self,
         renderer: &mut Renderer<Text>,
         ctx: Context<Self::Kind>,
     ) -> fmt::Result {
+        ctx.title(&self.title);
+        if let Some(metadata) = &self.metadata {
+            ctx.metadata(metadata);
+        }
+        let footnotes = Footnotes::new();
+        let ctx = ctx.with_footnotes(&footnotes);
+        let citations = Citations::default();
+        let ctx = match ctx.citations() {
+            Some(_) => ctx,
+            None => ctx.with_citations(&citations),
+        };
         self.banner.render(renderer, ctx.with_kind(&BlockComponent))?;
         renderer.write_str("\n\n")?;
         self.title.render(renderer, ctx.with_kind(&InlineComponent))?;
@@ -312,40 +512,369 @@ where
         for child in self.children.iter() {
             child.render(renderer, ctx.with_kind(&SectionComponent))?;
         }
+        FootnoteList.render(renderer, ctx.with_kind(&BlockComponent))?;
+        Ok(())
+    }
+}
+
+impl<T, A, B, L, Y> Render<Latex> for Page<T, A, B, L, Y>
+where
+    T: Component<Kind = BlockComponent>,
+    A: Component<Kind = AssetComponent>,
+    B: Render<Latex, Kind = BlockComponent>,
+    L: IntoIterRef,
+    <L as IntoIterRef>::Item: Render<Latex, Kind = SectionComponent>,
+    Y: Layout,
+{
+    fn render(
+        &self,
+        renderer: &mut Renderer<Latex>,
+        ctx: Context<Self::Kind>,
+    ) -> fmt::Result {
+        ctx.title(&self.title);
+        if let Some(metadata) = &self.metadata {
+            ctx.metadata(metadata);
+        }
+        let citations = Citations::default();
+        let ctx = match ctx.citations() {
+            Some(_) => ctx,
+            None => ctx.with_citations(&citations),
+        };
+        let label = latex::label(ctx.location(), None);
+        let style = renderer.format().page_style();
+        match style {
+            PageStyle::Document => {
+                renderer.write_str(
+                    "\\documentclass{article}\n\\usepackage[T1]{fontenc}\n\
+                     \\usepackage[utf8]{inputenc}\n",
+                )?;
+                for package in latex::PACKAGES {
+                    writeln!(renderer, "\\usepackage{{{}}}", package)?;
+                }
+                renderer.write_str("\\title{")?;
+                self.title.render(renderer, ctx.with_kind(&InlineComponent))?;
+                renderer.write_str("}\n\\author{")?;
+                let metadata = self.metadata.as_ref();
+                let authors = metadata.map_or(&[][..], |meta| &meta.authors);
+                for (i, author) in authors.iter().enumerate() {
+                    if i > 0 {
+                        renderer.write_str(" \\and ")?;
+                    }
+                    author.render(renderer, ctx.with_kind(&InlineComponent))?;
+                }
+                renderer.write_str("}\n\\date{")?;
+                let date = metadata.and_then(|meta| {
+                    meta.updated.as_ref().or(meta.created.as_ref())
+                });
+                if let Some(date) = date {
+                    date.render(renderer, ctx.with_kind(&InlineComponent))?;
+                }
+                write!(
+                    renderer,
+                    "}}\n\\begin{{document}}\n\\maketitle\\label{{{}}}\n\n",
+                    label
+                )?;
+            },
+            PageStyle::Chapter => {
+                renderer.write_str("\\chapter{")?;
+                self.title.render(renderer, ctx.with_kind(&InlineComponent))?;
+                write!(renderer, "}}\\label{{{}}}\n\n", label)?;
+            },
+        }
+        self.body.render(renderer, ctx.with_kind(&BlockComponent))?;
+        for child in self.children.iter() {
+            child.render(renderer, ctx.with_kind(&SectionComponent))?;
+        }
+        if style == PageStyle::Document {
+            renderer.write_str("\\end{document}\n")?;
+        }
+        Ok(())
+    }
+}
+
+impl<T, A, B, L, Y> Render<Gemtext> for Page<T, A, B, L, Y>
+where
+    T: Render<Gemtext, Kind = BlockComponent>,
+    A: Component<Kind = AssetComponent>,
+    B: Render<Gemtext, Kind = BlockComponent>,
+    L: IntoIterRef,
+    <L as IntoIterRef>::Item: Render<Gemtext, Kind = SectionComponent>,
+    Y: Layout,
+{
+    fn render(
+        &self,
+        renderer: &mut Renderer<Gemtext>,
+        ctx: Context<Self::Kind>,
+    ) -> fmt::Result {
+        ctx.title(&self.title);
+        if let Some(metadata) = &self.metadata {
+            ctx.metadata(metadata);
+        }
+        let footnotes = Footnotes::new();
+        let ctx = ctx.with_footnotes(&footnotes);
+        let citations = Citations::default();
+        let ctx = match ctx.citations() {
+            Some(_) => ctx,
+            None => ctx.with_citations(&citations),
+        };
+        self.banner.render(renderer, ctx.with_kind(&BlockComponent))?;
+        renderer.write_str("\n\n")?;
+        renderer.write_str("# ")?;
+        renderer.scoped(gemtext::Line, |renderer| {
+            self.title.render(renderer, ctx.with_kind(&InlineComponent))
+        })?;
+        renderer.write_str("\n\n")?;
+        self.body.render(renderer, ctx.with_kind(&BlockComponent))?;
+        for child in self.children.iter() {
+            child.render(renderer, ctx.with_kind(&SectionComponent))?;
+        }
+        FootnoteList.render(renderer, ctx.with_kind(&BlockComponent))?;
         Ok(())
     }
 }
 
+impl<T, A, B, L, Y> Visit for Page<T, A, B, L, Y>
+where
+    T: Visit<Kind = BlockComponent>,
+    A: Visit<Kind = AssetComponent>,
+    B: Visit<Kind = BlockComponent>,
+    L: IntoIterRef,
+    <L as IntoIterRef>::Item: Visit<Kind = SectionComponent>,
+    Y: Layout,
+{
+    fn visit<V>(&self, visitor: &mut V, ctx: Context<Self::Kind>)
+    where
+        V: Visitor + ?Sized,
+    {
+        visit_node(visitor, Node::Page(&self.title), ctx, |visitor| {
+            self.banner.visit(visitor, ctx.with_kind(&BlockComponent));
+            self.assets.visit(visitor, ctx.with_kind(&AssetComponent));
+            self.body.visit(visitor, ctx.with_kind(&BlockComponent));
+            for child in self.children.iter() {
+                child.visit(visitor, ctx.with_kind(&SectionComponent));
+            }
+            if let Some(metadata) = &self.metadata {
+                visit_node(visitor, Node::PageMetadata(metadata), ctx, |_| ());
+            }
+        });
+    }
+}
+
+/// Renders the `<meta>` tags, the canonical link and the OpenGraph tags of a
+/// page with the given metadata.
+fn render_meta_tags(
+    metadata: &PageMetadata,
+    title: &str,
+    renderer: &mut Renderer<Html>,
+    ctx: Context<PageComponent>,
+) -> fmt::Result {
+    let ctx = ctx.with_kind(&InlineComponent);
+    let mut meta = |attr: &str, name: &str, content: &str| {
+        write!(renderer, "<meta {}=\"{}\" content=\"", attr, name)?;
+        content.render(renderer, ctx)?;
+        renderer.write_str("\">")
+    };
+
+    if let Some(description) = &metadata.description {
+        meta("name", "description", description)?;
+    }
+    for author in &metadata.authors {
+        meta("name", "author", author)?;
+    }
+    if !metadata.keywords.is_empty() {
+        meta("name", "keywords", &metadata.keywords.join(", "))?;
+    }
+
+    meta("property", "og:type", "article")?;
+    meta("property", "og:title", title)?;
+    if let Some(description) = &metadata.description {
+        meta("property", "og:description", description)?;
+    }
+    if let Some(Location::Url(url)) = &metadata.canonical {
+        meta("property", "og:url", url.as_str())?;
+    }
+    if let Some(lang) = &metadata.lang {
+        meta("property", "og:locale", &lang.replace('-', "_"))?;
+    }
+    if let Some(created) = &metadata.created {
+        meta("property", "article:published_time", created)?;
+    }
+    if let Some(updated) = &metadata.updated {
+        meta("property", "article:modified_time", updated)?;
+    }
+    for author in &metadata.authors {
+        meta("property", "article:author", author)?;
+    }
+    for keyword in &metadata.keywords {
+        meta("property", "article:tag", keyword)?;
+    }
+
+    if let Some(canonical) = &metadata.canonical {
+        renderer.write_str("<link rel=\"canonical\" href=\"")?;
+        canonical.render(renderer, ctx)?;
+        renderer.write_str("\">")?;
+    }
+    Ok(())
+}
+
+/// Writes the YAML front matter of a page with the given metadata.
+fn write_front_matter(
+    metadata: &PageMetadata,
+    title: &str,
+    renderer: &mut Renderer<Markdown>,
+    ctx: Context<PageComponent>,
+) -> fmt::Result {
+    let fields = [
+        ("title", Some(title)),
+        ("description", metadata.description.as_deref()),
+        ("created", metadata.created.as_deref()),
+        ("updated", metadata.updated.as_deref()),
+        ("lang", metadata.lang.as_deref()),
+    ];
+    let lists =
+        [("authors", &metadata.authors), ("keywords", &metadata.keywords)];
+
+    renderer.write_str("---\n")?;
+    for (key, value) in fields {
+        if let Some(value) = value {
+            writeln!(renderer, "{}: {}", key, YamlString(value))?;
+        }
+    }
+    for (key, values) in lists {
+        if !values.is_empty() {
+            writeln!(renderer, "{}:", key)?;
+            for value in values {
+                writeln!(renderer, "  - {}", YamlString(value))?;
+            }
+        }
+    }
+    if let Some(canonical) = &metadata.canonical {
+        let mut format = Markdown::default();
+        let mut location = String::new();
+        canonical.render(
+            &mut Renderer::new(&mut format, &mut location),
+            ctx.with_kind(&InlineComponent),
+        )?;
+        writeln!(renderer, "canonical: {}", YamlString(&location))?;
+    }
+    renderer.write_str("---\n\n")?;
+    Ok(())
+}
+
+/// Displays a string as a double-quoted YAML scalar.
+#[derive(Debug, Clone, Copy)]
+struct YamlString<'str>(&'str str);
+
+impl fmt::Display for YamlString<'_> {
+    fn fmt(&self, fmtr: &mut fmt::Formatter) -> fmt::Result {
+        fmtr.write_char('"')?;
+        for ch in self.0.chars() {
+            match ch {
+                '"' => fmtr.write_str("\\\"")?,
+                '\\' => fmtr.write_str("\\\\")?,
+                '\n' => fmtr.write_str("\\n")?,
+                '\t' => fmtr.write_str("\\t")?,
+                ch if ch.is_control() => {
+                    write!(fmtr, "\\u{:04x}", u32::from(ch))?
+                },
+                ch => fmtr.write_char(ch)?,
+            }
+        }
+        fmtr.write_char('"')
+    }
+}
+
 #[cfg(test)]
 mod test {
-    use super::{Page, PageComponent};
+    use super::{layout::DefaultLayout, Page, PageComponent, PageMetadata};
     use crate::{
         component::{
             asset::{Script, Stylesheet},
-            block::{text::Paragraph, InlineBlock},
+            block::{
+                list::UnorderedList,
+                table::{Cell, Row, Table},
+                text::Paragraph,
+                InlineBlock,
+            },
+            inline::text::Link,
             section::Section,
         },
         harray,
         location::{Id, InternalPath, Location},
         render::{
             html::test::validate_html_document,
+            latex::PageStyle,
             Context,
+            Gemtext,
             Html,
+            Latex,
+            Markdown,
             RenderAsDisplay,
         },
     };
+    use url::Url;
+
+    fn metadata() -> PageMetadata {
+        PageMetadata {
+            description: Some(String::from("All about \"Foo\".")),
+            authors: vec![String::from("Ana"), String::from("Bo")],
+            created: Some(String::from("2024-01-31")),
+            updated: None,
+            lang: Some(String::from("pt-BR")),
+            keywords: vec![String::from("foo"), String::from("bar")],
+            canonical: Some(Location::Url(
+                Url::parse("https://example.com/foo").unwrap(),
+            )),
+        }
+    }
 
     #[test]
-    fn page_without_banner_is_valid_html() {
-        let rendered = RenderAsDisplay::new(
+    fn new_page_has_defaults() {
+        let page = Page::new(
+            InlineBlock("My Encyclopedia"),
+            String::from("Hello"),
+            harray![],
+            Paragraph("World!"),
+            harray![],
+        );
+        assert_eq!(
+            page,
             Page {
-                banner: harray![],
+                banner: InlineBlock("My Encyclopedia"),
                 title: String::from("Hello"),
                 assets: harray![],
                 body: Paragraph("World!"),
                 children: harray![],
-            },
-            &mut Html::default(),
+                metadata: None,
+                layout: DefaultLayout,
+            }
+        );
+    }
+
+    #[test]
+    fn page_with_metadata_from_builder() {
+        let page = Page::new(
+            harray![],
+            String::from("Foo"),
+            harray![],
+            Paragraph("Bar"),
+            harray![],
+        )
+        .with_metadata(metadata());
+        assert_eq!(page.metadata, Some(metadata()));
+    }
+
+    #[test]
+    fn page_without_banner_is_valid_html() {
+        let rendered = RenderAsDisplay::new(
+            Page::new(
+                harray![],
+                String::from("Hello"),
+                harray![],
+                Paragraph("World!"),
+                harray![],
+            ),
+            &mut Html,
             Context::new(&InternalPath::default(), &PageComponent),
         )
         .to_string();
@@ -356,14 +885,14 @@ mod test {
     #[test]
     fn page_without_assets_is_valid_html() {
         let rendered = RenderAsDisplay::new(
-            Page {
-                banner: InlineBlock("My Encyclopedia"),
-                title: String::from("Hello"),
-                assets: harray![],
-                body: Paragraph("World!"),
-                children: harray![],
-            },
-            &mut Html::default(),
+            Page::new(
+                InlineBlock("My Encyclopedia"),
+                String::from("Hello"),
+                harray![],
+                Paragraph("World!"),
+                harray![],
+            ),
+            &mut Html,
             Context::new(&InternalPath::default(), &PageComponent),
         )
         .to_string();
@@ -374,19 +903,19 @@ mod test {
     #[test]
     fn page_with_assets_is_valid_html() {
         let rendered = RenderAsDisplay::new(
-            Page {
-                banner: InlineBlock("My Encyclopedia"),
-                title: String::from("Hello"),
-                assets: harray![
+            Page::new(
+                InlineBlock("My Encyclopedia"),
+                String::from("Hello"),
+                harray![
                     Stylesheet {
                         location: Location::internal("styles/main.css"),
                     },
                     Script { location: Location::internal("js/main.js") }
                 ],
-                body: Paragraph("World!"),
-                children: harray![],
-            },
-            &mut Html::default(),
+                Paragraph("World!"),
+                harray![],
+            ),
+            &mut Html,
             Context::new(&InternalPath::default(), &PageComponent),
         )
         .to_string();
@@ -397,14 +926,14 @@ mod test {
     #[test]
     fn page_with_children_is_valid_html() {
         let rendered = RenderAsDisplay::new(
-            Page {
-                banner: InlineBlock("My Encyclopedia"),
-                title: String::from("Hello"),
-                assets: harray![Stylesheet {
+            Page::new(
+                InlineBlock("My Encyclopedia"),
+                String::from("Hello"),
+                harray![Stylesheet {
                     location: Location::internal("styles/main.css"),
                 }],
-                body: Paragraph("World, aaaa!"),
-                children: harray![
+                Paragraph("World, aaaa!"),
+                harray![
                     Section {
                         title: "Hey",
                         id: None,
@@ -429,12 +958,192 @@ mod test {
                         children: harray![],
                     },
                 ],
-            },
-            &mut Html::default(),
+            ),
+            &mut Html,
             Context::new(&InternalPath::default(), &PageComponent),
         )
         .to_string();
 
         validate_html_document(&rendered).unwrap();
     }
+
+    #[test]
+    fn page_as_latex_chapter() {
+        let rendered = RenderAsDisplay::new(
+            Page::new(
+                InlineBlock("My Encyclopedia"),
+                String::from("Foo & Bar"),
+                harray![],
+                Paragraph("Hello"),
+                harray![Section {
+                    title: "History",
+                    id: Some(Id::new("history")),
+                    body: Paragraph("Old"),
+                    children: harray![Section {
+                        title: "Origins",
+                        id: None,
+                        body: Paragraph("Older"),
+                        children: harray![],
+                    }],
+                }],
+            ),
+            &mut Latex::new(PageStyle::Chapter),
+            Context::new(
+                &InternalPath::parse("foo/index.html"),
+                &PageComponent,
+            ),
+        )
+        .to_string();
+
+        assert_eq!(
+            rendered,
+            "\\chapter{Foo \\& Bar}\\label{pedia:foo}\n\nHello\n\n\
+             \\section{History}\\label{pedia:foo:history}\n\nOld\n\n\
+             \\subsection{Origins}\n\nOlder\n\n"
+        );
+    }
+
+    #[test]
+    fn page_as_latex_document() {
+        let rendered = RenderAsDisplay::new(
+            Page::new(
+                harray![],
+                String::from("Hello"),
+                harray![],
+                Paragraph("World!"),
+                harray![],
+            ),
+            &mut Latex::default(),
+            Context::new(&InternalPath::default(), &PageComponent),
+        )
+        .to_string();
+
+        assert!(rendered.starts_with("\\documentclass{article}\n"));
+        assert!(rendered.contains("\\usepackage{multirow}\n"));
+        assert!(rendered.contains("\\title{Hello}"));
+        assert!(rendered.ends_with("World!\n\n\\end{document}\n"));
+    }
+
+    #[test]
+    fn page_as_gemtext() {
+        let rendered = RenderAsDisplay::new(
+            Page::new(
+                InlineBlock("My Encyclopedia"),
+                String::from("Foo"),
+                harray![],
+                Paragraph(harray![
+                    "See ",
+                    Link {
+                        location: Location::internal("bar/baz.html#qux"),
+                        target: "Baz",
+                    },
+                    " for\nmore.",
+                ]),
+                harray![Section {
+                    title: "History",
+                    id: None,
+                    body: UnorderedList(harray![
+                        InlineBlock("Old"),
+                        InlineBlock("Older"),
+                    ]),
+                    children: harray![Section {
+                        title: "Origins",
+                        id: None,
+                        body: Table(harray![Row(harray![
+                            Cell::from(InlineBlock("A")),
+                            Cell::from(InlineBlock("B")),
+                        ])]),
+                        children: harray![Section {
+                            title: "Details",
+                            id: None,
+                            body: harray![],
+                            children: harray![],
+                        }],
+                    }],
+                }],
+            ),
+            &mut Gemtext::default(),
+            Context::new(
+                &InternalPath::parse("foo/index.html"),
+                &PageComponent,
+            ),
+        )
+        .to_string();
+
+        assert_eq!(
+            rendered,
+            "My Encyclopedia\n\n# Foo\n\nSee Baz for more.\n\
+             => ../bar/baz.html Baz\n\n## History\n\n* Old\n* Older\n\n\
+             ### Origins\n\n```\nA | B\n```\n\n### Details\n\n"
+        );
+    }
+
+    #[test]
+    fn html_metadata() {
+        let rendered = RenderAsDisplay::new(
+            Page {
+                metadata: Some(metadata()),
+                ..Page::new(
+                    harray![],
+                    String::from("Foo"),
+                    harray![],
+                    harray![],
+                    harray![],
+                )
+            },
+            &mut Html,
+            Context::new(&InternalPath::parse("foo"), &PageComponent),
+        )
+        .to_string();
+
+        validate_html_document(&rendered).unwrap();
+        assert!(rendered.starts_with(
+            "<!DOCTYPE html><html lang=\"pt-BR\"><head><meta \
+             charset=\"utf-8\"><meta name=\"viewport\" \
+             content=\"width=device-width, initial-scale=1.0\"><meta \
+             name=\"description\" content=\"All about &quot;Foo&quot;.\">\
+             <meta name=\"author\" content=\"Ana\"><meta name=\"author\" \
+             content=\"Bo\"><meta name=\"keywords\" content=\"foo, bar\">\
+             <meta property=\"og:type\" content=\"article\"><meta \
+             property=\"og:title\" content=\"Foo\"><meta \
+             property=\"og:description\" content=\"All about \
+             &quot;Foo&quot;.\"><meta property=\"og:url\" \
+             content=\"https:&#47;&#47;example.com&#47;foo\"><meta \
+             property=\"og:locale\" content=\"pt_BR\"><meta \
+             property=\"article:published_time\" content=\"2024-01-31\">\
+             <meta property=\"article:author\" content=\"Ana\"><meta \
+             property=\"article:author\" content=\"Bo\"><meta \
+             property=\"article:tag\" content=\"foo\"><meta \
+             property=\"article:tag\" content=\"bar\"><link \
+             rel=\"canonical\" href=\"https://example.com/foo\"><title>"
+        ));
+    }
+
+    #[test]
+    fn markdown_front_matter() {
+        let rendered = RenderAsDisplay::new(
+            Page {
+                metadata: Some(metadata()),
+                ..Page::new(
+                    InlineBlock("My Encyclopedia"),
+                    String::from("Foo"),
+                    harray![],
+                    Paragraph("Hello"),
+                    harray![],
+                )
+            },
+            &mut Markdown::default(),
+            Context::new(&InternalPath::parse("foo"), &PageComponent),
+        )
+        .to_string();
+
+        assert_eq!(
+            rendered,
+            "---\ntitle: \"Foo\"\ndescription: \"All about \\\"Foo\\\".\"\n\
+             created: \"2024-01-31\"\nlang: \"pt-BR\"\nauthors:\n  - \"Ana\"\n\
+             \u{20} - \"Bo\"\nkeywords:\n  - \"foo\"\n  - \"bar\"\n\
+             canonical: \"https://example.com/foo\"\n---\n\nMy Encyclopedia\n\n\
+             # Foo\n\nHello\n"
+        );
+    }
 }
@@ -0,0 +1,187 @@
+//! This module exports the layout of pages in HTML, i.e. the document
+//! skeleton in which the parts of a page are placed.
+
+use super::PageComponent;
+use crate::{
+    component::InlineComponent,
+    render::{Context, Html, Render, Renderer},
+};
+use std::fmt::{self, Write};
+
+/// The parts of a page, already rendered in HTML, given to its layout.
+#[derive(Debug, Clone, Copy, PartialEq, Eq, PartialOrd, Ord, Hash, Default)]
+pub struct PageSlots<'slots> {
+    /// Language tag of the page, if known, e.g. for the `lang` attribute of
+    /// the `<html>` element.
+    pub lang: Option<&'slots str>,
+    /// The `<meta>` and `<link>` tags describing the page, to be placed in
+    /// the `<head>` element.
+    pub meta: &'slots str,
+    /// The assets of the page, to be placed in the `<head>` element.
+    pub assets: &'slots str,
+    /// The banner of the page.
+    pub banner: &'slots str,
+    /// The title of the page, escaped, without any markup.
+    pub title: &'slots str,
+    /// The body of the page.
+    pub body: &'slots str,
+    /// The child sections of the page.
+    pub children: &'slots str,
+    /// The footnotes of the page not yet listed elsewhere, if any.
+    pub footnotes: &'slots str,
+}
+
+/// A layout of pages in HTML: it writes the whole HTML document of a page,
+/// placing the rendered parts of the page. Custom layouts can add e.g.
+/// navigation, sidebars or footers around them.
+pub trait Layout: fmt::Debug {
+    /// Writes the document of a page given its rendered parts.
+    fn render_page(
+        &self,
+        renderer: &mut Renderer<Html>,
+        slots: PageSlots,
+        ctx: Context<PageComponent>,
+    ) -> fmt::Result;
+}
+
+impl<L> Layout for &L
+where
+    L: Layout + ?Sized,
+{
+    fn render_page(
+        &self,
+        renderer: &mut Renderer<Html>,
+        slots: PageSlots,
+        ctx: Context<PageComponent>,
+    ) -> fmt::Result {
+        (**self).render_page(renderer, slots, ctx)
+    }
+}
+
+/// The builtin layout of pages, the default one.
+///
+/// # HTML IDs
+///
+/// - `pedia-page-top` in a `<div>` element wrapping the whole page.
+/// - `pedia-banner` in a `<div>` element wrapping the banner.
+/// - `pedia-page-body-wrapper` in a `<div>` element, surrounding the ID
+///   `pedia-page-body`.
+/// - `pedia-page-body` in a `<div>` element.
+/// - `pedia-page-children` in a `<div>` element, inside
+///   `pedia-page-body-wrapper`, but not `pedia-page-body`.
+///
+/// # HTML Classes
+///
+/// - `pedia-title` in a `<h1>` element, surrounding a `pedia-title-link` in an
+///   `<a>` element (whose link is `#pedia-page-root`).
+#[derive(Debug, Clone, Copy, PartialEq, Eq, PartialOrd, Ord, Hash, Default)]
+pub struct DefaultLayout;
+
+impl Layout for DefaultLayout {
+    fn render_page(
+        &self,
+        renderer: &mut Renderer<Html>,
+        slots: PageSlots,
+        ctx: Context<PageComponent>,
+    ) -> fmt::Result {
+        renderer.write_str("<!DOCTYPE html><html")?;
+        if let Some(lang) = slots.lang {
+            renderer.write_str(" lang=\"")?;
+            lang.render(renderer, ctx.with_kind(&InlineComponent))?;
+            renderer.write_str("\"")?;
+        }
+        renderer.write_str(
+            "><head><meta charset=\"utf-8\"><meta name=\"viewport\" \
+             content=\"width=device-width, initial-scale=1.0\">",
+        )?;
+        renderer.write_str(slots.meta)?;
+        renderer.write_str(slots.assets)?;
+        write!(
+            renderer,
+            "<title>{}</title></head><body><div id=\"pedia-page-top\"><div \
+             id=\"pedia-banner\">{}</div><h1 class=\"pedia-title\"><a \
+             class=\"pedia-title-link\" href=\"#pedia-page-root\">{}</a></h1>\
+             <div id=\"pedia-page-body-wrapper\"><div \
+             id=\"pedia-page-body\">{}</div><div \
+             id=\"pedia-page-children\">{}</div>{}</div></div></body></html>",
+            slots.title,
+            slots.banner,
+            slots.title,
+            slots.body,
+            slots.children,
+            slots.footnotes,
+        )
+    }
+}
+
+#[cfg(test)]
+mod test {
+    use super::{Layout, PageSlots};
+    use crate::{
+        component::{
+            block::InlineBlock,
+            page::{Page, PageComponent},
+        },
+        harray,
+        location::InternalPath,
+        render::{
+            html::test::validate_html_document,
+            Context,
+            Html,
+            RenderAsDisplay,
+            Renderer,
+        },
+    };
+    use std::fmt::{self, Write};
+
+    #[derive(Debug)]
+    struct FooterLayout;
+
+    impl Layout for FooterLayout {
+        fn render_page(
+            &self,
+            renderer: &mut Renderer<Html>,
+            slots: PageSlots,
+            _ctx: Context<PageComponent>,
+        ) -> fmt::Result {
+            write!(
+                renderer,
+                "<!DOCTYPE html><html><head><title>{}</title>{}</head><body>\
+                 <main>{}{}{}</main><footer>{}</footer></body></html>",
+                slots.title,
+                slots.assets,
+                slots.body,
+                slots.children,
+                slots.footnotes,
+                slots.banner,
+            )
+        }
+    }
+
+    #[test]
+    fn custom_layout() {
+        let rendered = RenderAsDisplay::new(
+            Page {
+                banner: InlineBlock("Banner"),
+                title: String::from("A & B"),
+                assets: harray![],
+                body: InlineBlock("Body"),
+                children: harray![],
+                metadata: None,
+                layout: FooterLayout,
+            },
+            &mut Html,
+            Context::new(&InternalPath::default(), &PageComponent),
+        )
+        .to_string();
+
+        validate_html_document(&rendered).unwrap();
+        assert_eq!(
+            rendered,
+            "<!DOCTYPE html><html><head><title>A &amp; B</title></head><body>\
+             <main><span class=\"pedia-inline-block\">Body</span></main>\
+             <footer><span class=\"pedia-inline-block\">Banner</span>\
+             </footer></body></html>"
+        );
+    }
+}
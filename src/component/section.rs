@@ -4,7 +4,20 @@ use super::{BlockComponent, Component, ComponentKind, InlineComponent};
 use crate::{
     hseq::IntoIterRef,
     location::{Id, InternalLoc, Location},
-    render::{Context, Html, Markdown, Render, Renderer, Text},
+    render::{
+        gemtext,
+        latex,
+        Context,
+        Format,
+        Gemtext,
+        Html,
+        Latex,
+        Markdown,
+        Render,
+        Renderer,
+        Text,
+    },
+    visit::{visit_node, Node, Visit, Visitor},
 };
 use std::{
     cmp::Ordering,
@@ -213,6 +226,7 @@ where
             ctx.section_level()
         )?;
         if let Some(id) = &self.id {
+            ctx.define(id);
             renderer.write_str(" id=\"")?;
             id.render(renderer, ctx.with_kind(&InlineComponent))?;
             renderer.write_str("\"")?;
@@ -227,7 +241,7 @@ where
             location.render(renderer, ctx.with_kind(&InlineComponent))?;
             renderer.write_str("\">")?;
         }
-        self.title.render(renderer, ctx.with_kind(&InlineComponent))?;
+        render_title(&self.title, self.id.as_ref(), renderer, ctx)?;
         if self.id.is_some() {
             renderer.write_str("</a>")?;
         }
@@ -271,12 +285,13 @@ where
         };
         write!(renderer, "{} ", tag)?;
         if let Some(id) = &self.id {
+            ctx.define(id);
             renderer.write_str("<span id=\"")?;
             id.render(renderer, ctx.with_kind(&InlineComponent))?;
             renderer.write_str("\">[")?;
         }
 
-        self.title.render(renderer, ctx.with_kind(&InlineComponent))?;
+        render_title(&self.title, self.id.as_ref(), renderer, ctx)?;
 
         if let Some(id) = &self.id {
             let location = Location::Internal(InternalLoc {
@@ -311,7 +326,10 @@ where
         renderer: &mut Renderer<Text>,
         ctx: Context<Self::Kind>,
     ) -> fmt::Result {
-        self.title.render(renderer, ctx.with_kind(&InlineComponent))?;
+        if let Some(id) = &self.id {
+            ctx.define(id);
+        }
+        render_title(&self.title, self.id.as_ref(), renderer, ctx)?;
         renderer.write_str("\n\n")?;
         self.body.render(renderer, ctx.with_kind(&BlockComponent))?;
         for child in self.children.iter() {
@@ -324,6 +342,135 @@ where
     }
 }
 
+impl<T, B, L> Render<Latex> for Section<T, B, L>
+where
+    T: Render<Latex, Kind = InlineComponent>,
+    B: Render<Latex, Kind = BlockComponent>,
+    L: IntoIterRef,
+    <L as IntoIterRef>::Item: Render<Latex, Kind = SectionComponent> + Hash,
+{
+    fn render(
+        &self,
+        renderer: &mut Renderer<Latex>,
+        ctx: Context<Self::Kind>,
+    ) -> fmt::Result {
+        let command = match ctx.section_level() {
+            0 => "section",
+            1 => "subsection",
+            2 => "subsubsection",
+            3 => "paragraph",
+            _ => "subparagraph",
+        };
+        if let Some(id) = &self.id {
+            ctx.define(id);
+        }
+        write!(renderer, "\\{}{{", command)?;
+        render_title(&self.title, self.id.as_ref(), renderer, ctx)?;
+        renderer.write_str("}")?;
+        if let Some(id) = &self.id {
+            let label = latex::label(ctx.location(), Some(id));
+            write!(renderer, "\\label{{{}}}", label)?;
+        }
+        renderer.write_str("\n\n")?;
+        self.body.render(renderer, ctx.with_kind(&BlockComponent))?;
+        for child in self.children.iter() {
+            child.render(
+                renderer,
+                ctx.enter_section().with_kind(&SectionComponent),
+            )?;
+        }
+        Ok(())
+    }
+}
+
+impl<T, B, L> Render<Gemtext> for Section<T, B, L>
+where
+    T: Render<Gemtext, Kind = InlineComponent>,
+    B: Render<Gemtext, Kind = BlockComponent>,
+    L: IntoIterRef,
+    <L as IntoIterRef>::Item: Render<Gemtext, Kind = SectionComponent> + Hash,
+{
+    fn render(
+        &self,
+        renderer: &mut Renderer<Gemtext>,
+        ctx: Context<Self::Kind>,
+    ) -> fmt::Result {
+        // The page title takes the first of the three heading levels.
+        let tag = match ctx.section_level() {
+            0 => "##",
+            _ => "###",
+        };
+        if let Some(id) = &self.id {
+            ctx.define(id);
+        }
+        write!(renderer, "{} ", tag)?;
+        renderer.scoped(gemtext::Line, |renderer| {
+            render_title(&self.title, self.id.as_ref(), renderer, ctx)
+        })?;
+        renderer.write_str("\n\n")?;
+        self.body.render(renderer, ctx.with_kind(&BlockComponent))?;
+        for child in self.children.iter() {
+            child.render(
+                renderer,
+                ctx.enter_section().with_kind(&SectionComponent),
+            )?;
+        }
+        Ok(())
+    }
+}
+
+impl<T, B, L> Visit for Section<T, B, L>
+where
+    T: Visit<Kind = InlineComponent>,
+    B: Visit<Kind = BlockComponent>,
+    L: IntoIterRef,
+    <L as IntoIterRef>::Item: Visit<Kind = SectionComponent> + Hash,
+{
+    fn visit<V>(&self, visitor: &mut V, ctx: Context<Self::Kind>)
+    where
+        V: Visitor + ?Sized,
+    {
+        visit_node(visitor, Node::Section(self.id.as_ref()), ctx, |visitor| {
+            let title_ctx = ctx.with_kind(&InlineComponent);
+            visit_node(visitor, Node::Title, title_ctx, |visitor| {
+                self.title.visit(visitor, title_ctx)
+            });
+            self.body.visit(visitor, ctx.with_kind(&BlockComponent));
+            for child in self.children.iter() {
+                child.visit(
+                    visitor,
+                    ctx.enter_section().with_kind(&SectionComponent),
+                );
+            }
+        });
+    }
+}
+
+/// Renders the title of a section. If the section has an ID, the observer
+/// (if any) is notified of the section along with its title rendered as a
+/// label, since the title may be placed inside links to the section.
+fn render_title<T, W>(
+    title: &T,
+    id: Option<&Id>,
+    renderer: &mut Renderer<W>,
+    ctx: Context<SectionComponent>,
+) -> fmt::Result
+where
+    W: Format + ?Sized,
+    T: Render<W, Kind = InlineComponent> + ?Sized,
+{
+    let title_ctx = ctx.with_kind(&InlineComponent);
+    title.render(renderer, title_ctx)?;
+    if let (Some(id), Some(_)) = (id, ctx.observer()) {
+        let mut label = String::new();
+        let mut label_renderer =
+            Renderer::new(renderer.format_mut(), &mut label);
+        title.render(&mut label_renderer, title_ctx.as_label())?;
+        ctx.section(id, &label);
+    }
+    Ok(())
+}
+
 #[cfg(test)]
 mod test {
     use super::{Section, SectionComponent};
@@ -348,7 +495,7 @@ mod test {
                 body: Paragraph("World!"),
                 children: harray![],
             },
-            &mut Html::default(),
+            &mut Html,
             Context::new(&InternalPath::default(), &SectionComponent),
         )
         .to_string();
@@ -365,7 +512,7 @@ mod test {
                 body: Paragraph("World!"),
                 children: harray![],
             },
-            &mut Html::default(),
+            &mut Html,
             Context::new(&InternalPath::default(), &SectionComponent),
         )
         .to_string();
@@ -406,7 +553,7 @@ mod test {
                     },
                 ],
             },
-            &mut Html::default(),
+            &mut Html,
             Context::new(&InternalPath::default(), &SectionComponent),
         )
         .to_string();
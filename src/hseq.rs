@@ -293,7 +293,7 @@ mod test {
     const F64_META_LIST: HArray![(bool, &str, i32): f64] =
         harray![false, "a", 2];
 
-    #[allow(dead_code)]
+    #[allow(dead_code, clippy::type_complexity)]
     const MANUAL_F64_META_LIST: [Coproduct![(bool, &str, i32): f64]; 3] =
         harray![false, "a", 2];
 }
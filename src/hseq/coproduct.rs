@@ -58,7 +58,7 @@ where
     M: ?Sized,
 {
     fn clone(&self) -> Self {
-        self.coerce()
+        *self
     }
 }
 
@@ -79,8 +79,8 @@ impl<M> PartialOrd for Conil<M>
 where
     M: ?Sized,
 {
-    fn partial_cmp(&self, _other: &Self) -> Option<Ordering> {
-        self.coerce()
+    fn partial_cmp(&self, other: &Self) -> Option<Ordering> {
+        Some(self.cmp(other))
     }
 }
 
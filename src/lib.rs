@@ -31,17 +31,26 @@
 //! the rendering will produce. Builtin rendering formats are:
 //! - [HTML](render::Html);
 //! - [Markdown](render::Markdown);
-//! - [Plaintext](render::Text).
+//! - [Plaintext](render::Text);
+//! - [LaTeX](render::Latex), which is not required by
+//!   [`FullRender`](render::FullRender) but by
+//!   [`FullRenderLatex`](render::FullRenderLatex);
+//! - [Gemtext](render::Gemtext), implemented by builtin components only.
 //!
 //! Components can implement a trait named [`Render<W>`](render::Render) where
 //! `W` is a rendering format. For instance, if a component wants to render
 //! HTML, it must implement `Render<Html>`.
 //!
+//! Components can also implement [`Visit`](visit::Visit), which allows walking
+//! over a tree of components with a [`Visitor`](visit::Visitor) in order to
+//! analyze it (e.g. collecting links or headings) without rendering it.
+//!
 //! Finally, a [`Site`](site::Site) is a collection of pages structured in terms
 //! of in-memory directories. The build process consists of generating pages
 //! into actual directories, as well copying resources. Look at the function
 //! [`static_site_main`]. No JavaScript dependency required for the build
-//! output.
+//! output. For previewing a site locally, [`serve_site_main`] serves it over
+//! HTTP straight from memory.
 //!
 //! # Example
 //!
@@ -75,11 +84,11 @@
 //! }
 //!
 //! fn index() -> impl FullRender<Kind = PageComponent> + Send + Sync + 'static {
-//!     Page {
-//!         banner: banner(),
-//!         title: String::from("Simple Pedia"),
-//!         assets: default_assets(),
-//!         body: harray![
+//!     Page::new(
+//!         banner(),
+//!         String::from("Simple Pedia"),
+//!         default_assets(),
+//!         harray![
 //!             Paragraph(
 //!                 "This is the initial page of the simple pedia. You can dive \
 //!                  down into the following:"
@@ -95,7 +104,7 @@
 //!                 }),
 //!             ]),
 //!         ],
-//!         children: harray![
+//!         harray![
 //!             Section {
 //!                 title: "Random Section",
 //!                 id: Some(Id::new("random")),
@@ -126,45 +135,45 @@
 //!                 children: harray![],
 //!             }
 //!         ],
-//!     }
+//!     )
 //! }
 //!
 //! fn foo_page() -> impl FullRender<Kind = PageComponent> + Send + Sync + 'static {
-//!     Page {
-//!         banner: banner(),
-//!         title: String::from("Foo"),
-//!         assets: default_assets(),
-//!         body: harray![Paragraph("Foo is a metavariable."),],
-//!         children: harray![],
-//!     }
+//!     Page::new(
+//!         banner(),
+//!         String::from("Foo"),
+//!         default_assets(),
+//!         harray![Paragraph("Foo is a metavariable."),],
+//!         harray![],
+//!     )
 //! }
 //!
 //! fn bar_page() -> impl FullRender<Kind = PageComponent> + Send + Sync + 'static {
-//!     Page {
-//!         banner: banner(),
-//!         title: String::from("Bar"),
-//!         assets: default_assets(),
-//!         body: harray![Paragraph(harray![
+//!     Page::new(
+//!         banner(),
+//!         String::from("Bar"),
+//!         default_assets(),
+//!         harray![Paragraph(harray![
 //!             "Bar is a metavariable. ",
 //!             Link { location: Location::internal("bar/baz"), target: "Baz" },
 //!             " is also a metavariable."
 //!         ])],
-//!         children: harray![],
-//!     }
+//!         harray![],
+//!     )
 //! }
 //!
 //! fn baz_page() -> impl FullRender<Kind = PageComponent> + Send + Sync + 'static {
-//!     Page {
-//!         banner: banner(),
-//!         title: String::from("Baz"),
-//!         assets: default_assets(),
-//!         body: harray![Paragraph(harray![
+//!     Page::new(
+//!         banner(),
+//!         String::from("Baz"),
+//!         default_assets(),
+//!         harray![Paragraph(harray![
 //!             "Baz is a metavariable, similar to ",
 //!             Link { location: Location::internal("bar"), target: "Bar" },
 //!             "."
 //!         ])],
-//!         children: harray![],
-//!     }
+//!         harray![],
+//!     )
 //! }
 //!
 //! fn simple_pedia_site() -> Site<DynFullComponent<'static, PageComponent>> {
@@ -200,13 +209,18 @@
 //! ```
 
 use component::page::PageComponent;
-use std::{path::PathBuf, process};
+use std::{
+    net::{TcpListener, ToSocketAddrs},
+    path::PathBuf,
+    process,
+};
 
 pub mod hseq;
 pub mod render;
 pub mod component;
 pub mod location;
 pub mod site;
+pub mod visit;
 
 use render::{Format, Render};
 use site::Site;
@@ -232,3 +246,36 @@ pub fn static_site_main<P, W, O, R>(
         process::exit(1);
     }
 }
+
+/// Main function of a local preview server of a static site targetting one
+/// format. Binds to the given address and serves the site from memory,
+/// rendering pages on each request, without writing a build directory. A
+/// convenience over [`site::Site::serve`].
+pub fn serve_site_main<P, W, A, R>(
+    site: &Site<P>,
+    format: &mut W,
+    address: A,
+    resource_dir: R,
+) where
+    P: Render<W, Kind = PageComponent>,
+    W: Format + ?Sized,
+    A: ToSocketAddrs,
+    R: Into<PathBuf>,
+{
+    let listener = match TcpListener::bind(address) {
+        Ok(listener) => listener,
+        Err(error) => {
+            eprintln!("Failed to bind preview server.\n");
+            eprintln!("{}", error);
+            process::exit(1);
+        },
+    };
+    if let Ok(address) = listener.local_addr() {
+        eprintln!("Serving static encyclopedia at http://{}/", address);
+    }
+    if let Err(error) = site.serve(format, &listener, &resource_dir.into()) {
+        eprintln!("Preview server failed.\n");
+        eprintln!("{}", error);
+        process::exit(1);
+    }
+}
@@ -1,8 +1,22 @@
 //! This module provides location, paths, Urls.
 
 use crate::{
-    component::{Component, InlineComponent},
-    render::{Context, Format, Html, Markdown, Render, Renderer, Text},
+    component::{Component, ComponentKind, InlineComponent},
+    render::{
+        gemtext,
+        latex,
+        Context,
+        Format,
+        Gemtext,
+        Html,
+        Latex,
+        Lookup,
+        Markdown,
+        Render,
+        Renderer,
+        Text,
+    },
+    visit::{visit_node, Node, Visit, Visitor},
 };
 use percent_encoding::{percent_encode, CONTROLS};
 use std::{
@@ -14,6 +28,11 @@ use std::{
 };
 use url::Url;
 
+/// Name of the index page of a directory in a site (see
+/// [`InternalPath::eq_index`]). Rendering formats may name the file of such
+/// pages differently (see [`Format::index_name`]).
+pub const INDEX_NAME: &str = "index.html";
+
 /// A location of a page, either internal or external.
 #[derive(Debug, Clone, PartialEq, Eq, PartialOrd, Ord, Hash)]
 pub enum Location {
@@ -125,6 +144,40 @@ impl Render<Text> for Location {
     }
 }
 
+impl Render<Latex> for Location {
+    fn render(
+        &self,
+        renderer: &mut Renderer<Latex>,
+        ctx: Context<Self::Kind>,
+    ) -> fmt::Result {
+        match self {
+            Location::Url(url) => {
+                write!(renderer, "\\url{{{}}}", latex::escape_url(url.as_str()))
+            },
+            Location::Internal(int) => int.render(renderer, ctx),
+        }
+    }
+}
+
+impl Render<Gemtext> for Location {
+    fn render(
+        &self,
+        renderer: &mut Renderer<Gemtext>,
+        ctx: Context<Self::Kind>,
+    ) -> fmt::Result {
+        renderer.write_str(&gemtext::location_url(self, ctx))
+    }
+}
+
+impl Visit for Location {
+    fn visit<V>(&self, visitor: &mut V, ctx: Context<Self::Kind>)
+    where
+        V: Visitor + ?Sized,
+    {
+        visit_node(visitor, Node::Location(self), ctx, |_| ());
+    }
+}
+
 /// An internal path, without any ID. Always absolute (with the root pointing to
 /// the root of the encyclopedia).
 #[derive(Debug, Clone, PartialEq, Eq, PartialOrd, Ord, Hash)]
@@ -142,7 +195,7 @@ impl InternalPath {
         let string = string.as_ref();
         let mut this = Self { fragments: Vec::new() };
 
-        if string.len() > 0 {
+        if !string.is_empty() {
             for fragment in string.split('/') {
                 this.fragments.push(Fragment::try_new(fragment)?);
             }
@@ -171,7 +224,7 @@ impl InternalPath {
 
     /// Tests if this path leads to the root.
     pub fn is_root(&self) -> bool {
-        self.fragments.len() == 0
+        self.fragments.is_empty()
     }
 
     /// Counts the directory depth.
@@ -214,17 +267,73 @@ impl InternalPath {
     pub fn eq_index(&self, other: &Self) -> bool {
         if self.fragments.len() == other.fragments.len() + 1 {
             let last_index = self.fragments.len() - 1;
-            self.fragments[last_index].as_str() == "index.html"
+            self.fragments[last_index].as_str() == INDEX_NAME
                 && self.fragments[.. last_index] == other.fragments
         } else if other.fragments.len() == self.fragments.len() + 1 {
             let last_index = other.fragments.len() - 1;
-            other.fragments[last_index].as_str() == "index.html"
+            other.fragments[last_index].as_str() == INDEX_NAME
                 && other.fragments[.. last_index] == self.fragments
         } else {
             self == other
         }
     }
 
+    /// Path of the file of the page at this path when rendered in the given
+    /// format. Pages of a site are named after their HTML files: a last
+    /// fragment named [`INDEX_NAME`] is replaced by the index name of the
+    /// format, and any other `.html` extension by the extension of the
+    /// format. Only meant for paths of pages: e.g. resources keep their
+    /// names, whatever their extension (see [`InternalPath::resolve_page`]).
+    pub fn for_format<W>(&self, format: &W) -> Self
+    where
+        W: Format + ?Sized,
+    {
+        let mut path = self.clone();
+        if let Some(last) = path.fragments.last_mut() {
+            let renamed = if last.as_str() == INDEX_NAME {
+                Some(Fragment::new(format.index_name()))
+            } else {
+                last.as_str().strip_suffix(".html").map(|stem| {
+                    Fragment::new(format!("{}.{}", stem, format.extension()))
+                })
+            };
+            if let Some(renamed) = renamed {
+                *last = renamed;
+            }
+        }
+        path
+    }
+
+    /// Path of the page this path leads to in a site, given the lookup of its
+    /// pages (see [`Context::registry`]): the index page of the directory at
+    /// this path, or else this path itself, if it is a page. Yields `None` for
+    /// other paths, e.g. the ones of resources.
+    pub fn resolve_page(&self, lookup: &dyn Lookup) -> Option<Self> {
+        let index = self.clone().append(INDEX_NAME);
+        if lookup.contains_page(&index) {
+            Some(index)
+        } else if lookup.contains_page(self) {
+            Some(self.clone())
+        } else {
+            None
+        }
+    }
+
+    /// Path a link to this path leads to in the given format: pages (looked
+    /// up in the registry of the context, if any) are renamed after the
+    /// format (see [`InternalPath::for_format`]), while directories and other
+    /// paths are kept.
+    fn link_for_format<W, K>(&self, format: &W, ctx: Context<K>) -> Self
+    where
+        W: Format + ?Sized,
+        K: ComponentKind + ?Sized,
+    {
+        match ctx.registry().and_then(|registry| self.resolve_page(registry)) {
+            Some(page) if page == *self => page.for_format(format),
+            _ => self.clone(),
+        }
+    }
+
     fn render_as_url<W>(
         &self,
         renderer: &mut Renderer<W>,
@@ -238,7 +347,8 @@ impl InternalPath {
             for _ in 0 .. ctx.location().dir_depth() {
                 renderer.write_str("../")?;
             }
-            let encoded = percent_encode(self.to_string().as_bytes(), CONTROLS)
+            let path = self.link_for_format(renderer.format(), ctx);
+            let encoded = percent_encode(path.to_string().as_bytes(), CONTROLS)
                 .collect::<String>();
             encoded.render(renderer, ctx)?;
         }
@@ -305,6 +415,35 @@ impl Render<Text> for InternalPath {
     }
 }
 
+impl Render<Latex> for InternalPath {
+    fn render(
+        &self,
+        renderer: &mut Renderer<Latex>,
+        ctx: Context<Self::Kind>,
+    ) -> fmt::Result {
+        self.to_string().render(renderer, ctx)
+    }
+}
+
+impl Render<Gemtext> for InternalPath {
+    fn render(
+        &self,
+        renderer: &mut Renderer<Gemtext>,
+        ctx: Context<Self::Kind>,
+    ) -> fmt::Result {
+        renderer.write_str(&gemtext::url(self, ctx))
+    }
+}
+
+impl Visit for InternalPath {
+    fn visit<V>(&self, visitor: &mut V, ctx: Context<Self::Kind>)
+    where
+        V: Visitor + ?Sized,
+    {
+        visit_node(visitor, Node::InternalPath(self), ctx, |_| ());
+    }
+}
+
 /// Error yielded when an internal location was attempted to be constructed from
 /// an invalid string.
 #[derive(Debug, Clone)]
@@ -434,6 +573,7 @@ impl Render<Html> for InternalLoc {
         renderer: &mut Renderer<Html>,
         ctx: Context<Self::Kind>,
     ) -> fmt::Result {
+        ctx.reference(self);
         self.render_as_url(renderer, ctx)
     }
 }
@@ -444,6 +584,7 @@ impl Render<Markdown> for InternalLoc {
         renderer: &mut Renderer<Markdown>,
         ctx: Context<Self::Kind>,
     ) -> fmt::Result {
+        ctx.reference(self);
         self.render_as_url(renderer, ctx)
     }
 }
@@ -454,10 +595,42 @@ impl Render<Text> for InternalLoc {
         renderer: &mut Renderer<Text>,
         ctx: Context<Self::Kind>,
     ) -> fmt::Result {
+        ctx.reference(self);
         self.render_as_url(renderer, ctx)
     }
 }
 
+impl Render<Latex> for InternalLoc {
+    fn render(
+        &self,
+        renderer: &mut Renderer<Latex>,
+        ctx: Context<Self::Kind>,
+    ) -> fmt::Result {
+        ctx.reference(self);
+        self.to_string().render(renderer, ctx)
+    }
+}
+
+impl Render<Gemtext> for InternalLoc {
+    fn render(
+        &self,
+        renderer: &mut Renderer<Gemtext>,
+        ctx: Context<Self::Kind>,
+    ) -> fmt::Result {
+        ctx.reference(self);
+        renderer.write_str(&gemtext::url(&self.path, ctx))
+    }
+}
+
+impl Visit for InternalLoc {
+    fn visit<V>(&self, visitor: &mut V, ctx: Context<Self::Kind>)
+    where
+        V: Visitor + ?Sized,
+    {
+        visit_node(visitor, Node::InternalLoc(self), ctx, |_| ());
+    }
+}
+
 /// Error when an invalid ID string is given to be parsed.
 #[derive(Debug, Clone)]
 pub struct InvalidId;
@@ -518,7 +691,7 @@ impl Id {
 
 impl fmt::Display for Id {
     fn fmt(&self, fmt: &mut fmt::Formatter) -> fmt::Result {
-        fmt.write_str(&self.as_str())
+        fmt.write_str(self.as_str())
     }
 }
 
@@ -539,6 +712,15 @@ where
     }
 }
 
+impl Visit for Id {
+    fn visit<V>(&self, visitor: &mut V, ctx: Context<Self::Kind>)
+    where
+        V: Visitor + ?Sized,
+    {
+        visit_node(visitor, Node::Id(self), ctx, |_| ());
+    }
+}
+
 /// Error when an invalid fragment (piece of a path) string is given to be
 /// parsed.
 #[derive(Debug, Clone)]
@@ -602,7 +784,7 @@ impl Fragment {
 
 impl fmt::Display for Fragment {
     fn fmt(&self, fmt: &mut fmt::Formatter) -> fmt::Result {
-        fmt.write_str(&self.as_str())
+        fmt.write_str(self.as_str())
     }
 }
 
@@ -626,7 +808,7 @@ impl PathAppendable for Fragment {
     }
 }
 
-impl<'input> PathAppendable for &'input str {
+impl PathAppendable for &str {
     type Error = InvalidFragment;
 
     fn append(self, to_path: &mut InternalPath) -> Result<(), Self::Error> {
@@ -662,6 +844,54 @@ impl PathAppendable for Box<str> {
 #[cfg(test)]
 mod test {
     use super::InternalPath;
+    use crate::{
+        component::InlineComponent,
+        render::{Context, Markdown, RenderAsDisplay},
+        site::registry::{PageInfo, Registry},
+    };
+
+    #[test]
+    fn for_format() {
+        let format = Markdown::default();
+        assert_eq!(
+            InternalPath::parse("foo/index.html").for_format(&format),
+            InternalPath::parse("foo/index.md")
+        );
+        assert_eq!(
+            InternalPath::parse("foo/bar.html").for_format(&format),
+            InternalPath::parse("foo/bar.md")
+        );
+        assert_eq!(
+            InternalPath::parse("foo/bar.png").for_format(&format),
+            InternalPath::parse("foo/bar.png")
+        );
+    }
+
+    #[test]
+    fn links_renamed_for_pages() {
+        let mut registry = Registry::new();
+        for page in ["index.html", "bar.html"] {
+            registry.insert(InternalPath::parse(page), PageInfo::default());
+        }
+        let location = InternalPath::parse("index.html");
+        let ctx =
+            Context::new(&location, &InlineComponent).with_registry(&registry);
+        let link = |path| {
+            let path = InternalPath::parse(path);
+            RenderAsDisplay::new(&path, &mut Markdown::default(), ctx)
+                .to_string()
+        };
+        assert_eq!(link("bar.html"), "bar.md");
+        assert_eq!(link("embed.html"), "embed.html");
+
+        let ctx = Context::new(&location, &InlineComponent);
+        let path = InternalPath::parse("bar.html");
+        assert_eq!(
+            RenderAsDisplay::new(&path, &mut Markdown::default(), ctx)
+                .to_string(),
+            "bar.html"
+        );
+    }
 
     #[test]
     fn eq_index() {
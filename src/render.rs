@@ -1,20 +1,30 @@
 //! This module exports items related to rendering components.
 
 use crate::{
-    component::{Component, ComponentKind},
+    component::{
+        inline::cite::Citations,
+        page::PageMetadata,
+        Component,
+        ComponentKind,
+    },
     hseq::coproduct::{Cocons, Conil},
-    location::InternalPath,
+    location::{self, Id, InternalLoc, InternalPath},
 };
+pub use gemtext::Gemtext;
 pub use html::Html;
+pub use latex::Latex;
 pub use markdown::Markdown;
 use std::{
+    cell::{Cell, RefCell},
     fmt,
     rc::Rc,
     sync::{Arc, Mutex},
 };
 pub use text::Text;
 
+pub mod gemtext;
 pub mod html;
+pub mod latex;
 pub mod markdown;
 pub mod text;
 
@@ -29,9 +39,23 @@ pub trait Format {
         input: &str,
         target: &mut dyn fmt::Write,
     ) -> fmt::Result;
+
+    /// Extension (without the dot) of the files of pages rendered in this
+    /// format. Pages of a site are named after their HTML files, whose `.html`
+    /// extension is replaced by this one when building (see
+    /// [`InternalPath::for_format`]).
+    fn extension(&self) -> &str {
+        "html"
+    }
+
+    /// Name of the file of index pages rendered in this format, replacing
+    /// [`location::INDEX_NAME`] when building.
+    fn index_name(&self) -> &str {
+        location::INDEX_NAME
+    }
 }
 
-impl<'this, W> Format for &'this mut W
+impl<W> Format for &mut W
 where
     W: Format + ?Sized,
 {
@@ -42,6 +66,14 @@ where
     ) -> fmt::Result {
         (**self).write_str(input, target)
     }
+
+    fn extension(&self) -> &str {
+        (**self).extension()
+    }
+
+    fn index_name(&self) -> &str {
+        (**self).index_name()
+    }
 }
 
 impl<W> Format for Box<W>
@@ -55,6 +87,14 @@ where
     ) -> fmt::Result {
         (**self).write_str(input, target)
     }
+
+    fn extension(&self) -> &str {
+        (**self).extension()
+    }
+
+    fn index_name(&self) -> &str {
+        (**self).index_name()
+    }
 }
 
 /// A scope of a render format. The scope allows a component to change rendering
@@ -70,7 +110,7 @@ pub trait Scope {
         F: FnOnce(&mut Self::Format) -> T;
 }
 
-impl<'this, S> Scope for &'this S
+impl<S> Scope for &S
 where
     S: Scope + ?Sized,
 {
@@ -84,7 +124,7 @@ where
     }
 }
 
-impl<'this, S> Scope for &'this mut S
+impl<S> Scope for &mut S
 where
     S: Scope + ?Sized,
 {
@@ -194,7 +234,7 @@ where
     }
 }
 
-impl<'this, T, W> Render<W> for &'this T
+impl<T, W> Render<W> for &T
 where
     W: Format + ?Sized,
     T: Render<W> + ?Sized,
@@ -208,7 +248,7 @@ where
     }
 }
 
-impl<'this, T, W> Render<W> for &'this mut T
+impl<T, W> Render<W> for &mut T
 where
     W: Format + ?Sized,
     T: Render<W> + ?Sized,
@@ -378,6 +418,44 @@ impl<T> FullRender for T where
 {
 }
 
+/// An auto-trait for fully renderable components that also render to LaTeX.
+/// LaTeX is not required by [`FullRender`], so components opt in to it by
+/// implementing `Render<Latex>`.
+pub trait FullRenderLatex: FullRender + Render<Latex> {
+    /// Converts the component into a trait object wrapped by a shared
+    /// reference, like [`FullRender::into_dyn`], but keeping LaTeX support.
+    fn into_dyn_latex<'obj>(self) -> DynFullLatexComponent<'obj, Self::Kind>
+    where
+        Self: Sized + Send + Sync + 'obj,
+    {
+        Arc::new(self)
+    }
+
+    /// Converts the component into a trait object wrapped by a shared
+    /// reference, like [`FullRender::into_dyn_unsync`], but keeping LaTeX
+    /// support.
+    fn into_dyn_latex_unsync<'obj>(
+        self,
+    ) -> DynFullLatexComponentUnsync<'obj, Self::Kind>
+    where
+        Self: Sized + 'obj,
+    {
+        Rc::new(self)
+    }
+}
+
+/// A dynamic trait object for thread-safe components with full render support,
+/// LaTeX included.
+pub type DynFullLatexComponent<'obj, K> =
+    Arc<dyn FullRenderLatex<Kind = K> + Send + Sync + 'obj>;
+
+/// A dynamic trait object for thread-unsafe components with full render
+/// support, LaTeX included.
+pub type DynFullLatexComponentUnsync<'obj, K> =
+    Rc<dyn FullRenderLatex<Kind = K> + 'obj>;
+
+impl<T> FullRenderLatex for T where T: FullRender + Render<Latex> + ?Sized {}
+
 /// A proxy formatter for rendering using Rust's formatting and a render format.
 pub struct Renderer<'format, 'target, 'obj, W>
 where
@@ -412,6 +490,17 @@ where
         Self { format, target }
     }
 
+    /// Yields the render format of this renderer.
+    pub fn format(&self) -> &W {
+        self.format
+    }
+
+    /// Yields the render format of this renderer, mutably. Useful for formats
+    /// keeping track of state during rendering.
+    pub fn format_mut(&mut self) -> &mut W {
+        self.format
+    }
+
     /// Given a scope over the render format and a scope consumer, enters the
     /// given scope.
     pub fn scoped<S, F, T>(&mut self, scope: S, consumer: F) -> T
@@ -438,6 +527,138 @@ where
     }
 }
 
+/// An observer of a rendering session. Components notify the observer (if
+/// any) about things such as internal locations they reference and IDs they
+/// define, allowing analysis of the rendered pages, such as link checking.
+pub trait Observer: fmt::Debug {
+    /// Notifies that the given internal location was referenced.
+    fn reference(&self, _location: &InternalLoc) {}
+
+    /// Notifies that the given ID was defined in the page being rendered.
+    fn define(&self, _id: &Id) {}
+
+    /// Notifies the title of the page being rendered.
+    fn title(&self, _title: &str) {}
+
+    /// Notifies that a section with the given ID was rendered, along with its
+    /// title, rendered as a label in the format of the rendering session (see
+    /// [`Context::as_label`]).
+    fn section(&self, _id: &Id, _title: &str) {}
+
+    /// Notifies the metadata of the page being rendered.
+    fn metadata(&self, _metadata: &PageMetadata) {}
+}
+
+impl<O> Observer for &O
+where
+    O: Observer + ?Sized,
+{
+    fn reference(&self, location: &InternalLoc) {
+        (**self).reference(location)
+    }
+
+    fn define(&self, id: &Id) {
+        (**self).define(id)
+    }
+
+    fn title(&self, title: &str) {
+        (**self).title(title)
+    }
+
+    fn section(&self, id: &Id, title: &str) {
+        (**self).section(id, title)
+    }
+
+    fn metadata(&self, metadata: &PageMetadata) {
+        (**self).metadata(metadata)
+    }
+}
+
+/// Lookup of the pages of the site being rendered, allowing components to
+/// refer to other pages, e.g. by their titles (see [`Context::registry`]).
+/// Implemented by the registry of a site (see
+/// [`Registry`](crate::site::registry::Registry)).
+pub trait Lookup: fmt::Debug {
+    /// Tests whether there is a page at the given path, or an index page in
+    /// the directory at the given path.
+    fn contains_page(&self, path: &InternalPath) -> bool;
+
+    /// Tests whether the given location points to a page and, if it has an
+    /// ID, to a section of the page.
+    fn contains(&self, location: &InternalLoc) -> bool;
+
+    /// Yields the title of the page at the given path, if any.
+    fn title(&self, path: &InternalPath) -> Option<&str>;
+
+    /// Yields the title of the section with the given ID in the page at the
+    /// given path, if any.
+    fn section_title(&self, path: &InternalPath, id: &Id) -> Option<&str>;
+
+    /// Yields the metadata of the page at the given path, if any.
+    fn metadata(&self, path: &InternalPath) -> Option<&PageMetadata>;
+}
+
+/// Footnotes of a page being rendered, numbered from 1. Footnote components
+/// add their rendered contents, and lists of footnotes take the ones not
+/// listed yet (see [`Context::footnotes`]).
+#[derive(Debug, Default)]
+pub struct Footnotes {
+    count: Cell<u32>,
+    pending: RefCell<Vec<(u32, String)>>,
+}
+
+impl Footnotes {
+    /// Creates an empty collection of footnotes.
+    pub fn new() -> Self {
+        Self::default()
+    }
+
+    /// Allocates the number of a new footnote. Numbers are allocated before
+    /// footnotes are rendered, so that footnotes nested in a footnote come
+    /// after it.
+    pub fn next_number(&self) -> u32 {
+        let number = self.count.get() + 1;
+        self.count.set(number);
+        number
+    }
+
+    /// Adds the rendered contents of the footnote with the given number.
+    pub fn push(&self, number: u32, contents: String) {
+        self.pending.borrow_mut().push((number, contents));
+    }
+
+    /// Takes the footnotes added but not listed yet, in order of numbers.
+    pub fn take(&self) -> Vec<(u32, String)> {
+        let mut footnotes = self.pending.take();
+        footnotes.sort_by_key(|(number, _)| *number);
+        footnotes
+    }
+}
+
+/// Errors reported by components failing to render a page, e.g. because of
+/// invalid contents (see [`Context::fail`]).
+#[derive(Debug, Default)]
+pub struct RenderErrors {
+    messages: RefCell<Vec<String>>,
+}
+
+impl RenderErrors {
+    /// Creates an empty collection of errors.
+    pub fn new() -> Self {
+        Self::default()
+    }
+
+    /// Adds the message of an error.
+    pub fn push(&self, message: String) {
+        self.messages.borrow_mut().push(message);
+    }
+
+    /// Takes the messages of the errors reported so far, in order.
+    pub fn take(&self) -> Vec<String> {
+        self.messages.take()
+    }
+}
+
 /// Context data of a session of rendering.
 #[derive(Debug)]
 pub struct Context<'loc, 'kind, K>
@@ -447,6 +668,12 @@ where
     location: &'loc InternalPath,
     level: u32,
     kind: &'kind K,
+    observer: Option<&'loc dyn Observer>,
+    footnotes: Option<&'loc Footnotes>,
+    citations: Option<&'loc Citations>,
+    errors: Option<&'loc RenderErrors>,
+    registry: Option<&'loc dyn Lookup>,
+    label: bool,
 }
 
 impl<'loc, 'kind, K> Clone for Context<'loc, 'kind, K>
@@ -454,7 +681,7 @@ where
     K: ComponentKind + ?Sized,
 {
     fn clone(&self) -> Self {
-        Self { location: self.location, level: self.level, kind: self.kind }
+        *self
     }
 }
 
@@ -469,7 +696,17 @@ where
 {
     /// Creates a context from page location and component kind.
     pub fn new(location: &'loc InternalPath, kind: &'kind K) -> Self {
-        Self { location, level: 0, kind }
+        Self {
+            location,
+            level: 0,
+            kind,
+            observer: None,
+            footnotes: None,
+            citations: None,
+            errors: None,
+            registry: None,
+            label: false,
+        }
     }
 
     /// Recreates the context but with another  component kind.
@@ -477,7 +714,55 @@ where
     where
         Q: ComponentKind + ?Sized,
     {
-        Context { location: self.location, level: self.level, kind }
+        Context {
+            location: self.location,
+            level: self.level,
+            kind,
+            observer: self.observer,
+            footnotes: self.footnotes,
+            citations: self.citations,
+            errors: self.errors,
+            registry: self.registry,
+            label: self.label,
+        }
+    }
+
+    /// Recreates the context but with the given observer attached.
+    pub fn with_observer(self, observer: &'loc dyn Observer) -> Self {
+        Self { observer: Some(observer), ..self }
+    }
+
+    /// Recreates the context but with the given footnotes collection, where
+    /// footnotes rendered from now on are added.
+    pub fn with_footnotes(self, footnotes: &'loc Footnotes) -> Self {
+        Self { footnotes: Some(footnotes), ..self }
+    }
+
+    /// Recreates the context but with the given citations collection, where
+    /// citations rendered from now on are recorded.
+    pub fn with_citations(self, citations: &'loc Citations) -> Self {
+        Self { citations: Some(citations), ..self }
+    }
+
+    /// Recreates the context but with the given errors collection, where
+    /// errors reported from now on are added.
+    pub fn with_errors(self, errors: &'loc RenderErrors) -> Self {
+        Self { errors: Some(errors), ..self }
+    }
+
+    /// Recreates the context but with the given registry of the site being
+    /// rendered, allowing components to look up other pages.
+    pub fn with_registry(self, registry: &'loc dyn Lookup) -> Self {
+        Self { registry: Some(registry), ..self }
+    }
+
+    /// Recreates the context for rendering a label, i.e. contents placed
+    /// inside a link elsewhere, such as the title of a section given to the
+    /// observer (see [`Observer::section`]). Links render only their contents
+    /// and footnotes are not rendered. Since the contents were already
+    /// rendered normally, the observer and the errors collection are dropped.
+    pub fn as_label(self) -> Self {
+        Self { label: true, observer: None, errors: None, ..self }
     }
 
     /// Yields the location of the page being rendered.
@@ -499,6 +784,88 @@ where
     pub fn kind(self) -> &'kind K {
         self.kind
     }
+
+    /// Yields the observer of this rendering session, if any.
+    pub fn observer(self) -> Option<&'loc dyn Observer> {
+        self.observer
+    }
+
+    /// Yields the footnotes collection of the page being rendered, if any.
+    pub fn footnotes(self) -> Option<&'loc Footnotes> {
+        self.footnotes
+    }
+
+    /// Yields the citations collection of the page being rendered, if any.
+    pub fn citations(self) -> Option<&'loc Citations> {
+        self.citations
+    }
+
+    /// Yields the errors collection of the page being rendered, if any.
+    pub fn errors(self) -> Option<&'loc RenderErrors> {
+        self.errors
+    }
+
+    /// Yields the registry of the site being rendered, if any.
+    pub fn registry(self) -> Option<&'loc dyn Lookup> {
+        self.registry
+    }
+
+    /// Tests whether a label is being rendered (see [`Context::as_label`]).
+    pub fn in_label(self) -> bool {
+        self.label
+    }
+
+    /// Reports an error making the rendering fail to the errors collection
+    /// (if any), yielding the formatting error to be returned by the failing
+    /// component.
+    pub fn fail<E>(self, error: E) -> fmt::Error
+    where
+        E: fmt::Display,
+    {
+        if let Some(errors) = self.errors {
+            errors.push(error.to_string());
+        }
+        fmt::Error
+    }
+
+    /// Notifies the observer (if any) that the given internal location was
+    /// referenced.
+    pub fn reference(self, location: &InternalLoc) {
+        if let Some(observer) = self.observer {
+            observer.reference(location);
+        }
+    }
+
+    /// Notifies the observer (if any) that the given ID was defined.
+    pub fn define(self, id: &Id) {
+        if let Some(observer) = self.observer {
+            observer.define(id);
+        }
+    }
+
+    /// Notifies the observer (if any) of the title of the page being
+    /// rendered.
+    pub fn title(self, title: &str) {
+        if let Some(observer) = self.observer {
+            observer.title(title);
+        }
+    }
+
+    /// Notifies the observer (if any) that a section with the given ID and
+    /// title (rendered as a label) was rendered.
+    pub fn section(self, id: &Id, title: &str) {
+        if let Some(observer) = self.observer {
+            observer.section(id, title);
+        }
+    }
+
+    /// Notifies the observer (if any) of the metadata of the page being
+    /// rendered.
+    pub fn metadata(self, metadata: &PageMetadata) {
+        if let Some(observer) = self.observer {
+            observer.metadata(metadata);
+        }
+    }
 }
 
 /// A helper type that will render a component using Rust's [`fmt::Display`]
@@ -116,13 +116,13 @@ mod test {
         let mut output = String::new();
         let mut format = CommonText::default();
         let mut renderer = Renderer::new(&mut format, &mut output);
-        write!(renderer, "abcdefg\n").unwrap();
+        writeln!(renderer, "abcdefg").unwrap();
 
         renderer
             .scoped(Nest, |renderer| write!(renderer, "123\n4567\nh\n"))
             .unwrap();
 
-        write!(renderer, "ijk\n").unwrap();
+        writeln!(renderer, "ijk").unwrap();
 
         renderer
             .scoped(Nest, |renderer| {
@@ -133,7 +133,7 @@ mod test {
                         write!(renderer, "idk\nyeah\n")
                     })
                 })?;
-                write!(renderer, "eee\n")
+                writeln!(renderer, "eee")
             })
             .unwrap();
 
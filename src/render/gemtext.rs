@@ -0,0 +1,211 @@
+//! This module provides utilities about the Gemini (gemtext) rendering format.
+
+use super::{Context, Format, Scope};
+use crate::{
+    component::ComponentKind,
+    location::{InternalPath, Location},
+};
+use percent_encoding::{percent_encode, AsciiSet, CONTROLS};
+use std::fmt;
+
+/// Characters encoded in URLs of link lines, where a space would end the URL.
+const URL_ENCODE_SET: &AsciiSet = &CONTROLS.add(b' ');
+
+/// The gemtext rendering format.
+///
+/// Gemtext has no inline links: links are hoisted by components (see
+/// [`Gemtext::hoist_link`]) and written by this format as link lines (`=>`)
+/// right after the line where they appeared.
+#[derive(Debug, Clone, PartialEq, Eq, Default)]
+pub struct Gemtext {
+    links: Vec<(String, String)>,
+    line_level: u32,
+    preformatted: bool,
+}
+
+impl Gemtext {
+    /// Hoists a link with the given URL and label, to be written after the
+    /// current line.
+    pub fn hoist_link(&mut self, url: String, label: String) {
+        self.links.push((url, label));
+    }
+}
+
+impl Format for Gemtext {
+    fn write_str(
+        &mut self,
+        input: &str,
+        target: &mut dyn fmt::Write,
+    ) -> fmt::Result {
+        if self.line_level > 0 {
+            let mut start = 0;
+            for (end, _) in input.match_indices('\n') {
+                target.write_str(&input[start .. end])?;
+                target.write_str(" ")?;
+                start = end + 1;
+            }
+            return target.write_str(&input[start ..]);
+        }
+
+        for line in input.split_inclusive('\n') {
+            target.write_str(line)?;
+            if line.ends_with('\n') && !self.preformatted {
+                for (url, label) in self.links.drain(..) {
+                    target.write_str(&link_line(&url, &label))?;
+                }
+            }
+        }
+        Ok(())
+    }
+
+    fn extension(&self) -> &str {
+        "gmi"
+    }
+
+    fn index_name(&self) -> &str {
+        "index.gmi"
+    }
+}
+
+/// Line scope: everything written while in this scope stays in the same line,
+/// line breaks being written as spaces. Used for headings, list items, etc.
+#[derive(Debug, Clone, Copy)]
+pub struct Line;
+
+impl Scope for Line {
+    type Format = Gemtext;
+
+    fn enter<F, T>(&self, format: &mut Self::Format, consumer: F) -> T
+    where
+        F: FnOnce(&mut Self::Format) -> T,
+    {
+        format.line_level += 1;
+        let output = consumer(format);
+        format.line_level -= 1;
+        output
+    }
+}
+
+/// Preformatted scope: hoisted links are not written while in this scope, but
+/// after the first line finished outside of it.
+#[derive(Debug, Clone, Copy)]
+pub struct Preformatted;
+
+impl Scope for Preformatted {
+    type Format = Gemtext;
+
+    fn enter<F, T>(&self, format: &mut Self::Format, consumer: F) -> T
+    where
+        F: FnOnce(&mut Self::Format) -> T,
+    {
+        let previous = format.preformatted;
+        format.preformatted = true;
+        let output = consumer(format);
+        format.preformatted = previous;
+        output
+    }
+}
+
+/// A link line (`=>`) to the given URL with the given label, including the
+/// line break.
+pub fn link_line(url: &str, label: &str) -> String {
+    let label = label.trim();
+    if label.is_empty() {
+        format!("=> {}\n", url)
+    } else {
+        format!("=> {} {}\n", url, label)
+    }
+}
+
+/// URL of the given path, relative to the page being rendered. Paths leading
+/// to pages, as looked up in the registry of the context (see
+/// [`InternalPath::resolve_page`]), lead to their gemtext files, e.g. a
+/// directory to its `index.gmi`. Other paths, e.g. the ones of resources, and
+/// every path if there is no registry, are kept.
+pub fn url<K>(path: &InternalPath, ctx: Context<K>) -> String
+where
+    K: ComponentKind + ?Sized,
+{
+    let format = Gemtext::default();
+    let mut url = String::new();
+    for _ in 0 .. ctx.location().dir_depth() {
+        url.push_str("../");
+    }
+    let target = match ctx.registry().and_then(|reg| path.resolve_page(reg)) {
+        Some(page) => page.for_format(&format),
+        None => path.clone(),
+    };
+    let target = target.to_string();
+    url.extend(percent_encode(target.as_bytes(), URL_ENCODE_SET));
+    url
+}
+
+/// URL of a location for a link line, relative to the page being rendered.
+/// Internal locations are notified to the observer (if any) as referenced;
+/// since gemtext has no anchors, their IDs are dropped.
+pub fn location_url<K>(location: &Location, ctx: Context<K>) -> String
+where
+    K: ComponentKind + ?Sized,
+{
+    match location {
+        Location::Url(url) => url.to_string(),
+        Location::Internal(loc) => {
+            ctx.reference(loc);
+            url(&loc.path, ctx)
+        },
+    }
+}
+
+#[cfg(test)]
+mod test {
+    use super::{url, Gemtext, Line};
+    use crate::{
+        component::InlineComponent,
+        location::InternalPath,
+        render::{Context, Renderer},
+        site::registry::{PageInfo, Registry},
+    };
+    use std::fmt::Write;
+
+    #[test]
+    fn hoisted_links() {
+        let mut output = String::new();
+        let mut format = Gemtext::default();
+        let mut renderer = Renderer::new(&mut format, &mut output);
+        write!(renderer, "see ").unwrap();
+        renderer
+            .format_mut()
+            .hoist_link(String::from("foo.gmi"), String::from("Foo"));
+        renderer.scoped(Line, |renderer| write!(renderer, "Foo\nnow")).unwrap();
+        write!(renderer, "\n\nnext\n").unwrap();
+
+        assert_eq!(output, "see Foo now\n=> foo.gmi Foo\n\nnext\n");
+    }
+
+    #[test]
+    fn urls() {
+        let mut registry = Registry::new();
+        for page in
+            ["index.html", "bar/index.html", "a b/c.html", "v1.2/index.html"]
+        {
+            registry.insert(InternalPath::parse(page), PageInfo::default());
+        }
+        let from = InternalPath::parse("foo/index.html");
+        let ctx =
+            Context::new(&from, &InlineComponent).with_registry(&registry);
+        let url = |path| url(&InternalPath::parse(path), ctx);
+        assert_eq!(url("bar"), "../bar/index.gmi");
+        assert_eq!(url("a b/c.html"), "../a%20b/c.gmi");
+        assert_eq!(url("v1.2"), "../v1.2/index.gmi");
+        assert_eq!(url("d.png"), "../d.png");
+        assert_eq!(url("pineapple"), "../pineapple");
+        assert_eq!(url("embed.html"), "../embed.html");
+
+        let root = InternalPath::root();
+        let ctx =
+            Context::new(&root, &InlineComponent).with_registry(&registry);
+        assert_eq!(super::url(&root, ctx), "index.gmi");
+        let ctx = Context::new(&root, &InlineComponent);
+        assert_eq!(super::url(&InternalPath::parse("bar"), ctx), "bar");
+    }
+}
@@ -17,22 +17,25 @@ impl Format for Html {
     }
 }
 
+/// Helpers for testing HTML output.
 #[cfg(test)]
 pub mod test {
     use scraper::Html;
 
+    /// Validates an HTML fragment, returning the parser errors if any.
     pub fn validate_html_fragment(fragment: &str) -> Result<(), Vec<String>> {
         let output = Html::parse_fragment(fragment);
-        if output.errors.len() == 0 {
+        if output.errors.is_empty() {
             Ok(())
         } else {
             Err(output.errors.into_iter().map(String::from).collect())
         }
     }
 
+    /// Validates a whole HTML document, returning the parser errors if any.
     pub fn validate_html_document(fragment: &str) -> Result<(), Vec<String>> {
         let output = Html::parse_document(fragment);
-        if output.errors.len() == 0 {
+        if output.errors.is_empty() {
             Ok(())
         } else {
             Err(output.errors.into_iter().map(String::from).collect())
@@ -0,0 +1,248 @@
+//! This module provides utilities about the LaTeX rendering format.
+
+use super::Format;
+use crate::location::{Id, InternalPath, INDEX_NAME};
+use std::fmt;
+
+/// Packages used by the LaTeX rendering of builtin components. A page rendered
+/// with [`PageStyle::Document`] loads them by itself, but pages rendered as
+/// chapters expect the including document to load them.
+pub const PACKAGES: &[&str] = &["graphicx", "multirow", "hyperref"];
+
+/// How pages are mapped to LaTeX.
+#[derive(Debug, Clone, Copy, PartialEq, Eq, PartialOrd, Ord, Hash, Default)]
+pub enum PageStyle {
+    /// A page is a whole `article` document, with its own preamble.
+    #[default]
+    Document,
+    /// A page is a `\chapter`, suitable for inclusion in a `book` document.
+    Chapter,
+}
+
+/// State of a table being rendered.
+#[derive(Debug, Clone, PartialEq, Eq, Default)]
+struct TableState {
+    /// Number of columns of the widest row so far.
+    columns: u32,
+    /// Column where the next cell of the current row starts.
+    column: u32,
+    /// Whether the current row has any cell written.
+    started: bool,
+    /// Whether a cell is being rendered.
+    in_cell: bool,
+    /// For each column, the number of rows still covered by a cell spanning
+    /// multiple rows, current row included.
+    covered: Vec<u32>,
+}
+
+/// The LaTeX rendering format.
+///
+/// Besides writing to the target, this format keeps track of the tables being
+/// rendered, so that cells spanning multiple rows or columns can be laid out.
+/// While rendering a table cell, line breaks are written as spaces, since
+/// paragraphs are not allowed in cells.
+#[derive(Debug, Clone, PartialEq, Eq, Default)]
+pub struct Latex {
+    page_style: PageStyle,
+    tables: Vec<TableState>,
+}
+
+impl Latex {
+    /// Creates a new LaTeX format renderer given how pages are mapped.
+    pub fn new(page_style: PageStyle) -> Self {
+        Self { page_style, tables: Vec::new() }
+    }
+
+    /// Yields how pages are mapped.
+    pub fn page_style(&self) -> PageStyle {
+        self.page_style
+    }
+
+    /// Starts a table. Rows and cells are reported to the innermost table.
+    pub fn begin_table(&mut self) {
+        self.tables.push(TableState::default());
+    }
+
+    /// Finishes the innermost table, yielding its number of columns.
+    pub fn end_table(&mut self) -> u32 {
+        self.tables.pop().map_or(0, |table| table.columns)
+    }
+
+    /// Starts a row in the innermost table.
+    pub fn begin_row(&mut self) {
+        if let Some(table) = self.tables.last_mut() {
+            table.column = 0;
+            table.started = false;
+        }
+    }
+
+    /// Finishes a row in the innermost table.
+    pub fn end_row(&mut self) {
+        if let Some(table) = self.tables.last_mut() {
+            let covered_end = table
+                .covered
+                .iter()
+                .rposition(|&rows| rows > 0)
+                .map_or(0, |column| column as u32 + 1);
+            table.columns = table.columns.max(table.column).max(covered_end);
+            for rows in &mut table.covered {
+                *rows = rows.saturating_sub(1);
+            }
+        }
+    }
+
+    /// Starts a cell in the current row of the innermost table, spanning the
+    /// given number of rows and columns. Yields how many column separators
+    /// (`&`) must be written before the cell, including the ones skipping
+    /// columns covered by cells of previous rows.
+    pub fn begin_cell(&mut self, rowspan: u32, colspan: u32) -> u32 {
+        let table = match self.tables.last_mut() {
+            Some(table) => table,
+            None => return 0,
+        };
+        let mut separators = 0;
+        loop {
+            let rows = table.covered.get(table.column as usize);
+            if matches!(rows, None | Some(0)) {
+                break;
+            }
+            if table.started {
+                separators += 1;
+            }
+            table.started = true;
+            table.column += 1;
+        }
+        if table.started {
+            separators += 1;
+        }
+        table.started = true;
+
+        let start = table.column as usize;
+        let end = start + colspan.max(1) as usize;
+        if table.covered.len() < end {
+            table.covered.resize(end, 0);
+        }
+        for rows in &mut table.covered[start .. end] {
+            *rows = rowspan.max(1);
+        }
+        table.column = end as u32;
+        table.in_cell = true;
+        separators
+    }
+
+    /// Finishes a cell of the innermost table.
+    pub fn end_cell(&mut self) {
+        if let Some(table) = self.tables.last_mut() {
+            table.in_cell = false;
+        }
+    }
+}
+
+impl Format for Latex {
+    fn write_str(
+        &mut self,
+        input: &str,
+        target: &mut dyn fmt::Write,
+    ) -> fmt::Result {
+        if self.tables.last().is_some_and(|table| table.in_cell) {
+            let mut start = 0;
+            for (end, _) in input.match_indices('\n') {
+                target.write_str(&input[start .. end])?;
+                target.write_str(" ")?;
+                start = end + 1;
+            }
+            target.write_str(&input[start ..])
+        } else {
+            target.write_str(input)
+        }
+    }
+
+    fn extension(&self) -> &str {
+        "tex"
+    }
+
+    fn index_name(&self) -> &str {
+        "index.tex"
+    }
+}
+
+/// Label of a page, or of a section if an ID is given, suitable for `\label`
+/// and `\hyperref`. A path ending in [`INDEX_NAME`] has the same label as its
+/// directory.
+pub fn label(path: &InternalPath, id: Option<&Id>) -> String {
+    let fragments = match path.fragments.split_last() {
+        Some((last, init)) if last.as_str() == INDEX_NAME => init,
+        _ => &path.fragments[..],
+    };
+    let mut label = String::from("pedia:");
+    for (i, fragment) in fragments.iter().enumerate() {
+        if i > 0 {
+            label.push('/');
+        }
+        for ch in fragment.as_str().chars() {
+            if ch.is_ascii_alphanumeric() || matches!(ch, '.' | '-' | '_') {
+                label.push(ch);
+            } else {
+                let mut buf = [0; 4];
+                for byte in ch.encode_utf8(&mut buf).bytes() {
+                    label.push_str(&format!("+{:02X}", byte));
+                }
+            }
+        }
+    }
+    if let Some(id) = id {
+        label.push(':');
+        label.push_str(id.as_str());
+    }
+    label
+}
+
+/// Escapes an URL for use as an argument of `\href` or `\url`.
+pub fn escape_url(url: &str) -> String {
+    let mut escaped = String::with_capacity(url.len());
+    for ch in url.chars() {
+        if let '#' | '%' | '\\' | '{' | '}' = ch {
+            escaped.push('\\');
+        }
+        escaped.push(ch);
+    }
+    escaped
+}
+
+#[cfg(test)]
+mod test {
+    use super::{label, Latex};
+    use crate::location::{Id, InternalPath};
+
+    #[test]
+    fn labels() {
+        assert_eq!(
+            label(&InternalPath::parse("foo/bar/index.html"), None),
+            "pedia:foo/bar"
+        );
+        assert_eq!(
+            label(&InternalPath::parse("foo bar"), Some(&Id::new("baz"))),
+            "pedia:foo+20bar:baz"
+        );
+    }
+
+    #[test]
+    fn spanning_cells() {
+        let mut format = Latex::default();
+        format.begin_table();
+
+        format.begin_row();
+        assert_eq!(format.begin_cell(2, 1), 0);
+        format.end_cell();
+        assert_eq!(format.begin_cell(1, 2), 1);
+        format.end_cell();
+        format.end_row();
+
+        format.begin_row();
+        assert_eq!(format.begin_cell(1, 1), 1);
+        format.end_cell();
+        format.end_row();
+
+        assert_eq!(format.end_table(), 3);
+    }
+}
@@ -29,6 +29,14 @@ impl Format for Markdown {
     ) -> fmt::Result {
         self.inner.write_str(input, target)
     }
+
+    fn extension(&self) -> &str {
+        "md"
+    }
+
+    fn index_name(&self) -> &str {
+        "index.md"
+    }
 }
 
 /// Nesting scope: advances indentation level every entering, except for the
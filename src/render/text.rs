@@ -29,6 +29,14 @@ impl Format for Text {
     ) -> fmt::Result {
         self.inner.write_str(input, target)
     }
+
+    fn extension(&self) -> &str {
+        "txt"
+    }
+
+    fn index_name(&self) -> &str {
+        "index.txt"
+    }
 }
 
 /// Nesting scope: advances indentation level every entering, except for the
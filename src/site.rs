@@ -2,9 +2,13 @@
 //! pages and effectively generating them.
 
 use crate::{
-    component::{page::PageComponent, Component},
-    location::{Fragment, InternalPath},
-    render::{self, Context, Render, RenderAsDisplay},
+    component::{
+        inline::cite::{CitationStyle, Citations},
+        page::PageComponent,
+        Component,
+    },
+    location::{Fragment, InternalPath, INDEX_NAME},
+    render::{self, Context, Render, RenderAsDisplay, RenderErrors},
 };
 use std::{
     collections::{hash_map, HashMap},
@@ -12,8 +16,33 @@ use std::{
     fmt,
     fs,
     io::{self, Write},
-    path::PathBuf,
+    num::NonZeroUsize,
+    path::{Path, PathBuf},
+    sync::{
+        atomic::{self, AtomicUsize},
+        Mutex,
+        PoisonError,
+    },
+    thread,
 };
+use url::Url;
+
+pub mod archive;
+pub mod check;
+pub mod feed;
+pub mod incremental;
+pub mod multi;
+pub mod output;
+pub mod registry;
+pub mod search;
+pub mod serve;
+pub mod sitemap;
+
+use feed::Feed;
+use output::Output;
+use registry::Registry;
+use search::SearchIndex;
+use sitemap::Sitemap;
 
 /// An error that may happen when building a site.
 #[derive(Debug)]
@@ -74,6 +103,24 @@ where
 {
     /// Root directory.
     pub root: Directory<P>,
+    /// Configuration of the `sitemap.xml` written at the root of the build
+    /// output, if any is desired.
+    pub sitemap: Option<Sitemap>,
+    /// Atom feeds written along with the build output.
+    pub feeds: Vec<Feed>,
+    /// Full-text search index written along with the build output, if any is
+    /// desired (see [`SearchIndex::from_site`]).
+    pub search: Option<SearchIndex>,
+    /// Citation style of the pages of the site (see
+    /// [`Context::citations`]).
+    pub citation_style: CitationStyle,
+    /// Whether pages are rendered a first time when building, gathering their
+    /// titles, sections and metadata into the registry of the site (see
+    /// [`Registry::from_site`]), e.g. for breadcrumbs, auto-titled links or
+    /// dates of the sitemap. Otherwise, the registry only knows which pages
+    /// exist (see [`Registry::from_pages`]). Disabled by default, since pages
+    /// are then rendered twice.
+    pub prerender: bool,
 }
 
 impl<P> Default for Site<P>
@@ -81,7 +128,14 @@ where
     P: Component<Kind = PageComponent>,
 {
     fn default() -> Self {
-        Self { root: Directory::default() }
+        Self {
+            root: Directory::default(),
+            sitemap: None,
+            feeds: Vec::new(),
+            search: None,
+            citation_style: CitationStyle::default(),
+            prerender: false,
+        }
     }
 }
 
@@ -90,7 +144,9 @@ where
     P: Component<Kind = PageComponent>,
 {
     /// Builds the site into a concrete filesystem, given a render format,
-    /// an output directory path, a resource directory path.
+    /// an output directory path, a resource directory path. The output
+    /// directory is cleared first. A convenience over [`Site::build_into`]
+    /// with an [`output::Filesystem`].
     ///
     /// The output and resource directories must be a mutable reference because
     /// they will be used to navigate to the site, but they will be restored,
@@ -104,6 +160,31 @@ where
     where
         W: render::Format + ?Sized,
         P: Render<W>,
+    {
+        let _ = fs::remove_dir_all(&output_dir);
+        let mut output = output::Filesystem::new(output_dir.clone());
+        self.build_into(format, &mut output, resource_dir)
+    }
+
+    /// Builds the site through the given output backend, given a render
+    /// format and a resource directory path. Entries are built in order of
+    /// their paths, and directories are created before their entries. Files
+    /// of pages are named after the format (see [`InternalPath::for_format`]).
+    /// Pages are rendered a first time if [`Site::prerender`] is enabled.
+    ///
+    /// The resource directory must be a mutable reference because it will be
+    /// used to navigate to the site, but it will be restored, unless a panic
+    /// occurs.
+    pub fn build_into<W, O>(
+        &self,
+        format: &mut W,
+        output: &mut O,
+        resource_dir: &mut PathBuf,
+    ) -> Result<(), BuildError>
+    where
+        W: render::Format + ?Sized,
+        P: Render<W>,
+        O: Output + ?Sized,
     {
         enum Operation<'site, P>
         where
@@ -114,24 +195,23 @@ where
             Pop,
         }
 
-        let dest = output_dir;
+        let registry = self.registry(format);
         let source = resource_dir;
         let mut internal_path = InternalPath::default();
 
-        let _ = fs::remove_dir_all(&dest)
-            .map_err(|cause| BuildError { path: internal_path.clone(), cause });
-
         let mut operations =
             vec![Operation::Build(Entry::Directory(&self.root))];
 
         while let Some(operation) = operations.pop() {
             match operation {
                 Operation::Build(Entry::Directory(directory)) => {
-                    fs::create_dir_all(&dest).map_err(|cause| BuildError {
-                        path: internal_path.clone(),
-                        cause,
+                    output.create_dir(&internal_path).map_err(|cause| {
+                        BuildError { path: internal_path.clone(), cause }
                     })?;
-                    for (fragment, entry) in &directory.entries {
+                    let mut entries =
+                        directory.entries.iter().collect::<Vec<_>>();
+                    entries.sort_by(|(left, _), (right, _)| right.cmp(left));
+                    for (fragment, entry) in entries {
                         operations.push(Operation::Pop);
                         operations.push(Operation::Build(entry.by_ref()));
                         operations.push(Operation::Push(fragment));
@@ -139,44 +219,363 @@ where
                 },
 
                 Operation::Build(Entry::Page(page)) => {
-                    let mut file =
-                        fs::File::create(&dest).map_err(|cause| {
-                            BuildError { path: internal_path.clone(), cause }
-                        })?;
-
-                    let context = Context::new(&internal_path, &PageComponent);
-                    let renderer = RenderAsDisplay::new(page, format, context);
-
-                    write!(file, "{}", renderer).map_err(|cause| {
-                        BuildError { path: internal_path.clone(), cause }
-                    })?;
+                    let path = internal_path.for_format(format);
+                    let mut contents = Vec::new();
+                    build_page(
+                        page,
+                        format,
+                        &internal_path,
+                        self.citation_style,
+                        &registry,
+                        &mut contents,
+                    )
+                    .and_then(|()| output.write_page(&path, &contents))
+                    .map_err(|cause| BuildError { path, cause })?;
                 },
 
                 Operation::Build(Entry::Resource) => {
-                    fs::copy(&source, &dest).map_err(|cause| BuildError {
-                        path: internal_path.clone(),
-                        cause,
-                    })?;
+                    output.copy_resource(&internal_path, source).map_err(
+                        |cause| BuildError {
+                            path: internal_path.clone(),
+                            cause,
+                        },
+                    )?;
                 },
 
                 Operation::Push(fragment) => {
-                    dest.push(fragment.as_str());
                     source.push(fragment.as_str());
                     internal_path.fragments.push(fragment.clone());
                 },
 
                 Operation::Pop => {
-                    dest.pop();
                     source.pop();
                     internal_path.fragments.pop();
                 },
             }
         }
 
+        for (path, contents) in self.generated_files(format, &registry) {
+            output
+                .write_page(&path, contents.as_bytes())
+                .map_err(|cause| BuildError { path, cause })?;
+        }
+
+        Ok(())
+    }
+
+    /// Gathers the registry of the site, rendering pages with the given
+    /// format only if [`Site::prerender`] is enabled.
+    fn registry<W>(&self, format: &mut W) -> Registry
+    where
+        W: render::Format + ?Sized,
+        P: Render<W>,
+    {
+        if self.prerender {
+            Registry::from_site(self, format)
+        } else {
+            Registry::from_pages(self)
+        }
+    }
+
+    /// Gathers the registry of the site like [`Site::registry`], but renders
+    /// pages concurrently, using the given number of worker threads.
+    fn registry_parallel<W>(
+        &self,
+        format: &W,
+        workers: NonZeroUsize,
+    ) -> Registry
+    where
+        W: render::Format + Clone + Send,
+        P: Render<W> + Sync,
+    {
+        if self.prerender {
+            Registry::from_site_parallel(self, format, workers)
+        } else {
+            Registry::from_pages(self)
+        }
+    }
+
+    /// Paths and contents of files generated from the site as a whole,
+    /// rather than from its entries, i.e. the sitemap, the feeds and the
+    /// search index. Information about pages is taken from the given
+    /// registry. Since these files refer to HTML pages, they are only
+    /// generated when building with a format whose extension is `html`.
+    fn generated_files<W>(
+        &self,
+        format: &W,
+        registry: &Registry,
+    ) -> Vec<(InternalPath, String)>
+    where
+        W: render::Format + ?Sized,
+    {
+        let mut files = Vec::new();
+        if format.extension() != "html" {
+            return files;
+        }
+        if let Some(sitemap) = &self.sitemap {
+            let path = InternalPath::root().append(sitemap::SITEMAP_NAME);
+            files.push((path, self.render_sitemap(sitemap, registry)));
+        }
+        for feed in &self.feeds {
+            files.push((feed.path.clone(), feed.render()));
+        }
+        if let Some(search) = &self.search {
+            files.push((search.path.clone(), search.render_json()));
+            if let (Some(path), Some(script)) =
+                (&search.script_path, search.render_script())
+            {
+                files.push((path.clone(), script));
+            }
+        }
+        files
+    }
+
+    /// Builds the site into a concrete filesystem, like [`Site::build`], but
+    /// renders pages concurrently, using the given number of worker threads.
+    /// A convenience over [`Site::build_parallel_into`] with an
+    /// [`output::Filesystem`].
+    pub fn build_parallel<W>(
+        &self,
+        format: &mut W,
+        output_dir: &mut PathBuf,
+        resource_dir: &mut PathBuf,
+        workers: NonZeroUsize,
+    ) -> Result<(), BuildError>
+    where
+        W: render::Format + Clone + Send,
+        P: Render<W> + Sync,
+    {
+        let _ = fs::remove_dir_all(&output_dir);
+        let mut output = output::Filesystem::new(output_dir.clone());
+        self.build_parallel_into(format, &mut output, resource_dir, workers)
+    }
+
+    /// Builds the site through the given output backend, like
+    /// [`Site::build_into`], but renders pages concurrently, using the given
+    /// number of worker threads, the registry of the site included (see
+    /// [`Site::prerender`]). Each worker renders with its own clone of the
+    /// given format. Since output backends are not shared between threads,
+    /// writes through the output are done one at a time.
+    ///
+    /// Directories are created before any worker starts. The output is the
+    /// same as the one of the sequential build. If errors occur, every
+    /// remaining file is still attempted, and the error of the smallest path
+    /// (in [`InternalPath`] order) is returned, so errors are reported
+    /// deterministically.
+    pub fn build_parallel_into<W, O>(
+        &self,
+        format: &mut W,
+        output: &mut O,
+        resource_dir: &mut PathBuf,
+        workers: NonZeroUsize,
+    ) -> Result<(), BuildError>
+    where
+        W: render::Format + Clone + Send,
+        P: Render<W> + Sync,
+        O: Output + Send + ?Sized,
+    {
+        let mut directories = vec![InternalPath::root()];
+        let mut jobs = Vec::new();
+        for (path, entry) in self.root.walk() {
+            match entry {
+                Entry::Directory(_) => directories.push(path),
+                Entry::Page(_) | Entry::Resource => jobs.push((path, entry)),
+            }
+        }
+        directories.sort();
+        jobs.sort_by(|(left, _), (right, _)| left.cmp(right));
+
+        for path in directories {
+            output
+                .create_dir(&path)
+                .map_err(|cause| BuildError { path, cause })?;
+        }
+
+        let citation_style = self.citation_style;
+        let registry = &self.registry_parallel(format, workers);
+        let resource_dir = &*resource_dir;
+        let output = Mutex::new(output);
+        let errors = Mutex::new(Vec::new());
+
+        for_each_parallel(&jobs, format, workers, |(path, entry), format| {
+            let (path, result) = match entry {
+                Entry::Page(page) => {
+                    let file_path = path.for_format(format);
+                    let mut contents = Vec::new();
+                    let result = build_page(
+                        *page,
+                        format,
+                        path,
+                        citation_style,
+                        registry,
+                        &mut contents,
+                    )
+                    .and_then(|()| {
+                        output
+                            .lock()
+                            .unwrap_or_else(PoisonError::into_inner)
+                            .write_page(&file_path, &contents)
+                    });
+                    (file_path, result)
+                },
+                Entry::Resource => {
+                    let source = fs_path(resource_dir, path);
+                    let result = output
+                        .lock()
+                        .unwrap_or_else(PoisonError::into_inner)
+                        .copy_resource(path, &source);
+                    (path.clone(), result)
+                },
+                Entry::Directory(_) => (path.clone(), Ok(())),
+            };
+            if let Err(cause) = result {
+                let error = BuildError { path, cause };
+                errors
+                    .lock()
+                    .unwrap_or_else(PoisonError::into_inner)
+                    .push(error);
+            }
+        });
+
+        let output =
+            output.into_inner().unwrap_or_else(PoisonError::into_inner);
+        let mut errors =
+            errors.into_inner().unwrap_or_else(PoisonError::into_inner);
+        for (path, contents) in self.generated_files(format, registry) {
+            if let Err(cause) = output.write_page(&path, contents.as_bytes()) {
+                errors.push(BuildError { path, cause });
+            }
+        }
+
+        let first_error =
+            errors.into_iter().min_by(|left, right| left.path.cmp(&right.path));
+        match first_error {
+            Some(error) => Err(error),
+            None => Ok(()),
+        }
+    }
+}
+
+/// Absolute URL of a page given its path and the base URL of the site. A path
+/// ending in [`INDEX_NAME`] becomes the URL of its directory.
+fn page_url(base: &Url, path: &InternalPath) -> Url {
+    let (fragments, is_dir) = match path.fragments.split_last() {
+        Some((last, init)) if last.as_str() == INDEX_NAME => (init, true),
+        _ => (&path.fragments[..], path.is_root()),
+    };
+    let mut url = base.clone();
+    if let Ok(mut segments) = url.path_segments_mut() {
+        segments.pop_if_empty();
+        segments.extend(fragments.iter().map(Fragment::as_str));
+        if is_dir {
+            segments.push("");
+        }
+    }
+    url
+}
+
+/// Writes text into XML, escaping special characters.
+fn escape_xml(xml: &mut String, text: &str) {
+    for ch in text.chars() {
+        match ch {
+            '&' => xml.push_str("&amp;"),
+            '<' => xml.push_str("&lt;"),
+            '>' => xml.push_str("&gt;"),
+            '"' => xml.push_str("&quot;"),
+            '\'' => xml.push_str("&apos;"),
+            _ => xml.push(ch),
+        }
+    }
+}
+
+/// Runs the given job on every item, spreading the items over the given
+/// number of worker threads. Each worker passes its own clone of the given
+/// format to the job.
+fn for_each_parallel<T, W, F>(
+    items: &[T],
+    format: &W,
+    workers: NonZeroUsize,
+    job: F,
+) where
+    T: Sync,
+    W: Clone + Send,
+    F: Fn(&T, &mut W) + Sync,
+{
+    let next_item = AtomicUsize::new(0);
+    thread::scope(|scope| {
+        for _ in 0 .. workers.get() {
+            let mut format = format.clone();
+            let next_item = &next_item;
+            let job = &job;
+            scope.spawn(move || loop {
+                let index = next_item.fetch_add(1, atomic::Ordering::Relaxed);
+                let Some(item) = items.get(index) else {
+                    break;
+                };
+                job(item, &mut format);
+            });
+        }
+    });
+}
+
+/// A writer that discards everything written to it.
+#[derive(Debug, Clone, Copy, Default)]
+struct Discard;
+
+impl fmt::Write for Discard {
+    fn write_str(&mut self, _input: &str) -> fmt::Result {
         Ok(())
     }
 }
 
+/// Converts an internal path into an OS path, relative to the given base.
+fn fs_path(base: &Path, path: &InternalPath) -> PathBuf {
+    let mut fs_path = base.to_path_buf();
+    for fragment in &path.fragments {
+        fs_path.push(fragment.as_str());
+    }
+    fs_path
+}
+
+/// Renders a page at the given path with the given format, citation style and
+/// site registry into a writer. A failed rendering is yielded as an invalid
+/// data error, whose message is made of the errors reported by components (see
+/// [`Context::fail`]).
+fn build_page<P, W, O>(
+    page: &P,
+    format: &mut W,
+    path: &InternalPath,
+    citation_style: CitationStyle,
+    registry: &Registry,
+    output: &mut O,
+) -> io::Result<()>
+where
+    W: render::Format + ?Sized,
+    P: Render<W, Kind = PageComponent> + ?Sized,
+    O: Write + ?Sized,
+{
+    let citations = Citations::new(citation_style);
+    let errors = RenderErrors::new();
+    let context = Context::new(path, &PageComponent)
+        .with_citations(&citations)
+        .with_errors(&errors)
+        .with_registry(registry);
+    let renderer = RenderAsDisplay::new(page, format, context);
+    let mut rendered = String::new();
+    if fmt::Write::write_fmt(&mut rendered, format_args!("{}", renderer))
+        .is_err()
+    {
+        let messages = errors.take();
+        let message = if messages.is_empty() {
+            String::from("formatter error")
+        } else {
+            messages.join("; ")
+        };
+        return Err(io::Error::new(io::ErrorKind::InvalidData, message));
+    }
+    output.write_all(rendered.as_bytes())
+}
+
 /// A site's directory.
 #[derive(Debug, Clone, PartialEq, Eq)]
 pub struct Directory<P>
@@ -270,7 +669,9 @@ where
     /// Inserts the given new entry at the given path, assuming it points to a
     /// directory, and then appending a `/index.html` suffix. Path cannot fully
     /// exist, and if part of it exists, it must be a sequence of
-    /// directories. Returns an `Err` if any error is found.
+    /// directories. Returns an `Err` if any error is found. When building,
+    /// the file of the page is named after the format instead (see
+    /// [`render::Format::index_name`]).
     ///
     /// # Example
     ///
@@ -287,13 +688,13 @@ where
     /// let mut directory = Directory::default();
     /// directory.try_insert_index(
     ///     InternalPath::parse("foo/bar"),
-    ///     Entry::Page(Page {
-    ///         title: String::from("Foo Bar"),
-    ///         banner: harray![],
-    ///         assets: harray![],
-    ///         body: harray![],
-    ///         children: harray![],
-    ///     }),
+    ///     Entry::Page(Page::new(
+    ///         harray![],
+    ///         String::from("Foo Bar"),
+    ///         harray![],
+    ///         harray![],
+    ///         harray![],
+    ///     )),
     /// )
     /// .unwrap();
     ///
@@ -310,7 +711,7 @@ where
         path: InternalPath,
         new_entry: Entry<P>,
     ) -> Result<(), InsertPathError> {
-        self.try_insert_path(&path.append("index.html"), new_entry)
+        self.try_insert_path(&path.append(INDEX_NAME), new_entry)
     }
 
     /// Inserts the given new entry at the given path, assuming it points to a
@@ -336,13 +737,13 @@ where
     /// let mut directory = Directory::default();
     /// directory.insert_index(
     ///     InternalPath::parse("foo/bar"),
-    ///     Entry::Page(Page {
-    ///         title: String::from("Foo Bar"),
-    ///         banner: harray![],
-    ///         assets: harray![],
-    ///         body: harray![],
-    ///         children: harray![],
-    ///     }),
+    ///     Entry::Page(Page::new(
+    ///         harray![],
+    ///         String::from("Foo Bar"),
+    ///         harray![],
+    ///         harray![],
+    ///         harray![],
+    ///     )),
     /// );
     /// assert!(
     ///     directory
@@ -356,6 +757,44 @@ where
         self.try_insert_index(path, new_entry)
             .expect("error found inserting path appending index")
     }
+
+    /// Resolves the given path into a page, taking "index" into account (see
+    /// [`InternalPath::eq_index`]): if the path points to a directory, its
+    /// `index.html` entry is used. Returns the actual path of the page
+    /// together with the page.
+    pub fn resolve_page(
+        &self,
+        path: &InternalPath,
+    ) -> Option<(InternalPath, &P)> {
+        match self.get(path)? {
+            Entry::Page(page) => Some((path.clone(), page)),
+            Entry::Directory(directory) => {
+                let index = Fragment::new(INDEX_NAME);
+                match directory.get(&index)? {
+                    Entry::Page(page) => {
+                        Some((path.clone().append(index), page))
+                    },
+                    _ => None,
+                }
+            },
+            Entry::Resource => None,
+        }
+    }
+
+    /// Iterates over all entries of this directory, recursively, yielding
+    /// their paths (relative to this directory) as well. A directory is
+    /// yielded before its entries.
+    pub fn walk(&self) -> Walk<'_, P> {
+        Walk {
+            stack: self
+                .entries
+                .iter()
+                .map(|(fragment, entry)| {
+                    (InternalPath::root().append(fragment.clone()), entry)
+                })
+                .collect(),
+        }
+    }
 }
 
 /// An entry at a directory. Parametrized so pages and directories can be
@@ -421,6 +860,33 @@ where
     }
 }
 
+/// Iterator over the entries of a directory, recursively. See
+/// [`Directory::walk`].
+#[derive(Debug)]
+pub struct Walk<'dir, P>
+where
+    P: Component<Kind = PageComponent>,
+{
+    stack: Vec<(InternalPath, &'dir Entry<P>)>,
+}
+
+impl<'dir, P> Iterator for Walk<'dir, P>
+where
+    P: Component<Kind = PageComponent>,
+{
+    type Item = (InternalPath, Entry<&'dir P, &'dir Directory<P>>);
+
+    fn next(&mut self) -> Option<Self::Item> {
+        let (path, entry) = self.stack.pop()?;
+        if let Entry::Directory(directory) = entry {
+            for (fragment, child) in &directory.entries {
+                self.stack.push((path.clone().append(fragment.clone()), child));
+            }
+        }
+        Some((path, entry.by_ref()))
+    }
+}
+
 /// An accessor over a directory.
 pub trait Accessor<D> {
     /// The output value of such access.
@@ -430,7 +896,7 @@ pub trait Accessor<D> {
     fn access(&self, directory: D) -> Self::Output;
 }
 
-impl<'this, A, D> Accessor<D> for &'this A
+impl<A, D> Accessor<D> for &A
 where
     A: Accessor<D> + ?Sized,
 {
@@ -503,9 +969,10 @@ where
     }
 }
 
+/// Tests of sites, and fixtures shared with the tests of the site modules.
 #[cfg(test)]
-mod test {
-    use super::{Directory, Entry};
+pub mod test {
+    use super::{Directory, Entry, Site};
     use crate::{
         component::{
             block::{text::Paragraph, InlineBlock},
@@ -513,9 +980,57 @@ mod test {
         },
         harray,
         location::{Fragment, InternalPath},
-        render::{DynFullComponent, FullRender},
+        render::{DynFullComponent, FullRender, Html},
+    };
+    use std::{
+        collections::BTreeMap,
+        fs,
+        num::NonZeroUsize,
+        path::{Path, PathBuf},
     };
 
+    /// Creates a page with the given title, a plain banner and a single
+    /// paragraph.
+    pub fn make_page(
+        title: &'static str,
+    ) -> DynFullComponent<'static, PageComponent> {
+        Page::new(
+            InlineBlock("Banner"),
+            String::from(title),
+            harray![],
+            Paragraph("Hello"),
+            harray![],
+        )
+        .into_dyn()
+    }
+
+    /// Creates a site with an index page (see [`make_page`]) in each of the
+    /// given directories, given as pairs of path and title.
+    pub fn make_site(
+        pages: &[(&str, &'static str)],
+    ) -> Site<DynFullComponent<'static, PageComponent>> {
+        let mut site = Site::default();
+        for &(path, title) in pages {
+            site.root.insert_index(
+                InternalPath::parse(path),
+                Entry::Page(make_page(title)),
+            );
+        }
+        site
+    }
+
+    /// Yields a path in the temporary directory unique to this process,
+    /// removing whatever is at it.
+    pub fn temp_dir(name: &str) -> PathBuf {
+        let path = std::env::temp_dir().join(format!(
+            "codedpedia-{}-{}",
+            name,
+            std::process::id()
+        ));
+        let _ = fs::remove_dir_all(&path);
+        path
+    }
+
     fn make_directory() -> Directory<DynFullComponent<'static, PageComponent>> {
         Directory {
             entries: [
@@ -526,13 +1041,13 @@ mod test {
                             (
                                 Fragment::new("apple"),
                                 Entry::Page(
-                                    Page {
-                                        banner: InlineBlock("My Banner"),
-                                        title: String::from("My Page"),
-                                        assets: harray![],
-                                        body: Paragraph("hello"),
-                                        children: harray![],
-                                    }
+                                    Page::new(
+                                        InlineBlock("My Banner"),
+                                        String::from("My Page"),
+                                        harray![],
+                                        Paragraph("hello"),
+                                        harray![],
+                                    )
                                     .into_dyn(),
                                 ),
                             ),
@@ -582,13 +1097,13 @@ mod test {
         dir.insert_index(
             InternalPath::parse("new-fruit/morgot"),
             Entry::Page(
-                Page {
-                    banner: InlineBlock("My Banner"),
-                    title: String::from("My Page"),
-                    assets: harray![],
-                    body: Paragraph("hello"),
-                    children: harray![],
-                }
+                Page::new(
+                    InlineBlock("My Banner"),
+                    String::from("My Page"),
+                    harray![],
+                    Paragraph("hello"),
+                    harray![],
+                )
                 .into_dyn(),
             ),
         );
@@ -597,4 +1112,71 @@ mod test {
             .unwrap()
             .is_page());
     }
+
+    fn read_tree(root: &Path) -> BTreeMap<PathBuf, Vec<u8>> {
+        let mut files = BTreeMap::new();
+        let mut stack = vec![root.to_path_buf()];
+        while let Some(dir) = stack.pop() {
+            for entry in fs::read_dir(dir).unwrap() {
+                let path = entry.unwrap().path();
+                if path.is_dir() {
+                    stack.push(path);
+                } else {
+                    let contents = fs::read(&path).unwrap();
+                    files.insert(
+                        path.strip_prefix(root).unwrap().to_path_buf(),
+                        contents,
+                    );
+                }
+            }
+        }
+        files
+    }
+
+    #[test]
+    fn parallel_build_same_as_sequential() {
+        let resources = temp_dir("parallel-resources");
+        fs::create_dir_all(resources.join("avocado")).unwrap();
+        fs::write(resources.join("avocado/audio.ogg"), b"ogg").unwrap();
+        fs::write(resources.join("pineapple"), b"pineapple").unwrap();
+
+        let site = Site { root: make_directory(), ..Site::default() };
+        let mut sequential = temp_dir("parallel-sequential");
+        site.build(&mut Html, &mut sequential, &mut resources.clone()).unwrap();
+        let parallel = temp_dir("parallel-parallel");
+        site.build_parallel(
+            &mut Html,
+            &mut parallel.clone(),
+            &mut resources.clone(),
+            NonZeroUsize::new(3).unwrap(),
+        )
+        .unwrap();
+
+        let expected = read_tree(&sequential);
+        assert_eq!(expected.len(), 3);
+        assert_eq!(read_tree(&parallel), expected);
+
+        for dir in [resources, sequential, parallel] {
+            fs::remove_dir_all(dir).unwrap();
+        }
+    }
+
+    #[test]
+    fn parallel_build_first_error() {
+        let resources = temp_dir("parallel-missing");
+        let output = temp_dir("parallel-missing-output");
+
+        let site = Site { root: make_directory(), ..Site::default() };
+        let error = site
+            .build_parallel(
+                &mut Html,
+                &mut output.clone(),
+                &mut resources.clone(),
+                NonZeroUsize::new(2).unwrap(),
+            )
+            .unwrap_err();
+        assert_eq!(error.path, InternalPath::parse("avocado/audio.ogg"));
+
+        fs::remove_dir_all(output).unwrap();
+    }
 }
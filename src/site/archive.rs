@@ -0,0 +1,367 @@
+//! This module provides archive output backends, which build a whole site into
+//! a single archive, without a staging directory. Supported archives are
+//! uncompressed tar (ustar) and zip with stored (i.e. uncompressed) entries.
+//!
+//! Entries are written with a zeroed modification time, so archives of the
+//! same site are reproducible.
+
+use super::{output::Output, BuildError, Site};
+use crate::{
+    component::{page::PageComponent, Component},
+    location::InternalPath,
+    render::{self, Render},
+};
+use std::{
+    io::{self, Write},
+    path::PathBuf,
+};
+
+/// Output backend writing an uncompressed tar archive into a writer. The
+/// archive must be finished with [`Tar::finish`].
+#[derive(Debug)]
+pub struct Tar<O>
+where
+    O: Write,
+{
+    output: O,
+}
+
+impl<O> Tar<O>
+where
+    O: Write,
+{
+    /// Creates a tar backend writing into the given output.
+    pub fn new(output: O) -> Self {
+        Self { output }
+    }
+
+    /// Writes the end of the archive, returning the output.
+    pub fn finish(mut self) -> io::Result<O> {
+        self.output.write_all(&[0; 2 * TAR_BLOCK])?;
+        self.output.flush()?;
+        Ok(self.output)
+    }
+
+    fn write_entry(
+        &mut self,
+        name: &str,
+        kind: u8,
+        mode: u32,
+        contents: &[u8],
+    ) -> io::Result<()> {
+        let mut header = [0; TAR_BLOCK];
+        let (prefix, name) = split_tar_name(name)?;
+        header[.. name.len()].copy_from_slice(name.as_bytes());
+        write_octal(&mut header[100 .. 108], u64::from(mode));
+        write_octal(&mut header[108 .. 116], 0);
+        write_octal(&mut header[116 .. 124], 0);
+        write_octal(&mut header[124 .. 136], contents.len() as u64);
+        write_octal(&mut header[136 .. 148], 0);
+        header[148 .. 156].fill(b' ');
+        header[156] = kind;
+        header[257 .. 263].copy_from_slice(b"ustar\0");
+        header[263 .. 265].copy_from_slice(b"00");
+        header[345 .. 345 + prefix.len()].copy_from_slice(prefix.as_bytes());
+        let checksum = header.iter().map(|&byte| u64::from(byte)).sum();
+        write_octal(&mut header[148 .. 155], checksum);
+
+        self.output.write_all(&header)?;
+        self.output.write_all(contents)?;
+        let padding = (TAR_BLOCK - contents.len() % TAR_BLOCK) % TAR_BLOCK;
+        self.output.write_all(&[0; TAR_BLOCK][.. padding])
+    }
+}
+
+impl<O> Output for Tar<O>
+where
+    O: Write,
+{
+    fn create_dir(&mut self, path: &InternalPath) -> io::Result<()> {
+        if path.is_root() {
+            return Ok(());
+        }
+        self.write_entry(&format!("{}/", path), b'5', 0o755, &[])
+    }
+
+    fn write_page(
+        &mut self,
+        path: &InternalPath,
+        contents: &[u8],
+    ) -> io::Result<()> {
+        self.write_entry(&path.to_string(), b'0', 0o644, contents)
+    }
+
+    fn write_resource(
+        &mut self,
+        path: &InternalPath,
+        contents: &[u8],
+    ) -> io::Result<()> {
+        self.write_entry(&path.to_string(), b'0', 0o644, contents)
+    }
+}
+
+/// Output backend writing a zip archive with stored entries into a writer.
+/// The archive must be finished with [`Zip::finish`]. Archives larger than
+/// 4 GiB or with more than 65535 entries are not supported.
+#[derive(Debug)]
+pub struct Zip<O>
+where
+    O: Write,
+{
+    output: O,
+    offset: u32,
+    central_directory: Vec<u8>,
+    entries: u16,
+}
+
+impl<O> Zip<O>
+where
+    O: Write,
+{
+    /// Creates a zip backend writing into the given output.
+    pub fn new(output: O) -> Self {
+        Self { output, offset: 0, central_directory: Vec::new(), entries: 0 }
+    }
+
+    /// Writes the central directory, which ends the archive, returning the
+    /// output.
+    pub fn finish(mut self) -> io::Result<O> {
+        let size = u32::try_from(self.central_directory.len())
+            .map_err(|_| too_large())?;
+        self.output.write_all(&self.central_directory)?;
+
+        let mut record = Vec::with_capacity(22);
+        record.extend_from_slice(&0x06054b50u32.to_le_bytes());
+        record.extend_from_slice(&[0; 4]);
+        record.extend_from_slice(&self.entries.to_le_bytes());
+        record.extend_from_slice(&self.entries.to_le_bytes());
+        record.extend_from_slice(&size.to_le_bytes());
+        record.extend_from_slice(&self.offset.to_le_bytes());
+        record.extend_from_slice(&[0; 2]);
+        self.output.write_all(&record)?;
+        self.output.flush()?;
+        Ok(self.output)
+    }
+
+    fn write_entry(&mut self, name: &str, contents: &[u8]) -> io::Result<()> {
+        let name_len = u16::try_from(name.len()).map_err(|_| too_large())?;
+        let size = u32::try_from(contents.len()).map_err(|_| too_large())?;
+        let entries = self.entries.checked_add(1).ok_or_else(too_large)?;
+        let is_dir = name.ends_with('/');
+        let crc = crc32(contents);
+
+        // Version needed, flags (UTF-8 names), method (stored), time, date
+        // (1980-01-01), CRC, sizes, name length and extra field length.
+        let mut common = Vec::with_capacity(26);
+        common.extend_from_slice(&20u16.to_le_bytes());
+        common.extend_from_slice(&0x0800u16.to_le_bytes());
+        common.extend_from_slice(&0u16.to_le_bytes());
+        common.extend_from_slice(&0u16.to_le_bytes());
+        common.extend_from_slice(&0x0021u16.to_le_bytes());
+        common.extend_from_slice(&crc.to_le_bytes());
+        common.extend_from_slice(&size.to_le_bytes());
+        common.extend_from_slice(&size.to_le_bytes());
+        common.extend_from_slice(&name_len.to_le_bytes());
+        common.extend_from_slice(&0u16.to_le_bytes());
+
+        let mut local = Vec::with_capacity(30 + name.len());
+        local.extend_from_slice(&0x04034b50u32.to_le_bytes());
+        local.extend_from_slice(&common);
+        local.extend_from_slice(name.as_bytes());
+
+        let next_offset = u32::try_from(local.len())
+            .ok()
+            .and_then(|len| self.offset.checked_add(len))
+            .and_then(|offset| offset.checked_add(size))
+            .ok_or_else(too_large)?;
+
+        let central = &mut self.central_directory;
+        central.extend_from_slice(&0x02014b50u32.to_le_bytes());
+        central.extend_from_slice(&20u16.to_le_bytes());
+        central.extend_from_slice(&common);
+        central.extend_from_slice(&[0; 6]);
+        let attributes: u32 = if is_dir { 0x10 } else { 0 };
+        central.extend_from_slice(&attributes.to_le_bytes());
+        central.extend_from_slice(&self.offset.to_le_bytes());
+        central.extend_from_slice(name.as_bytes());
+
+        self.output.write_all(&local)?;
+        self.output.write_all(contents)?;
+        self.offset = next_offset;
+        self.entries = entries;
+        Ok(())
+    }
+}
+
+impl<O> Output for Zip<O>
+where
+    O: Write,
+{
+    fn create_dir(&mut self, path: &InternalPath) -> io::Result<()> {
+        if path.is_root() {
+            return Ok(());
+        }
+        self.write_entry(&format!("{}/", path), &[])
+    }
+
+    fn write_page(
+        &mut self,
+        path: &InternalPath,
+        contents: &[u8],
+    ) -> io::Result<()> {
+        self.write_entry(&path.to_string(), contents)
+    }
+
+    fn write_resource(
+        &mut self,
+        path: &InternalPath,
+        contents: &[u8],
+    ) -> io::Result<()> {
+        self.write_entry(&path.to_string(), contents)
+    }
+}
+
+impl<P> Site<P>
+where
+    P: Component<Kind = PageComponent>,
+{
+    /// Builds the site as an uncompressed tar archive written into the given
+    /// output, given a render format and a resource directory path. Returns
+    /// the output. A convenience over [`Site::build_into`] with a [`Tar`].
+    pub fn build_tar<W, O>(
+        &self,
+        format: &mut W,
+        output: O,
+        resource_dir: &mut PathBuf,
+    ) -> Result<O, BuildError>
+    where
+        W: render::Format + ?Sized,
+        P: Render<W>,
+        O: Write,
+    {
+        let mut tar = Tar::new(output);
+        self.build_into(format, &mut tar, resource_dir)?;
+        tar.finish()
+            .map_err(|cause| BuildError { path: InternalPath::root(), cause })
+    }
+
+    /// Builds the site as a zip archive with stored entries written into the
+    /// given output, given a render format and a resource directory path.
+    /// Returns the output. A convenience over [`Site::build_into`] with a
+    /// [`Zip`].
+    pub fn build_zip<W, O>(
+        &self,
+        format: &mut W,
+        output: O,
+        resource_dir: &mut PathBuf,
+    ) -> Result<O, BuildError>
+    where
+        W: render::Format + ?Sized,
+        P: Render<W>,
+        O: Write,
+    {
+        let mut zip = Zip::new(output);
+        self.build_into(format, &mut zip, resource_dir)?;
+        zip.finish()
+            .map_err(|cause| BuildError { path: InternalPath::root(), cause })
+    }
+}
+
+const TAR_BLOCK: usize = 512;
+
+/// Splits a name into ustar's prefix and name fields, at a slash.
+fn split_tar_name(name: &str) -> io::Result<(&str, &str)> {
+    if name.len() <= 100 {
+        return Ok(("", name));
+    }
+    let trimmed = name.strip_suffix('/').unwrap_or(name);
+    trimmed
+        .char_indices()
+        .filter(|&(_, ch)| ch == '/')
+        .map(|(index, _)| (&name[.. index], &name[index + 1 ..]))
+        .find(|(prefix, name)| prefix.len() <= 155 && name.len() <= 100)
+        .ok_or_else(|| {
+            io::Error::new(io::ErrorKind::InvalidInput, "path too long for tar")
+        })
+}
+
+/// Writes a zero-padded, NUL-terminated octal number into a tar field.
+fn write_octal(field: &mut [u8], number: u64) {
+    let width = field.len() - 1;
+    let digits = format!("{:0width$o}", number, width = width);
+    let start = digits.len().saturating_sub(width);
+    field[.. width].copy_from_slice(&digits.as_bytes()[start ..]);
+    field[width] = 0;
+}
+
+/// Computes the CRC-32 (IEEE) of the given contents.
+fn crc32(contents: &[u8]) -> u32 {
+    let mut crc = !0u32;
+    for &byte in contents {
+        crc ^= u32::from(byte);
+        for _ in 0 .. 8 {
+            let mask = (crc & 1).wrapping_neg();
+            crc = (crc >> 1) ^ (0xedb88320 & mask);
+        }
+    }
+    !crc
+}
+
+fn too_large() -> io::Error {
+    io::Error::new(io::ErrorKind::InvalidInput, "too large for zip archive")
+}
+
+#[cfg(test)]
+mod test {
+    use super::crc32;
+    use crate::{
+        location::InternalPath,
+        render::Html,
+        site::{test::make_site, Entry},
+    };
+    use std::path::PathBuf;
+
+    #[test]
+    fn crc32_check_value() {
+        assert_eq!(crc32(b"123456789"), 0xcbf43926);
+    }
+
+    #[test]
+    fn tar_entries() {
+        let site = make_site(&[("foo", "Foo")]);
+        let archive =
+            site.build_tar(&mut Html, Vec::new(), &mut PathBuf::new()).unwrap();
+
+        assert_eq!(archive.len() % 512, 0);
+        assert_eq!(&archive[.. 5], b"foo/\0");
+        assert_eq!(archive[156], b'5');
+        assert_eq!(&archive[512 .. 527], b"foo/index.html\0");
+        assert_eq!(&archive[512 + 257 .. 512 + 263], b"ustar\0");
+        assert!(archive[1024 ..].starts_with(b"<!DOCTYPE html>"));
+        assert!(archive[archive.len() - 1024 ..].iter().all(|&byte| byte == 0));
+    }
+
+    #[test]
+    fn zip_entries() {
+        let site = make_site(&[("foo", "Foo")]);
+        let archive =
+            site.build_zip(&mut Html, Vec::new(), &mut PathBuf::new()).unwrap();
+
+        assert_eq!(&archive[.. 4], b"PK\x03\x04");
+        assert_eq!(&archive[30 .. 34], b"foo/");
+        let end = &archive[archive.len() - 22 ..];
+        assert_eq!(&end[.. 4], b"PK\x05\x06");
+        assert_eq!(u16::from_le_bytes([end[10], end[11]]), 2);
+    }
+
+    #[test]
+    fn missing_resource_reported() {
+        let mut site = make_site(&[("foo", "Foo")]);
+        site.root
+            .insert_path(&InternalPath::parse("foo/main.css"), Entry::Resource);
+        let error = site
+            .build_tar(&mut Html, Vec::new(), &mut PathBuf::from("/nowhere"))
+            .unwrap_err();
+        assert_eq!(error.path, InternalPath::parse("foo/main.css"));
+    }
+}
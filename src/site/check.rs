@@ -0,0 +1,299 @@
+//! This module provides a checker for internal links of a site: it finds
+//! internal locations that point nowhere, or to IDs that do not exist.
+
+use super::{Directory, Discard, Entry, Site};
+use crate::{
+    component::page::PageComponent,
+    location::{Id, InternalLoc, InternalPath},
+    render::{Context, Html, Observer, Render, Renderer},
+};
+use std::{
+    cell::RefCell,
+    collections::{HashMap, HashSet},
+    error::Error,
+    fmt,
+};
+
+/// Reason why a link is broken.
+#[derive(Debug, Clone, Copy, PartialEq, Eq, PartialOrd, Ord, Hash)]
+pub enum BrokenReason {
+    /// No entry exists at the target path.
+    NotFound,
+    /// The target path exists, but it is not a page, and the link requires
+    /// an ID.
+    NotAPage,
+    /// The target page exists, but no section in it has the target ID.
+    MissingId,
+}
+
+impl fmt::Display for BrokenReason {
+    fn fmt(&self, fmtr: &mut fmt::Formatter) -> fmt::Result {
+        match self {
+            Self::NotFound => write!(fmtr, "no such page or resource"),
+            Self::NotAPage => write!(fmtr, "IDs can only refer to pages"),
+            Self::MissingId => write!(fmtr, "no such ID in the page"),
+        }
+    }
+}
+
+/// A broken internal link found in a site.
+#[derive(Debug, Clone, PartialEq, Eq, PartialOrd, Ord, Hash)]
+pub struct BrokenLink {
+    /// Path of the page containing the link.
+    pub source: InternalPath,
+    /// The location the link points to.
+    pub target: InternalLoc,
+    /// Why the link is broken.
+    pub reason: BrokenReason,
+}
+
+impl fmt::Display for BrokenLink {
+    fn fmt(&self, fmtr: &mut fmt::Formatter) -> fmt::Result {
+        write!(
+            fmtr,
+            "{}: broken link to \"{}\": {}",
+            self.source, self.target, self.reason
+        )
+    }
+}
+
+/// Error yielded when checking a site finds problems. All problems found are
+/// reported at once.
+#[derive(Debug, Clone, PartialEq, Eq, Default)]
+pub struct CheckError {
+    /// Pages that failed rendering, thus they could not be checked.
+    pub unrendered: Vec<InternalPath>,
+    /// Broken links, sorted by source page.
+    pub broken: Vec<BrokenLink>,
+}
+
+impl fmt::Display for CheckError {
+    fn fmt(&self, fmtr: &mut fmt::Formatter) -> fmt::Result {
+        let unrendered = self
+            .unrendered
+            .iter()
+            .map(|path| format!("{}: failed to render page", path));
+        let broken = self.broken.iter().map(ToString::to_string);
+
+        let mut first = true;
+        for line in unrendered.chain(broken) {
+            if first {
+                first = false;
+            } else {
+                fmtr.write_str("\n")?;
+            }
+            fmtr.write_str(&line)?;
+        }
+
+        Ok(())
+    }
+}
+
+impl Error for CheckError {}
+
+/// Observer collecting referenced locations and defined IDs of a page.
+#[derive(Debug, Default)]
+struct Collector {
+    references: RefCell<Vec<InternalLoc>>,
+    ids: RefCell<HashSet<Id>>,
+}
+
+impl Observer for Collector {
+    fn reference(&self, location: &InternalLoc) {
+        self.references.borrow_mut().push(location.clone());
+    }
+
+    fn define(&self, id: &Id) {
+        self.ids.borrow_mut().insert(id.clone());
+    }
+}
+
+impl<P> Site<P>
+where
+    P: Render<Html, Kind = PageComponent>,
+{
+    /// Checks all internal locations referenced by pages of this site, e.g. by
+    /// links, images, audio, stylesheets and scripts. A location is valid if
+    /// its path resolves to an entry of the site (taking "index" into account,
+    /// see [`Directory::resolve_page`]) and, if it has an ID, the ID is defined
+    /// by a section of the target page.
+    ///
+    /// Pages are analyzed by rendering them as HTML and observing the
+    /// rendering, nothing is written.
+    pub fn check_links(&self) -> Result<(), CheckError> {
+        let mut error = CheckError::default();
+        let mut collected = HashMap::new();
+
+        for (path, entry) in self.root.walk() {
+            if let Entry::Page(page) = entry {
+                let collector = Collector::default();
+                let context = Context::new(&path, &PageComponent)
+                    .with_observer(&collector);
+                let mut format = Html;
+                let mut target = Discard;
+                let mut renderer = Renderer::new(&mut format, &mut target);
+                if page.render(&mut renderer, context).is_err() {
+                    error.unrendered.push(path.clone());
+                }
+                collected.insert(path, collector);
+            }
+        }
+
+        for (source, collector) in &collected {
+            for target in collector.references.borrow().iter() {
+                let reason = match &target.id {
+                    Some(id) => check_id(&self.root, &collected, target, id),
+                    None => check_path(&self.root, &target.path),
+                };
+                if let Some(reason) = reason {
+                    error.broken.push(BrokenLink {
+                        source: source.clone(),
+                        target: target.clone(),
+                        reason,
+                    });
+                }
+            }
+        }
+
+        if error.unrendered.is_empty() && error.broken.is_empty() {
+            Ok(())
+        } else {
+            error.unrendered.sort();
+            error.broken.sort();
+            error.broken.dedup();
+            Err(error)
+        }
+    }
+}
+
+fn check_path<P>(
+    root: &Directory<P>,
+    path: &InternalPath,
+) -> Option<BrokenReason>
+where
+    P: Render<Html, Kind = PageComponent>,
+{
+    match root.get(path) {
+        Some(Entry::Directory(_)) if root.resolve_page(path).is_none() => {
+            Some(BrokenReason::NotFound)
+        },
+        Some(_) => None,
+        None => Some(BrokenReason::NotFound),
+    }
+}
+
+fn check_id<P>(
+    root: &Directory<P>,
+    collected: &HashMap<InternalPath, Collector>,
+    target: &InternalLoc,
+    id: &Id,
+) -> Option<BrokenReason>
+where
+    P: Render<Html, Kind = PageComponent>,
+{
+    match root.resolve_page(&target.path) {
+        Some((path, _)) => {
+            let defined = collected
+                .get(&path)
+                .is_some_and(|collector| collector.ids.borrow().contains(id));
+            if defined {
+                None
+            } else {
+                Some(BrokenReason::MissingId)
+            }
+        },
+        None => match root.get(&target.path) {
+            Some(_) => Some(BrokenReason::NotAPage),
+            None => Some(BrokenReason::NotFound),
+        },
+    }
+}
+
+#[cfg(test)]
+mod test {
+    use super::BrokenReason;
+    use crate::{
+        component::{
+            asset::Stylesheet,
+            block::{text::Paragraph, InlineBlock},
+            inline::text::Link,
+            page::{Page, PageComponent},
+            section::Section,
+        },
+        harray,
+        location::{Id, InternalPath, Location},
+        render::{DynFullComponent, FullRender},
+        site::{Entry, Site},
+    };
+
+    fn make_page(
+        first: &'static str,
+        second: &'static str,
+    ) -> DynFullComponent<'static, PageComponent> {
+        Page::new(
+            InlineBlock(Link {
+                target: "Home",
+                location: Location::internal(""),
+            }),
+            String::from("Page"),
+            [Stylesheet { location: Location::internal("styles/main.css") }],
+            Paragraph(harray![
+                Link { target: "First", location: Location::internal(first) },
+                Link { target: "Second", location: Location::internal(second) },
+            ]),
+            harray![Section {
+                title: "Section",
+                id: Some(Id::new("section")),
+                body: Paragraph("Hello"),
+                children: harray![],
+            }],
+        )
+        .into_dyn()
+    }
+
+    fn make_site(
+        first: &'static str,
+        second: &'static str,
+    ) -> Site<DynFullComponent<'static, PageComponent>> {
+        let mut site = Site::default();
+        site.root.insert_index(
+            InternalPath::root(),
+            Entry::Page(make_page(first, second)),
+        );
+        site.root.insert_index(
+            InternalPath::parse("foo"),
+            Entry::Page(make_page("", "foo#section")),
+        );
+        site.root.insert_path(
+            &InternalPath::parse("styles/main.css"),
+            Entry::Resource,
+        );
+        site
+    }
+
+    #[test]
+    fn valid_links() {
+        let site = make_site("foo", "foo/index.html#section");
+        site.check_links().unwrap();
+    }
+
+    #[test]
+    fn missing_page() {
+        let site = make_site("bar", "foo#section");
+        let error = site.check_links().unwrap_err();
+        assert_eq!(error.broken.len(), 1);
+        assert_eq!(error.broken[0].source, InternalPath::parse("index.html"));
+        assert_eq!(error.broken[0].reason, BrokenReason::NotFound);
+    }
+
+    #[test]
+    fn all_errors_reported() {
+        let site = make_site("foo#nope", "styles/main.css#section");
+        let error = site.check_links().unwrap_err();
+        let reasons =
+            error.broken.iter().map(|link| link.reason).collect::<Vec<_>>();
+        assert_eq!(reasons.len(), 2);
+        assert!(reasons.contains(&BrokenReason::MissingId));
+        assert!(reasons.contains(&BrokenReason::NotAPage));
+    }
+}
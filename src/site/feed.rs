@@ -0,0 +1,481 @@
+//! This module provides generation of Atom feeds (RFC 4287) for a selection of
+//! pages of a site, e.g. recently changed articles.
+
+use super::{build_page, escape_xml, page_url, registry::Registry, Site};
+use crate::{
+    component::page::PageComponent,
+    location::InternalPath,
+    render::{Render, Text},
+};
+use std::{error::Error, fmt};
+use url::Url;
+
+/// Default maximum length of entry summaries, in characters.
+pub const DEFAULT_SUMMARY_LENGTH: usize = 280;
+
+/// Error when a page cannot be added to a feed.
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub enum FeedError {
+    /// No page exists at the given path.
+    NotFound(InternalPath),
+    /// The page failed to render as text, with the given message.
+    Render(InternalPath, String),
+    /// No title is given nor registered for the page.
+    Untitled(InternalPath),
+    /// No date of update is given nor found in the metadata of the page.
+    Undated(InternalPath),
+}
+
+impl fmt::Display for FeedError {
+    fn fmt(&self, fmtr: &mut fmt::Formatter) -> fmt::Result {
+        match self {
+            Self::NotFound(path) => write!(fmtr, "{}: no such page", path),
+            Self::Render(path, message) => write!(
+                fmtr,
+                "{}: failed to render page as text: {}",
+                path, message
+            ),
+            Self::Untitled(path) => write!(fmtr, "{}: page has no title", path),
+            Self::Undated(path) => {
+                write!(fmtr, "{}: page has no date of update", path)
+            },
+        }
+    }
+}
+
+impl Error for FeedError {}
+
+/// An entry of a feed, corresponding to a page.
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct FeedEntry {
+    /// Path of the page.
+    pub path: InternalPath,
+    /// Title of the entry.
+    pub title: String,
+    /// Date of the last update, in RFC 3339 format, e.g.
+    /// `2024-01-31T12:00:00Z`.
+    pub updated: String,
+    /// Summary of the page, as plain text.
+    pub summary: String,
+}
+
+/// An Atom feed of a site.
+///
+/// # Example
+///
+/// ```
+/// use codedpedia::{
+///     component::{
+///         block::text::Paragraph,
+///         page::{Page, PageMetadata},
+///     },
+///     harray,
+///     location::InternalPath,
+///     render::{DynFullComponent, FullRender, Text},
+///     site::{feed::Feed, registry::Registry, Entry, Site},
+/// };
+/// use url::Url;
+///
+/// let mut site = Site::default();
+/// site.root.insert_index(
+///     InternalPath::parse("foo"),
+///     Entry::Page(
+///         Page {
+///             metadata: Some(PageMetadata {
+///                 updated: Some(String::from("2024-01-31")),
+///                 ..PageMetadata::default()
+///             }),
+///             ..Page::new(
+///                 harray![],
+///                 String::from("Foo"),
+///                 harray![],
+///                 Paragraph("Foo is a metavariable."),
+///                 harray![],
+///             )
+///         }
+///         .into_dyn(),
+///     ),
+/// );
+/// let registry = Registry::from_site(&site, &mut Text::default());
+///
+/// let mut feed = Feed::new(
+///     Url::parse("https://example.com/").unwrap(),
+///     String::from("Recent changes"),
+///     String::from("Editors"),
+///     String::from("2024-01-31T12:00:00Z"),
+/// );
+/// feed.add_page(&site, &registry, InternalPath::parse("foo"), None, None)
+///     .unwrap();
+/// assert_eq!(feed.entries[0].title, "Foo");
+/// assert_eq!(feed.entries[0].updated, "2024-01-31T00:00:00Z");
+/// assert_eq!(feed.entries[0].summary, "Foo is a metavariable.");
+///
+/// site.feeds.push(feed);
+/// ```
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct Feed {
+    /// Absolute URL where the root of the site is deployed.
+    pub base: Url,
+    /// Path of the feed file in the build output, `atom.xml` by default.
+    pub path: InternalPath,
+    /// Title of the feed.
+    pub title: String,
+    /// Name of the author of the feed.
+    pub author: String,
+    /// Date of the last update of the feed, in RFC 3339 format.
+    pub updated: String,
+    /// Maximum length of summaries generated by [`Feed::add_page`], in
+    /// characters.
+    pub summary_length: usize,
+    /// Entries of the feed, in the order they are written.
+    pub entries: Vec<FeedEntry>,
+}
+
+impl Feed {
+    /// Creates a feed without entries, written at `atom.xml`.
+    pub fn new(
+        base: Url,
+        title: String,
+        author: String,
+        updated: String,
+    ) -> Self {
+        Self {
+            base,
+            path: InternalPath::parse("atom.xml"),
+            title,
+            author,
+            updated,
+            summary_length: DEFAULT_SUMMARY_LENGTH,
+            entries: Vec::new(),
+        }
+    }
+
+    /// Adds an entry for the page at the given path of the site (see
+    /// [`super::Directory::resolve_page`]), given the registry of the site,
+    /// gathered by rendering its pages (see [`Registry::from_site`]).
+    ///
+    /// The title of the entry is the registered title of the page, and its
+    /// date of update is the update date of the page metadata, or else its
+    /// creation date (see [`atom_date`]), unless overridden by the given title
+    /// and date. The summary is derived from the text rendering of the page,
+    /// done like in a build of the site: whatever precedes the title (e.g. a
+    /// banner) is skipped, whitespace is collapsed, and the result is
+    /// truncated at a word boundary.
+    pub fn add_page<P>(
+        &mut self,
+        site: &Site<P>,
+        registry: &Registry,
+        path: InternalPath,
+        title: Option<String>,
+        updated: Option<String>,
+    ) -> Result<(), FeedError>
+    where
+        P: Render<Text, Kind = PageComponent>,
+    {
+        let (path, page) = site
+            .root
+            .resolve_page(&path)
+            .ok_or_else(|| FeedError::NotFound(path.clone()))?;
+
+        let page_title = registry.title(&path);
+        let title = title
+            .or_else(|| page_title.map(str::to_owned))
+            .ok_or_else(|| FeedError::Untitled(path.clone()))?;
+        let updated = updated
+            .or_else(|| {
+                let metadata = registry.metadata(&path)?;
+                let date =
+                    metadata.updated.as_ref().or(metadata.created.as_ref());
+                date.map(|date| atom_date(date))
+            })
+            .ok_or_else(|| FeedError::Undated(path.clone()))?;
+
+        let mut text = Vec::new();
+        let mut format = Text::default();
+        let style = site.citation_style;
+        build_page(page, &mut format, &path, style, registry, &mut text)
+            .map_err(|error| {
+                FeedError::Render(path.clone(), error.to_string())
+            })?;
+
+        let text = String::from_utf8_lossy(&text);
+        let skipped_title = page_title.unwrap_or(&title);
+        let summary = summarize(&text, skipped_title, self.summary_length);
+        self.entries.push(FeedEntry { path, title, updated, summary });
+        Ok(())
+    }
+
+    /// Generates the contents of the feed.
+    pub fn render(&self) -> String {
+        let mut xml = String::from(
+            "<?xml version=\"1.0\" encoding=\"utf-8\"?>\n<feed \
+             xmlns=\"http://www.w3.org/2005/Atom\">\n",
+        );
+        let home = page_url(&self.base, &InternalPath::root());
+        let this = page_url(&self.base, &self.path);
+        push_element(&mut xml, "title", &self.title);
+        push_element(&mut xml, "id", home.as_str());
+        push_link(&mut xml, None, home.as_str());
+        push_link(&mut xml, Some("self"), this.as_str());
+        push_element(&mut xml, "updated", &self.updated);
+        xml.push_str("<author>");
+        push_element(&mut xml, "name", &self.author);
+        xml.push_str("</author>\n");
+
+        for entry in &self.entries {
+            let url = page_url(&self.base, &entry.path);
+            xml.push_str("<entry>\n");
+            push_element(&mut xml, "title", &entry.title);
+            push_element(&mut xml, "id", url.as_str());
+            push_link(&mut xml, None, url.as_str());
+            push_element(&mut xml, "updated", &entry.updated);
+            push_element(&mut xml, "summary", &entry.summary);
+            xml.push_str("</entry>\n");
+        }
+
+        xml.push_str("</feed>\n");
+        xml
+    }
+}
+
+fn push_element(xml: &mut String, name: &str, text: &str) {
+    xml.push('<');
+    xml.push_str(name);
+    xml.push('>');
+    escape_xml(xml, text);
+    xml.push_str("</");
+    xml.push_str(name);
+    xml.push_str(">\n");
+}
+
+fn push_link(xml: &mut String, rel: Option<&str>, href: &str) {
+    xml.push_str("<link");
+    if let Some(rel) = rel {
+        xml.push_str(" rel=\"");
+        escape_xml(xml, rel);
+        xml.push('"');
+    }
+    xml.push_str(" href=\"");
+    escape_xml(xml, href);
+    xml.push_str("\"/>\n");
+}
+
+/// Converts a date in W3C Datetime format, as in page metadata (see
+/// [`PageMetadata`](crate::component::page::PageMetadata)), into the RFC 3339
+/// format of Atom, completing the missing parts: e.g. `2024-01` becomes
+/// `2024-01-01T00:00:00Z`, and `2024-01-31T12:00+01:00` becomes
+/// `2024-01-31T12:00:00+01:00`.
+pub fn atom_date(date: &str) -> String {
+    let Some((day, time)) = date.split_once('T') else {
+        let mut date = date.to_owned();
+        for _ in date.split('-').count() .. 3 {
+            date.push_str("-01");
+        }
+        date.push_str("T00:00:00Z");
+        return date;
+    };
+    let zone_start = time.find(['Z', '+', '-']).unwrap_or(time.len());
+    let (clock, zone) = time.split_at(zone_start);
+    let seconds = if clock.matches(':').count() < 2 { ":00" } else { "" };
+    format!("{}T{}{}{}", day, clock, seconds, zone)
+}
+
+/// Derives a summary from the text rendering of a page.
+fn summarize(text: &str, title: &str, max_length: usize) -> String {
+    let blocks = text
+        .split("\n\n")
+        .map(str::trim)
+        .filter(|block| !block.is_empty())
+        .collect::<Vec<_>>();
+    let start = blocks
+        .iter()
+        .position(|block| *block == title.trim())
+        .map_or(0, |index| index + 1);
+
+    let mut summary = String::new();
+    let mut length = 0;
+    let words =
+        blocks[start ..].iter().flat_map(|block| block.split_whitespace());
+    for word in words {
+        let word_length = word.chars().count();
+        let separator = usize::from(length > 0);
+        if length + separator + word_length > max_length {
+            summary.push('…');
+            break;
+        }
+        if separator > 0 {
+            summary.push(' ');
+        }
+        summary.push_str(word);
+        length += separator + word_length;
+    }
+    summary
+}
+
+#[cfg(test)]
+mod test {
+    use super::{atom_date, summarize, Feed, FeedError};
+    use crate::{
+        component::{
+            block::{math::Math, text::Paragraph, InlineBlock},
+            page::{Page, PageComponent, PageMetadata},
+            section::Section,
+        },
+        harray,
+        location::InternalPath,
+        render::{DynFullComponent, FullRender, Text},
+        site::{registry::Registry, Entry, Site},
+    };
+    use url::Url;
+
+    fn make_site() -> Site<DynFullComponent<'static, PageComponent>> {
+        let mut site = Site::default();
+        site.root.insert_index(
+            InternalPath::parse("foo"),
+            Entry::Page(
+                Page {
+                    metadata: Some(PageMetadata {
+                        created: Some(String::from("2024-01-15")),
+                        ..PageMetadata::default()
+                    }),
+                    ..Page::new(
+                        InlineBlock("Banner"),
+                        String::from("Foo & Bar"),
+                        harray![],
+                        Paragraph("Foo is a metavariable."),
+                        harray![Section {
+                            title: "History",
+                            id: None,
+                            body: Paragraph("It is old."),
+                            children: harray![],
+                        }],
+                    )
+                }
+                .into_dyn(),
+            ),
+        );
+        site
+    }
+
+    fn make_feed() -> Feed {
+        Feed::new(
+            Url::parse("https://example.com/wiki/").unwrap(),
+            String::from("Changes"),
+            String::from("Editors"),
+            String::from("2024-02-01T00:00:00Z"),
+        )
+    }
+
+    #[test]
+    fn summary_truncated() {
+        assert_eq!(
+            summarize("Banner\n\nTitle\n\nOne two  three\nfour.", "Title", 13),
+            "One two three…"
+        );
+        assert_eq!(summarize("No title here.", "Title", 100), "No title here.");
+    }
+
+    #[test]
+    fn atom_dates() {
+        assert_eq!(atom_date("2024"), "2024-01-01T00:00:00Z");
+        assert_eq!(atom_date("2024-03"), "2024-03-01T00:00:00Z");
+        assert_eq!(atom_date("2024-03-05"), "2024-03-05T00:00:00Z");
+        assert_eq!(atom_date("2024-03-05T10:30Z"), "2024-03-05T10:30:00Z");
+        assert_eq!(
+            atom_date("2024-03-05T10:30:15.5-03:00"),
+            "2024-03-05T10:30:15.5-03:00"
+        );
+    }
+
+    #[test]
+    fn feed_entries() {
+        let site = make_site();
+        let registry = Registry::from_site(&site, &mut Text::default());
+        let mut feed = make_feed();
+        feed.add_page(&site, &registry, InternalPath::parse("foo"), None, None)
+            .unwrap();
+
+        let xml = feed.render();
+        assert!(xml.contains("<title>Foo &amp; Bar</title>"));
+        assert!(xml.contains("<id>https://example.com/wiki/foo/</id>"));
+        assert!(xml.contains(
+            "<link rel=\"self\" href=\"https://example.com/wiki/atom.xml\"/>"
+        ));
+        assert!(xml.contains("<updated>2024-01-15T00:00:00Z</updated>"));
+        assert!(xml.contains(
+            "<summary>Foo is a metavariable. History It is old.</summary>"
+        ));
+    }
+
+    #[test]
+    fn overridden_title_and_date() {
+        let site = make_site();
+        let registry = Registry::from_pages(&site);
+        let mut feed = make_feed();
+        let error = feed
+            .add_page(&site, &registry, InternalPath::parse("foo"), None, None)
+            .unwrap_err();
+        assert_eq!(
+            error,
+            FeedError::Untitled(InternalPath::parse("foo/index.html"))
+        );
+
+        feed.add_page(
+            &site,
+            &registry,
+            InternalPath::parse("foo"),
+            Some(String::from("Foo")),
+            Some(String::from("2024-01-31T12:00:00Z")),
+        )
+        .unwrap();
+        assert_eq!(feed.entries[0].title, "Foo");
+        assert_eq!(feed.entries[0].updated, "2024-01-31T12:00:00Z");
+    }
+
+    #[test]
+    fn missing_page() {
+        let site = make_site();
+        let registry = Registry::from_site(&site, &mut Text::default());
+        let error = make_feed()
+            .add_page(&site, &registry, InternalPath::parse("bar"), None, None)
+            .unwrap_err();
+        assert_eq!(error, FeedError::NotFound(InternalPath::parse("bar")));
+    }
+
+    #[test]
+    fn render_errors_reported() {
+        let mut site = make_site();
+        site.root.insert_index(
+            InternalPath::parse("bar"),
+            Entry::Page(
+                Page::new(
+                    harray![],
+                    String::from("Bar"),
+                    harray![],
+                    Math(String::from("\\frac{1}")),
+                    harray![],
+                )
+                .into_dyn(),
+            ),
+        );
+        let registry = Registry::from_site(&site, &mut Text::default());
+        let error = make_feed()
+            .add_page(
+                &site,
+                &registry,
+                InternalPath::parse("bar"),
+                None,
+                Some(String::from("2024-01-31T12:00:00Z")),
+            )
+            .unwrap_err();
+        assert_eq!(
+            error,
+            FeedError::Render(
+                InternalPath::parse("bar/index.html"),
+                String::from(
+                    "invalid math at byte 8: missing argument in `\\frac{1}`"
+                )
+            )
+        );
+    }
+}
@@ -0,0 +1,269 @@
+//! This module provides incremental builds of a site: a manifest of content
+//! hashes is kept in the output directory, and only files whose contents
+//! changed since the last build are written.
+
+use super::{fs_path, output::Output, BuildError, Site};
+use crate::{
+    component::{page::PageComponent, Component},
+    location::InternalPath,
+    render::{self, Render},
+};
+use std::{
+    collections::BTreeMap,
+    fs,
+    io::{self, ErrorKind},
+    path::{Path, PathBuf},
+};
+
+/// Name of the manifest file, stored at the root of the output directory.
+pub const MANIFEST_NAME: &str = ".codedpedia-manifest";
+
+/// Summary of the changes made by an incremental build. Paths are sorted.
+#[derive(Debug, Clone, PartialEq, Eq, Default)]
+pub struct Changes {
+    /// Pages and resources whose output was (re)written.
+    pub written: Vec<InternalPath>,
+    /// Pages and resources whose output was kept as it was.
+    pub unchanged: Vec<InternalPath>,
+    /// Files of the previous build deleted because they no longer exist in
+    /// the site.
+    pub removed: Vec<InternalPath>,
+}
+
+/// Content hashes of the files of a build, mapped by path.
+type Manifest = BTreeMap<InternalPath, u64>;
+
+/// Output backend writing to a concrete filesystem incrementally, under a
+/// root directory: a file is only written if the hash of its contents
+/// changed since the previous build, according to the manifest (named
+/// [`MANIFEST_NAME`]) of the previous build, or if it is missing.
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct Incremental {
+    root: PathBuf,
+    previous: Manifest,
+    current: Manifest,
+    changes: Changes,
+}
+
+impl Incremental {
+    /// Starts an incremental build under the given directory, reading the
+    /// manifest of the previous build, if any.
+    pub fn open<R>(root: R) -> Self
+    where
+        R: Into<PathBuf>,
+    {
+        let root = root.into();
+        let previous = read_manifest(&root);
+        Self {
+            root,
+            previous,
+            current: Manifest::new(),
+            changes: Changes::default(),
+        }
+    }
+
+    /// Finishes the build: files listed by the previous manifest but not
+    /// written by this build are deleted, as well as directories left empty
+    /// by such deletions, and the manifest of this build is written.
+    pub fn finish(mut self) -> Result<Changes, BuildError> {
+        for path in self.previous.keys() {
+            if self.current.contains_key(path) {
+                continue;
+            }
+            let dest = fs_path(&self.root, path);
+            match fs::remove_file(&dest) {
+                Ok(()) => (),
+                Err(cause) if cause.kind() == ErrorKind::NotFound => (),
+                Err(cause) => Err(BuildError { path: path.clone(), cause })?,
+            }
+            remove_empty_parents(&self.root, path);
+            self.changes.removed.push(path.clone());
+        }
+
+        write_manifest(&self.root, &self.current).map_err(|cause| {
+            BuildError { path: InternalPath::parse(MANIFEST_NAME), cause }
+        })?;
+
+        Ok(self.changes)
+    }
+
+    /// Writes the file at the given path, unless its contents are the same
+    /// as in the previous build.
+    fn write_file(
+        &mut self,
+        path: &InternalPath,
+        contents: &[u8],
+    ) -> io::Result<()> {
+        let dest = fs_path(&self.root, path);
+        let hash = hash(contents);
+        if self.previous.get(path) == Some(&hash) && dest.is_file() {
+            self.changes.unchanged.push(path.clone());
+        } else {
+            fs::write(&dest, contents)?;
+            self.changes.written.push(path.clone());
+        }
+        self.current.insert(path.clone(), hash);
+        Ok(())
+    }
+}
+
+impl Output for Incremental {
+    fn create_dir(&mut self, path: &InternalPath) -> io::Result<()> {
+        fs::create_dir_all(fs_path(&self.root, path))
+    }
+
+    fn write_page(
+        &mut self,
+        path: &InternalPath,
+        contents: &[u8],
+    ) -> io::Result<()> {
+        self.write_file(path, contents)
+    }
+
+    fn write_resource(
+        &mut self,
+        path: &InternalPath,
+        contents: &[u8],
+    ) -> io::Result<()> {
+        self.write_file(path, contents)
+    }
+}
+
+impl<P> Site<P>
+where
+    P: Component<Kind = PageComponent>,
+{
+    /// Builds the site into a concrete filesystem incrementally, given a
+    /// render format, an output directory path and a resource directory path.
+    /// A convenience over [`Site::build_into`] with an [`Incremental`]
+    /// output.
+    ///
+    /// Unlike [`Site::build`], the output directory is not cleared. Instead,
+    /// a manifest (named [`MANIFEST_NAME`]) with content hashes of the files
+    /// written by the previous build is read, and a file is only written if
+    /// its hash changed, or if it is missing. Files listed by the previous
+    /// manifest but no longer in the site are deleted, as well as
+    /// directories left empty by such deletions. Files not created by a
+    /// build are never touched.
+    pub fn build_incremental<W>(
+        &self,
+        format: &mut W,
+        output_dir: &Path,
+        resource_dir: &Path,
+    ) -> Result<Changes, BuildError>
+    where
+        W: render::Format + ?Sized,
+        P: Render<W>,
+    {
+        let mut output = Incremental::open(output_dir);
+        let mut resource_dir = resource_dir.to_path_buf();
+        self.build_into(format, &mut output, &mut resource_dir)?;
+        output.finish()
+    }
+}
+
+/// Hashes contents using 64-bit FNV-1a, which is stable across builds and
+/// platforms, unlike the standard library's hashers.
+fn hash(contents: &[u8]) -> u64 {
+    let mut hash = 0xcbf29ce484222325u64;
+    for &byte in contents {
+        hash ^= u64::from(byte);
+        hash = hash.wrapping_mul(0x100000001b3);
+    }
+    hash
+}
+
+/// Reads the manifest from the output directory. A missing manifest is
+/// empty, and invalid lines are ignored, which only causes files to be
+/// rewritten.
+fn read_manifest(output_dir: &Path) -> Manifest {
+    let contents = match fs::read_to_string(output_dir.join(MANIFEST_NAME)) {
+        Ok(contents) => contents,
+        Err(_) => return Manifest::new(),
+    };
+    let mut manifest = Manifest::new();
+    for line in contents.lines() {
+        let Some((hash, path)) = line.split_once(' ') else {
+            continue;
+        };
+        let (Ok(hash), Ok(path)) =
+            (u64::from_str_radix(hash, 16), InternalPath::try_parse(path))
+        else {
+            continue;
+        };
+        manifest.insert(path, hash);
+    }
+    manifest
+}
+
+/// Writes the manifest into the output directory, one "hash path" per line.
+fn write_manifest(output_dir: &Path, manifest: &Manifest) -> io::Result<()> {
+    let mut contents = String::new();
+    for (path, hash) in manifest {
+        contents.push_str(&format!("{:016x} {}\n", hash, path));
+    }
+    fs::write(output_dir.join(MANIFEST_NAME), contents)
+}
+
+/// Removes the parent directories of a deleted file, from the deepest one up,
+/// while they are empty. The output directory itself is kept.
+fn remove_empty_parents(output_dir: &Path, path: &InternalPath) {
+    let mut parent = path.clone();
+    parent.fragments.pop();
+    while !parent.is_root() {
+        if fs::remove_dir(fs_path(output_dir, &parent)).is_err() {
+            break;
+        }
+        parent.fragments.pop();
+    }
+}
+
+#[cfg(test)]
+mod test {
+    use super::MANIFEST_NAME;
+    use crate::{
+        location::{Fragment, InternalPath},
+        render::Html,
+        site::{
+            test::{make_page, make_site, temp_dir},
+            Entry,
+        },
+    };
+    use std::fs;
+
+    #[test]
+    fn only_changes_written() {
+        let resources = temp_dir("incremental-resources");
+        let output = temp_dir("incremental-output");
+        fs::create_dir_all(&resources).unwrap();
+        fs::write(resources.join("main.css"), "body {}").unwrap();
+
+        let mut site = make_site(&[("", "A"), ("foo", "B")]);
+        site.root
+            .insert_path(&InternalPath::parse("main.css"), Entry::Resource);
+
+        let changes =
+            site.build_incremental(&mut Html, &output, &resources).unwrap();
+        assert_eq!(changes.written.len(), 3);
+        assert!(output.join(MANIFEST_NAME).is_file());
+
+        let changes =
+            site.build_incremental(&mut Html, &output, &resources).unwrap();
+        assert!(changes.written.is_empty());
+        assert_eq!(changes.unchanged.len(), 3);
+
+        site.root.entries.remove(&Fragment::new("foo"));
+        site.root.entries.remove(&Fragment::new("index.html"));
+        site.root
+            .insert_index(InternalPath::root(), Entry::Page(make_page("C")));
+        let changes =
+            site.build_incremental(&mut Html, &output, &resources).unwrap();
+        assert_eq!(changes.written, [InternalPath::parse("index.html")]);
+        assert_eq!(changes.unchanged, [InternalPath::parse("main.css")]);
+        assert_eq!(changes.removed, [InternalPath::parse("foo/index.html")]);
+        assert!(!output.join("foo").exists());
+
+        fs::remove_dir_all(resources).unwrap();
+        fs::remove_dir_all(output).unwrap();
+    }
+}
@@ -0,0 +1,329 @@
+//! This module provides multi-format builds of a site: the HTML site, a
+//! Markdown mirror and a plain-text mirror, built in a single pass.
+
+use super::{
+    build_page,
+    fs_path,
+    output::{Filesystem, Output},
+    registry::Registry,
+    BuildError,
+    Entry,
+    Site,
+};
+use crate::{
+    component::{inline::cite::CitationStyle, page::PageComponent, Component},
+    location::InternalPath,
+    render::{self, FullRender, Html, Markdown, Render, Text},
+};
+use std::{error::Error, fmt, fs, io, path::Path};
+
+/// A rendering format of a multi-format build.
+#[derive(Debug, Clone, Copy, PartialEq, Eq, PartialOrd, Ord, Hash)]
+pub enum BuildFormat {
+    /// The HTML site.
+    Html,
+    /// The Markdown mirror.
+    Markdown,
+    /// The plain-text mirror.
+    Text,
+}
+
+impl BuildFormat {
+    /// All formats of a multi-format build, in build order.
+    pub const ALL: [Self; 3] = [Self::Html, Self::Markdown, Self::Text];
+
+    /// Name of the output directory of this format, used by
+    /// [`Site::build_multi`].
+    pub fn dir_name(self) -> &'static str {
+        match self {
+            Self::Html => "html",
+            Self::Markdown => "markdown",
+            Self::Text => "text",
+        }
+    }
+}
+
+impl fmt::Display for BuildFormat {
+    fn fmt(&self, fmtr: &mut fmt::Formatter) -> fmt::Result {
+        match self {
+            Self::Html => write!(fmtr, "HTML"),
+            Self::Markdown => write!(fmtr, "Markdown"),
+            Self::Text => write!(fmtr, "text"),
+        }
+    }
+}
+
+/// An error that may happen when building a site in multiple formats,
+/// tagged with the format that failed.
+#[derive(Debug)]
+pub struct MultiBuildError {
+    /// Format whose build failed.
+    pub format: BuildFormat,
+    /// The error, with the path of the problematic file.
+    pub error: BuildError,
+}
+
+impl From<MultiBuildError> for io::Error {
+    fn from(error: MultiBuildError) -> Self {
+        error.error.cause
+    }
+}
+
+impl fmt::Display for MultiBuildError {
+    fn fmt(&self, fmtr: &mut fmt::Formatter) -> fmt::Result {
+        write!(fmtr, "{} build: {}", self.format, self.error)
+    }
+}
+
+impl Error for MultiBuildError {
+    fn source(&self) -> Option<&(dyn Error + 'static)> {
+        Some(&self.error)
+    }
+}
+
+/// Output backends of a multi-format build, one per format.
+#[derive(Debug, Clone, PartialEq, Eq, Default)]
+pub struct Outputs<O> {
+    /// Output of the HTML site.
+    pub html: O,
+    /// Output of the Markdown mirror.
+    pub markdown: O,
+    /// Output of the plain-text mirror.
+    pub text: O,
+}
+
+impl<O> Outputs<O> {
+    /// Yields the output of the given format.
+    pub fn get_mut(&mut self, format: BuildFormat) -> &mut O {
+        match format {
+            BuildFormat::Html => &mut self.html,
+            BuildFormat::Markdown => &mut self.markdown,
+            BuildFormat::Text => &mut self.text,
+        }
+    }
+}
+
+impl<P> Site<P>
+where
+    P: Component<Kind = PageComponent>,
+{
+    /// Builds the site into a concrete filesystem in every format of
+    /// [`BuildFormat`], each one into a subdirectory of the given output
+    /// directory (see [`BuildFormat::dir_name`]), given a resource directory
+    /// path. The output directories are cleared first. A convenience over
+    /// [`Site::build_multi_into`] with [`Filesystem`] outputs.
+    pub fn build_multi(
+        &self,
+        output_dir: &Path,
+        resource_dir: &Path,
+    ) -> Result<(), MultiBuildError>
+    where
+        P: FullRender,
+    {
+        let output = |format: BuildFormat| {
+            let dir = output_dir.join(format.dir_name());
+            let _ = fs::remove_dir_all(&dir);
+            Filesystem::new(dir)
+        };
+        let mut outputs = Outputs {
+            html: output(BuildFormat::Html),
+            markdown: output(BuildFormat::Markdown),
+            text: output(BuildFormat::Text),
+        };
+        self.build_multi_into(&mut outputs, resource_dir)
+    }
+
+    /// Builds the site through the given output backends, one per format,
+    /// given a resource directory path. The site is traversed once: for each
+    /// entry in order of paths, directories are created, pages are rendered
+    /// and resources are copied for every format before moving to the next
+    /// entry. Resources are read once and written to every output. Files
+    /// generated from the site as a whole (e.g. the sitemap) only go to the
+    /// HTML output, since they refer to HTML pages.
+    pub fn build_multi_into<O>(
+        &self,
+        outputs: &mut Outputs<O>,
+        resource_dir: &Path,
+    ) -> Result<(), MultiBuildError>
+    where
+        P: FullRender,
+        O: Output,
+    {
+        let mut entries = self.root.walk().collect::<Vec<_>>();
+        entries.push((InternalPath::root(), Entry::Directory(&self.root)));
+        entries.sort_by(|(left, _), (right, _)| left.cmp(right));
+
+        let style = self.citation_style;
+        let mut html = Html;
+        let mut markdown = Markdown::default();
+        let mut text = Text::default();
+        // Section titles are registered as rendered, thus once per format
+        // when pages are rendered a first time.
+        let html_registry = self.registry(&mut html);
+        let markdown_registry = self.registry(&mut markdown);
+        let text_registry = self.registry(&mut text);
+
+        for (path, entry) in entries {
+            // Resources are read once for all formats, so that a failed read
+            // leaves no output half-written.
+            let resource = match entry {
+                Entry::Resource => {
+                    let source = fs_path(resource_dir, &path);
+                    let contents =
+                        fs::read(source).map_err(|cause| MultiBuildError {
+                            format: BuildFormat::Html,
+                            error: BuildError { path: path.clone(), cause },
+                        })?;
+                    Some(contents)
+                },
+                Entry::Directory(_) | Entry::Page(_) => None,
+            };
+
+            for format in BuildFormat::ALL {
+                let output = outputs.get_mut(format);
+                let result = match entry {
+                    Entry::Directory(_) => {
+                        output.create_dir(&path).map_err(|cause| BuildError {
+                            path: path.clone(),
+                            cause,
+                        })
+                    },
+                    Entry::Page(page) => match format {
+                        BuildFormat::Html => write_page(
+                            page,
+                            &mut html,
+                            &path,
+                            style,
+                            &html_registry,
+                            output,
+                        ),
+                        BuildFormat::Markdown => write_page(
+                            page,
+                            &mut markdown,
+                            &path,
+                            style,
+                            &markdown_registry,
+                            output,
+                        ),
+                        BuildFormat::Text => write_page(
+                            page,
+                            &mut text,
+                            &path,
+                            style,
+                            &text_registry,
+                            output,
+                        ),
+                    },
+                    Entry::Resource => {
+                        let contents = resource.as_deref().unwrap_or_default();
+                        output.write_resource(&path, contents).map_err(
+                            |cause| BuildError { path: path.clone(), cause },
+                        )
+                    },
+                };
+                result.map_err(|error| MultiBuildError { format, error })?;
+            }
+        }
+
+        for (path, contents) in self.generated_files(&html, &html_registry) {
+            outputs.html.write_page(&path, contents.as_bytes()).map_err(
+                |cause| MultiBuildError {
+                    format: BuildFormat::Html,
+                    error: BuildError { path, cause },
+                },
+            )?;
+        }
+
+        Ok(())
+    }
+}
+
+/// Renders a page with the given format, citation style and site registry,
+/// and writes it through the given output, into the file named after the
+/// format.
+fn write_page<P, W, O>(
+    page: &P,
+    format: &mut W,
+    path: &InternalPath,
+    citation_style: CitationStyle,
+    registry: &Registry,
+    output: &mut O,
+) -> Result<(), BuildError>
+where
+    W: render::Format + ?Sized,
+    P: Render<W, Kind = PageComponent> + ?Sized,
+    O: Output + ?Sized,
+{
+    let file_path = path.for_format(format);
+    let mut contents = Vec::new();
+    build_page(page, format, path, citation_style, registry, &mut contents)
+        .and_then(|()| output.write_page(&file_path, &contents))
+        .map_err(|cause| BuildError { path: file_path, cause })
+}
+
+#[cfg(test)]
+mod test {
+    use super::{BuildFormat, Outputs};
+    use crate::{
+        component::page::PageComponent,
+        location::InternalPath,
+        render::DynFullComponent,
+        site::{
+            test::{make_site, temp_dir},
+            Entry,
+            Site,
+        },
+    };
+    use std::{collections::BTreeMap, fs, path::Path};
+
+    #[test]
+    fn builds_every_format() {
+        let site = make_site(&[("", "Home"), ("foo", "Foo")]);
+        let mut outputs = Outputs::<BTreeMap<_, _>>::default();
+        site.build_multi_into(&mut outputs, Path::new("")).unwrap();
+
+        let paths = |output: &BTreeMap<InternalPath, Vec<u8>>| {
+            output.keys().map(ToString::to_string).collect::<Vec<_>>()
+        };
+        assert_eq!(paths(&outputs.html), ["foo/index.html", "index.html"]);
+        assert_eq!(paths(&outputs.markdown), ["foo/index.md", "index.md"]);
+        assert_eq!(paths(&outputs.text), ["foo/index.txt", "index.txt"]);
+        let foo = &outputs.markdown[&InternalPath::parse("foo/index.md")];
+        assert!(String::from_utf8_lossy(foo).contains("# Foo"));
+    }
+
+    #[test]
+    fn resources_in_every_format() {
+        let resources = temp_dir("multi-resources");
+        fs::create_dir_all(&resources).unwrap();
+        fs::write(resources.join("main.css"), "body {}").unwrap();
+        let mut site = Site::<DynFullComponent<PageComponent>>::default();
+        site.root
+            .insert_path(&InternalPath::parse("main.css"), Entry::Resource);
+
+        let mut outputs = Outputs::<BTreeMap<_, _>>::default();
+        site.build_multi_into(&mut outputs, &resources).unwrap();
+        fs::remove_dir_all(&resources).unwrap();
+
+        let path = InternalPath::parse("main.css");
+        for output in [&outputs.html, &outputs.markdown, &outputs.text] {
+            assert_eq!(output[&path], b"body {}");
+        }
+    }
+
+    #[test]
+    fn errors_tagged_with_format() {
+        let mut site = Site::<DynFullComponent<PageComponent>>::default();
+        site.root.insert_path(
+            &InternalPath::parse("styles/main.css"),
+            Entry::Resource,
+        );
+
+        let mut outputs = Outputs::<BTreeMap<_, _>>::default();
+        let error = site
+            .build_multi_into(&mut outputs, Path::new("/nowhere"))
+            .unwrap_err();
+        assert_eq!(error.format, BuildFormat::Html);
+        assert_eq!(error.error.path, InternalPath::parse("styles/main.css"));
+    }
+}
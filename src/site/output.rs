@@ -0,0 +1,236 @@
+//! This module provides output backends, through which a site is built (see
+//! [`Site::build_into`](super::Site::build_into)).
+
+use super::fs_path;
+use crate::location::InternalPath;
+use std::{
+    collections::BTreeMap,
+    fs,
+    io,
+    path::{Path, PathBuf},
+};
+
+/// An output backend of a site build. Paths given to the backend are internal
+/// paths of the site's entries.
+pub trait Output {
+    /// Creates a directory at the given path. Called for every directory of
+    /// the site, including the root (whose path is empty), before its
+    /// entries.
+    fn create_dir(&mut self, path: &InternalPath) -> io::Result<()>;
+
+    /// Writes the contents of a rendered page at the given path.
+    fn write_page(
+        &mut self,
+        path: &InternalPath,
+        contents: &[u8],
+    ) -> io::Result<()>;
+
+    /// Writes the contents of a resource at the given path.
+    fn write_resource(
+        &mut self,
+        path: &InternalPath,
+        contents: &[u8],
+    ) -> io::Result<()>;
+
+    /// Copies a resource at the given path, from the given source file of the
+    /// resource directory. By default, the source file is read and its
+    /// contents are written with [`Output::write_resource`].
+    fn copy_resource(
+        &mut self,
+        path: &InternalPath,
+        source: &Path,
+    ) -> io::Result<()> {
+        let contents = fs::read(source)?;
+        self.write_resource(path, &contents)
+    }
+}
+
+impl<O> Output for &mut O
+where
+    O: Output + ?Sized,
+{
+    fn create_dir(&mut self, path: &InternalPath) -> io::Result<()> {
+        (**self).create_dir(path)
+    }
+
+    fn write_page(
+        &mut self,
+        path: &InternalPath,
+        contents: &[u8],
+    ) -> io::Result<()> {
+        (**self).write_page(path, contents)
+    }
+
+    fn write_resource(
+        &mut self,
+        path: &InternalPath,
+        contents: &[u8],
+    ) -> io::Result<()> {
+        (**self).write_resource(path, contents)
+    }
+
+    fn copy_resource(
+        &mut self,
+        path: &InternalPath,
+        source: &Path,
+    ) -> io::Result<()> {
+        (**self).copy_resource(path, source)
+    }
+}
+
+/// Output backend writing to a concrete filesystem, under a root directory.
+#[derive(Debug, Clone, PartialEq, Eq, PartialOrd, Ord, Hash)]
+pub struct Filesystem {
+    /// The output directory.
+    pub root: PathBuf,
+}
+
+impl Filesystem {
+    /// Creates a filesystem backend writing under the given directory.
+    pub fn new<R>(root: R) -> Self
+    where
+        R: Into<PathBuf>,
+    {
+        Self { root: root.into() }
+    }
+}
+
+impl Output for Filesystem {
+    fn create_dir(&mut self, path: &InternalPath) -> io::Result<()> {
+        fs::create_dir_all(fs_path(&self.root, path))
+    }
+
+    fn write_page(
+        &mut self,
+        path: &InternalPath,
+        contents: &[u8],
+    ) -> io::Result<()> {
+        fs::write(fs_path(&self.root, path), contents)
+    }
+
+    fn write_resource(
+        &mut self,
+        path: &InternalPath,
+        contents: &[u8],
+    ) -> io::Result<()> {
+        fs::write(fs_path(&self.root, path), contents)
+    }
+
+    fn copy_resource(
+        &mut self,
+        path: &InternalPath,
+        source: &Path,
+    ) -> io::Result<()> {
+        fs::copy(source, fs_path(&self.root, path))?;
+        Ok(())
+    }
+}
+
+/// In-memory output backend, mapping the paths of pages and resources to
+/// their contents. Directories are implied by the paths.
+impl Output for BTreeMap<InternalPath, Vec<u8>> {
+    fn create_dir(&mut self, _path: &InternalPath) -> io::Result<()> {
+        Ok(())
+    }
+
+    fn write_page(
+        &mut self,
+        path: &InternalPath,
+        contents: &[u8],
+    ) -> io::Result<()> {
+        self.insert(path.clone(), contents.to_vec());
+        Ok(())
+    }
+
+    fn write_resource(
+        &mut self,
+        path: &InternalPath,
+        contents: &[u8],
+    ) -> io::Result<()> {
+        self.insert(path.clone(), contents.to_vec());
+        Ok(())
+    }
+}
+
+#[cfg(test)]
+mod test {
+    use crate::{
+        component::{
+            block::text::Paragraph,
+            inline::text::Link,
+            page::{Page, PageComponent},
+        },
+        harray,
+        location::{InternalPath, Location},
+        render::{DynFullComponent, FullRender, Html, Markdown},
+        site::{
+            test::{make_page, make_site},
+            Entry,
+            Site,
+        },
+    };
+    use std::{collections::BTreeMap, path::PathBuf};
+
+    #[test]
+    fn build_into_memory() {
+        let site = make_site(&[("", "Home"), ("foo/bar", "Bar")]);
+
+        let mut output = BTreeMap::new();
+        site.build_into(&mut Html, &mut output, &mut PathBuf::new()).unwrap();
+
+        let paths = output.keys().map(ToString::to_string).collect::<Vec<_>>();
+        assert_eq!(paths, ["foo/bar/index.html", "index.html"]);
+        let bar = &output[&InternalPath::parse("foo/bar/index.html")];
+        assert!(String::from_utf8_lossy(bar).contains("<title>Bar</title>"));
+    }
+
+    #[test]
+    fn files_named_after_format() {
+        let mut site = make_site(&[("", "Home")]);
+        site.root.insert_index(
+            InternalPath::parse("foo"),
+            Entry::Page(
+                Page::new(
+                    harray![],
+                    String::from("Foo"),
+                    harray![],
+                    Paragraph(Link {
+                        location: Location::internal("foo/bar.html"),
+                        target: "Bar",
+                    }),
+                    harray![],
+                )
+                .into_dyn(),
+            ),
+        );
+        site.root.insert_path(
+            &InternalPath::parse("foo/bar.html"),
+            Entry::Page(make_page("Bar")),
+        );
+
+        let mut output = BTreeMap::new();
+        let mut format = Markdown::default();
+        site.build_into(&mut format, &mut output, &mut PathBuf::new()).unwrap();
+
+        let paths = output.keys().map(ToString::to_string).collect::<Vec<_>>();
+        assert_eq!(paths, ["foo/bar.md", "foo/index.md", "index.md"]);
+        let foo = &output[&InternalPath::parse("foo/index.md")];
+        let foo = String::from_utf8_lossy(foo);
+        assert!(foo.contains("[Bar](../foo&#47;bar.md)"));
+    }
+
+    #[test]
+    fn missing_resource_reported() {
+        let mut site = Site::<DynFullComponent<PageComponent>>::default();
+        site.root.insert_path(
+            &InternalPath::parse("styles/main.css"),
+            Entry::Resource,
+        );
+
+        let mut output = BTreeMap::new();
+        let error = site
+            .build_into(&mut Html, &mut output, &mut PathBuf::from("/nowhere"))
+            .unwrap_err();
+        assert_eq!(error.path, InternalPath::parse("styles/main.css"));
+    }
+}
@@ -0,0 +1,481 @@
+//! This module provides the registry of a site: information about its pages
+//! gathered before building them, so components can look up other pages
+//! while rendering (see [`Context::registry`]).
+
+use super::{for_each_parallel, Discard, Entry, Site};
+use crate::{
+    component::{
+        page::{PageComponent, PageMetadata},
+        Component,
+    },
+    location::{Id, InternalLoc, InternalPath, INDEX_NAME},
+    render::{self, Context, Lookup, Observer, Render, Renderer},
+};
+use std::{
+    cell::RefCell,
+    collections::HashMap,
+    num::NonZeroUsize,
+    sync::{Mutex, PoisonError},
+};
+
+/// Information about a page of a site.
+#[derive(Debug, Clone, PartialEq, Eq, Default)]
+pub struct PageInfo {
+    /// Title of the page, if it was notified.
+    pub title: Option<String>,
+    /// Titles of the sections of the page having an ID, by ID, as rendered in
+    /// the format the registry was gathered with.
+    pub sections: HashMap<Id, String>,
+    /// Metadata of the page, if any.
+    pub metadata: Option<PageMetadata>,
+}
+
+/// Information about the pages of a site, by path of page. Rendered
+/// components look pages up through it as a [`Lookup`].
+#[derive(Debug, Clone, PartialEq, Eq, Default)]
+pub struct Registry {
+    pages: HashMap<InternalPath, PageInfo>,
+}
+
+impl Registry {
+    /// Creates an empty registry.
+    pub fn new() -> Self {
+        Self::default()
+    }
+
+    /// Registers all pages of the given site without rendering them: only
+    /// the existence of pages is known, not their titles, sections or
+    /// metadata.
+    pub fn from_pages<P>(site: &Site<P>) -> Self
+    where
+        P: Component<Kind = PageComponent>,
+    {
+        let mut registry = Self::new();
+        for (path, entry) in site.root.walk() {
+            if entry.is_page() {
+                registry.insert(path, PageInfo::default());
+            }
+        }
+        registry
+    }
+
+    /// Gathers information about all pages of the given site, in a pre-pass
+    /// rendering them with the given format and observing the rendering.
+    /// Nothing is written, and pages failing to render are registered with
+    /// what was observed before the failure, the failure being reported by
+    /// the actual build.
+    pub fn from_site<P, W>(site: &Site<P>, format: &mut W) -> Self
+    where
+        W: render::Format + ?Sized,
+        P: Render<W, Kind = PageComponent>,
+    {
+        let mut registry = Self::new();
+        for (path, entry) in site.root.walk() {
+            if let Entry::Page(page) = entry {
+                let info = observe_page(page, &path, format);
+                registry.insert(path, info);
+            }
+        }
+        registry
+    }
+
+    /// Gathers information about all pages of the given site, like
+    /// [`Registry::from_site`], but renders pages concurrently, using the
+    /// given number of worker threads, each one with its own clone of the
+    /// given format.
+    pub fn from_site_parallel<P, W>(
+        site: &Site<P>,
+        format: &W,
+        workers: NonZeroUsize,
+    ) -> Self
+    where
+        W: render::Format + Clone + Send,
+        P: Render<W, Kind = PageComponent> + Sync,
+    {
+        let pages = site
+            .root
+            .walk()
+            .filter_map(|(path, entry)| match entry {
+                Entry::Page(page) => Some((path, page)),
+                _ => None,
+            })
+            .collect::<Vec<_>>();
+        let registry = Mutex::new(Self::new());
+        for_each_parallel(&pages, format, workers, |(path, page), format| {
+            let info = observe_page(*page, path, format);
+            registry
+                .lock()
+                .unwrap_or_else(PoisonError::into_inner)
+                .insert(path.clone(), info);
+        });
+        registry.into_inner().unwrap_or_else(PoisonError::into_inner)
+    }
+
+    /// Registers the page at the given path, replacing and yielding the
+    /// previous information registered for the path, if any.
+    pub fn insert(
+        &mut self,
+        path: InternalPath,
+        info: PageInfo,
+    ) -> Option<PageInfo> {
+        self.pages.insert(path, info)
+    }
+
+    /// Registers the title of the page at the given path.
+    pub fn insert_title(&mut self, path: InternalPath, title: String) {
+        self.pages.entry(path).or_default().title = Some(title);
+    }
+
+    /// Registers a section of the page at the given path, with its ID and
+    /// title.
+    pub fn insert_section(
+        &mut self,
+        path: InternalPath,
+        id: Id,
+        title: String,
+    ) {
+        self.pages.entry(path).or_default().sections.insert(id, title);
+    }
+
+    /// Looks up the page at the given path. If the path points to a
+    /// directory, its index page is yielded (see [`InternalPath::eq_index`]).
+    pub fn page(&self, path: &InternalPath) -> Option<&PageInfo> {
+        match self.pages.get(path) {
+            Some(info) => Some(info),
+            None => self.pages.get(&path.clone().append(INDEX_NAME)),
+        }
+    }
+
+    /// Looks up the title of the page at the given path, resolving
+    /// directories like [`Registry::page`].
+    pub fn title(&self, path: &InternalPath) -> Option<&str> {
+        self.page(path)?.title.as_deref()
+    }
+
+    /// Looks up the title of the section with the given ID in the page at
+    /// the given path, resolving directories like [`Registry::page`].
+    pub fn section_title(&self, path: &InternalPath, id: &Id) -> Option<&str> {
+        self.page(path)?.sections.get(id).map(String::as_str)
+    }
+
+    /// Looks up the metadata of the page at the given path, resolving
+    /// directories like [`Registry::page`].
+    pub fn metadata(&self, path: &InternalPath) -> Option<&PageMetadata> {
+        self.page(path)?.metadata.as_ref()
+    }
+
+    /// Tests whether the given location points to a registered page and, if
+    /// it has an ID, to a registered section of the page.
+    pub fn contains(&self, location: &InternalLoc) -> bool {
+        match (self.page(&location.path), &location.id) {
+            (Some(info), Some(id)) => info.sections.contains_key(id),
+            (Some(_), None) => true,
+            (None, _) => false,
+        }
+    }
+}
+
+impl Lookup for Registry {
+    fn contains_page(&self, path: &InternalPath) -> bool {
+        self.page(path).is_some()
+    }
+
+    fn contains(&self, location: &InternalLoc) -> bool {
+        Registry::contains(self, location)
+    }
+
+    fn title(&self, path: &InternalPath) -> Option<&str> {
+        Registry::title(self, path)
+    }
+
+    fn section_title(&self, path: &InternalPath, id: &Id) -> Option<&str> {
+        Registry::section_title(self, path, id)
+    }
+
+    fn metadata(&self, path: &InternalPath) -> Option<&PageMetadata> {
+        Registry::metadata(self, path)
+    }
+}
+
+/// Gathers information about a page at the given path, rendering it with the
+/// given format and observing the rendering.
+fn observe_page<P, W>(page: &P, path: &InternalPath, format: &mut W) -> PageInfo
+where
+    W: render::Format + ?Sized,
+    P: Render<W, Kind = PageComponent> + ?Sized,
+{
+    let collector = Collector::default();
+    let context = Context::new(path, &PageComponent).with_observer(&collector);
+    let mut target = Discard;
+    let mut renderer = Renderer::new(format, &mut target);
+    let _ = page.render(&mut renderer, context);
+    collector.info.into_inner()
+}
+
+/// Observer collecting information about a page.
+#[derive(Debug, Default)]
+struct Collector {
+    info: RefCell<PageInfo>,
+}
+
+impl Observer for Collector {
+    fn title(&self, title: &str) {
+        self.info.borrow_mut().title.get_or_insert_with(|| title.to_owned());
+    }
+
+    fn section(&self, id: &Id, title: &str) {
+        self.info
+            .borrow_mut()
+            .sections
+            .entry(id.clone())
+            .or_insert_with(|| title.to_owned());
+    }
+
+    fn metadata(&self, metadata: &PageMetadata) {
+        self.info.borrow_mut().metadata.get_or_insert_with(|| metadata.clone());
+    }
+}
+
+#[cfg(test)]
+mod test {
+    use super::Registry;
+    use crate::{
+        component::{
+            block::{breadcrumbs::Breadcrumbs, text::Paragraph},
+            inline::{
+                footnote::Footnote,
+                text::{AutoLink, Bold, Link},
+            },
+            page::{Page, PageComponent, PageMetadata},
+            section::Section,
+        },
+        harray,
+        location::{Id, InternalLoc, InternalPath, Location},
+        render::{DynFullComponent, FullRender, Html, Text},
+        site::{Entry, Site},
+    };
+    use std::{collections::BTreeMap, path::PathBuf};
+
+    fn page_with_breadcrumbs(
+        title: &'static str,
+    ) -> DynFullComponent<'static, PageComponent> {
+        Page::new(
+            harray![],
+            String::from(title),
+            harray![],
+            harray![Breadcrumbs, Paragraph("Hello")],
+            harray![],
+        )
+        .into_dyn()
+    }
+
+    #[test]
+    fn titles_from_site() {
+        let mut site = Site::default();
+        site.root.insert_index(
+            InternalPath::root(),
+            Entry::Page(page_with_breadcrumbs("Home")),
+        );
+        site.root.insert_index(
+            InternalPath::parse("bar"),
+            Entry::Page(page_with_breadcrumbs("Bar")),
+        );
+        site.root.insert_path(
+            &InternalPath::parse("bar/baz.html"),
+            Entry::Page(page_with_breadcrumbs("Baz")),
+        );
+
+        let registry = Registry::from_site(&site, &mut Html);
+        assert_eq!(registry.title(&InternalPath::root()), Some("Home"));
+        assert_eq!(registry.title(&InternalPath::parse("bar")), Some("Bar"));
+        assert_eq!(
+            registry.title(&InternalPath::parse("bar/index.html")),
+            Some("Bar")
+        );
+        assert_eq!(
+            registry.title(&InternalPath::parse("bar/baz.html")),
+            Some("Baz")
+        );
+        assert_eq!(registry.title(&InternalPath::parse("qux")), None);
+    }
+
+    #[test]
+    fn sections_and_metadata() {
+        let page = Page {
+            metadata: Some(PageMetadata {
+                description: Some(String::from("The home page")),
+                ..PageMetadata::default()
+            }),
+            ..Page::new(
+                harray![],
+                String::from("Home"),
+                harray![],
+                harray![],
+                harray![
+                    Section {
+                        title: harray!["Fish ", Bold("& chips")],
+                        id: Some(Id::new("fish")),
+                        body: harray![],
+                        children: harray![],
+                    },
+                    Section {
+                        title: "Untagged",
+                        id: None,
+                        body: harray![],
+                        children: harray![],
+                    },
+                ],
+            )
+        };
+        let mut site = Site::default();
+        site.root
+            .insert_index(InternalPath::root(), Entry::Page(page.into_dyn()));
+
+        let registry = Registry::from_site(&site, &mut Html);
+        let root = InternalPath::root();
+        assert_eq!(
+            registry.section_title(&root, &Id::new("fish")),
+            Some("Fish <span class=\"pedia-bold\">&amp; chips</span>")
+        );
+        assert_eq!(
+            registry
+                .metadata(&root)
+                .and_then(|metadata| metadata.description.as_deref()),
+            Some("The home page")
+        );
+        assert!(registry.contains(&InternalLoc::parse("#fish")));
+        assert!(!registry.contains(&InternalLoc::parse("#nope")));
+        assert!(!registry.contains(&InternalLoc::parse("nope")));
+
+        let registry = Registry::from_site(&site, &mut Text::default());
+        assert_eq!(
+            registry.section_title(&root, &Id::new("fish")),
+            Some("Fish & chips")
+        );
+    }
+
+    #[test]
+    fn section_titles_as_labels() {
+        let page = Page::new(
+            harray![],
+            String::from("Home"),
+            harray![],
+            Paragraph(AutoLink { location: InternalLoc::parse("#fish") }),
+            harray![Section {
+                title: harray![
+                    "Fish ",
+                    Link {
+                        target: "& chips",
+                        location: Location::internal("chips"),
+                    },
+                    Footnote("Fried."),
+                ],
+                id: Some(Id::new("fish")),
+                body: harray![],
+                children: harray![],
+            }],
+        );
+        let mut site = Site { prerender: true, ..Site::default() };
+        site.root
+            .insert_index(InternalPath::root(), Entry::Page(page.into_dyn()));
+
+        let registry = Registry::from_site(&site, &mut Html);
+        let root = InternalPath::root();
+        assert_eq!(
+            registry.section_title(&root, &Id::new("fish")),
+            Some("Fish &amp; chips")
+        );
+
+        let mut output = BTreeMap::new();
+        site.build_into(&mut Html, &mut output, &mut PathBuf::new()).unwrap();
+        let home = &output[&InternalPath::parse("index.html")];
+        let home = String::from_utf8_lossy(home);
+        assert!(home.contains("href=\"#fish\">Fish &amp; chips</a>"));
+        assert_eq!(home.matches("id=\"pedia-footnote-ref-1\"").count(), 1);
+    }
+
+    #[test]
+    fn build_renders_with_registry() {
+        let mut site = Site { prerender: true, ..Site::default() };
+        site.root.insert_index(
+            InternalPath::root(),
+            Entry::Page(page_with_breadcrumbs("Home")),
+        );
+        site.root.insert_index(
+            InternalPath::parse("bar"),
+            Entry::Page(page_with_breadcrumbs("Bar")),
+        );
+
+        let mut output = BTreeMap::new();
+        site.build_into(&mut Text::default(), &mut output, &mut PathBuf::new())
+            .unwrap();
+        let bar = &output[&InternalPath::parse("bar/index.txt")];
+        assert!(String::from_utf8_lossy(bar).contains("Home > Bar\n"));
+    }
+
+    #[test]
+    fn pages_without_prerender() {
+        let mut site = Site::default();
+        site.root.insert_index(
+            InternalPath::root(),
+            Entry::Page(page_with_breadcrumbs("Home")),
+        );
+        site.root.insert_index(
+            InternalPath::parse("bar"),
+            Entry::Page(page_with_breadcrumbs("Bar")),
+        );
+
+        let registry = Registry::from_pages(&site);
+        assert!(registry.contains(&InternalLoc::parse("bar")));
+        assert!(!registry.contains(&InternalLoc::parse("qux")));
+        assert_eq!(registry.title(&InternalPath::parse("bar")), None);
+
+        let mut output = BTreeMap::new();
+        site.build_into(&mut Text::default(), &mut output, &mut PathBuf::new())
+            .unwrap();
+        let bar = &output[&InternalPath::parse("bar/index.txt")];
+        assert!(!String::from_utf8_lossy(bar).contains("Home > Bar"));
+    }
+
+    #[test]
+    fn build_fails_on_broken_auto_link() {
+        let page_with_breadcrumbs = |title: &'static str, target: &str| {
+            Page::new(
+                harray![],
+                String::from(title),
+                harray![],
+                Paragraph(AutoLink { location: InternalLoc::parse(target) }),
+                harray![],
+            )
+            .into_dyn()
+        };
+        let make_site = |target| {
+            let mut site = Site { prerender: true, ..Site::default() };
+            site.root.insert_index(
+                InternalPath::root(),
+                Entry::Page(page_with_breadcrumbs("Home", "bar")),
+            );
+            site.root.insert_index(
+                InternalPath::parse("bar"),
+                Entry::Page(page_with_breadcrumbs("Bar", target)),
+            );
+            site
+        };
+
+        let mut output = BTreeMap::new();
+        make_site("")
+            .build_into(&mut Text::default(), &mut output, &mut PathBuf::new())
+            .unwrap();
+        let home = &output[&InternalPath::parse("index.txt")];
+        assert!(String::from_utf8_lossy(home).ends_with("Home\n\nBar\n"));
+
+        let error = make_site("qux")
+            .build_into(&mut Text::default(), &mut output, &mut PathBuf::new())
+            .unwrap_err();
+        assert_eq!(error.path, InternalPath::parse("bar/index.txt"));
+        assert_eq!(
+            error.cause.to_string(),
+            "broken auto-titled link to \"qux\": no such page"
+        );
+    }
+}
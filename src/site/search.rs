@@ -0,0 +1,625 @@
+//! This module provides a static full-text search index of a site. The index
+//! is built from the text rendering of pages, written as JSON during the
+//! build, and can be queried either by the optional script generated along
+//! with it, or by [`SearchIndex::query`].
+
+use super::{Entry, Site};
+use crate::{
+    component::{asset::Script, page::PageComponent},
+    location::{Id, InternalPath, Location, INDEX_NAME},
+    render::{Context, Observer, Render, Renderer, Text},
+};
+use std::{
+    cell::RefCell,
+    collections::{BTreeMap, HashMap},
+    error::Error,
+    fmt,
+};
+
+/// Score of a term each time it appears in the title of a document.
+const TITLE_SCORE: u32 = 10;
+
+/// Score of a term each time it appears in the text of a document.
+const TEXT_SCORE: u32 = 1;
+
+/// Error when a page fails to render as text while indexing a site.
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct IndexError {
+    /// Path of the page.
+    pub path: InternalPath,
+}
+
+impl fmt::Display for IndexError {
+    fn fmt(&self, fmtr: &mut fmt::Formatter) -> fmt::Result {
+        write!(fmtr, "{}: failed to render page as text", self.path)
+    }
+}
+
+impl Error for IndexError {}
+
+/// A searchable document: either the top of a page (before any section with
+/// an ID), or a section with an ID, together with its untitled subsections.
+#[derive(Debug, Clone, PartialEq, Eq, PartialOrd, Ord, Hash)]
+pub struct SearchDocument {
+    /// Path of the page.
+    pub path: InternalPath,
+    /// ID of the section, if the document is a section.
+    pub id: Option<Id>,
+    /// Title of the page, or of the section.
+    pub title: String,
+}
+
+/// An occurrence of a term in a document.
+#[derive(Debug, Clone, Copy, PartialEq, Eq, PartialOrd, Ord, Hash)]
+pub struct Posting {
+    /// Index of the document in [`SearchIndex::documents`].
+    pub document: usize,
+    /// Score of the term in the document.
+    pub score: u32,
+}
+
+/// A document matching a query.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub struct SearchHit<'index> {
+    /// The matching document.
+    pub document: &'index SearchDocument,
+    /// Score of the document for the query.
+    pub score: u32,
+}
+
+/// A static inverted index of a site, mapping terms to pages and sections.
+///
+/// # HTML Classes
+///
+/// The script generated along with the index (see
+/// [`SearchIndex::script_path`]) appends a search box to the banner of the
+/// page:
+///
+/// - `pedia-search` attached to a `<form>` element.
+/// - `pedia-search-input` attached to an `<input>` element.
+/// - `pedia-search-results` attached to an `<ol>` element.
+/// - `pedia-search-result` attached to `<li>` elements.
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct SearchIndex {
+    /// Path of the index file in the build output, `search.json` by default.
+    pub path: InternalPath,
+    /// Path of the query script in the build output, if it should be
+    /// written. `search.js` by default. See [`SearchIndex::script`].
+    pub script_path: Option<InternalPath>,
+    /// Indexed documents.
+    pub documents: Vec<SearchDocument>,
+    /// Postings of each term, sorted by document.
+    pub terms: BTreeMap<String, Vec<Posting>>,
+}
+
+impl SearchIndex {
+    /// Indexes all pages of the given site, by rendering them as text.
+    pub fn from_site<P>(site: &Site<P>) -> Result<Self, IndexError>
+    where
+        P: Render<Text, Kind = PageComponent>,
+    {
+        let mut index = Self {
+            path: InternalPath::parse("search.json"),
+            script_path: Some(InternalPath::parse("search.js")),
+            documents: Vec::new(),
+            terms: BTreeMap::new(),
+        };
+
+        let mut pages = site
+            .root
+            .walk()
+            .filter_map(|(path, entry)| match entry {
+                Entry::Page(page) => Some((path, page)),
+                _ => None,
+            })
+            .collect::<Vec<_>>();
+        pages.sort_by(|(left, _), (right, _)| left.cmp(right));
+
+        for (path, page) in pages {
+            let collector = Collector::default();
+            let mut format = Text::default();
+            let mut target = CollectorWriter(&collector);
+            let mut renderer = Renderer::new(&mut format, &mut target);
+            let context =
+                Context::new(&path, &PageComponent).with_observer(&collector);
+            page.render(&mut renderer, context)
+                .map_err(|_| IndexError { path: path.clone() })?;
+            index.add_page(path, collector);
+        }
+
+        Ok(index)
+    }
+
+    /// Queries the index. Every term of the query must match (as a prefix)
+    /// a term of a document for the document to be yielded. Hits are sorted
+    /// by descending score.
+    pub fn query(&self, query: &str) -> Vec<SearchHit<'_>> {
+        let mut scores: Option<HashMap<usize, u32>> = None;
+        for query_term in tokenize(query) {
+            let mut term_scores = HashMap::new();
+            let matching = self
+                .terms
+                .range(query_term.clone() ..)
+                .take_while(|(term, _)| term.starts_with(&query_term));
+            for (_, postings) in matching {
+                for posting in postings {
+                    *term_scores.entry(posting.document).or_insert(0) +=
+                        posting.score;
+                }
+            }
+            scores = Some(match scores {
+                None => term_scores,
+                Some(scores) => scores
+                    .into_iter()
+                    .filter_map(|(document, score)| {
+                        let term_score = term_scores.get(&document)?;
+                        Some((document, score + term_score))
+                    })
+                    .collect(),
+            });
+        }
+
+        let mut hits =
+            scores.unwrap_or_default().into_iter().collect::<Vec<_>>();
+        hits.sort_by(|(left, left_score), (right, right_score)| {
+            right_score.cmp(left_score).then(left.cmp(right))
+        });
+        hits.into_iter()
+            .map(|(document, score)| SearchHit {
+                document: &self.documents[document],
+                score,
+            })
+            .collect()
+    }
+
+    /// A script asset loading the query script, if it is written. The script
+    /// appends a search box to the banner of pages including it.
+    pub fn script(&self) -> Option<Script> {
+        let path = self.script_path.clone()?;
+        Some(Script { location: Location::from(path) })
+    }
+
+    /// Generates the contents of the index file, as JSON.
+    pub fn render_json(&self) -> String {
+        let mut json = String::from("{\"documents\":[");
+        for (i, document) in self.documents.iter().enumerate() {
+            if i > 0 {
+                json.push(',');
+            }
+            json.push_str("{\"path\":");
+            push_json_string(&mut json, &url_path(&document.path));
+            if let Some(id) = &document.id {
+                json.push_str(",\"id\":");
+                push_json_string(&mut json, id.as_str());
+            }
+            json.push_str(",\"title\":");
+            push_json_string(&mut json, &document.title);
+            json.push('}');
+        }
+        json.push_str("],\"terms\":{");
+        for (i, (term, postings)) in self.terms.iter().enumerate() {
+            if i > 0 {
+                json.push(',');
+            }
+            push_json_string(&mut json, term);
+            json.push_str(":[");
+            for (j, posting) in postings.iter().enumerate() {
+                if j > 0 {
+                    json.push(',');
+                }
+                json.push_str(&format!(
+                    "[{},{}]",
+                    posting.document, posting.score
+                ));
+            }
+            json.push(']');
+        }
+        json.push_str("}}\n");
+        json
+    }
+
+    /// Generates the contents of the query script, if it is written.
+    pub fn render_script(&self) -> Option<String> {
+        let script_path = self.script_path.as_ref()?;
+        let mut root = String::new();
+        for _ in 0 .. script_path.dir_depth() {
+            root.push_str("../");
+        }
+        let mut script = String::from("(function () {\nvar root = ");
+        push_json_string(&mut script, &root);
+        script.push_str(";\nvar indexPath = ");
+        push_json_string(&mut script, &self.path.to_string());
+        script.push_str(";\n");
+        script.push_str(SCRIPT_BODY);
+        Some(script)
+    }
+
+    fn add_page(&mut self, path: InternalPath, collector: Collector) {
+        let text = collector.text.into_inner();
+        let sections = collector.sections.into_inner();
+        let page_title = collector.title.into_inner().unwrap_or_default();
+
+        let top_end = sections.first().map_or(text.len(), |mark| mark.start);
+        let top = &text[.. top_end];
+        let top_text = top
+            .find(page_title.trim())
+            .map_or(top, |start| &top[start + page_title.trim().len() ..]);
+        self.add_document(
+            SearchDocument { path: path.clone(), id: None, title: page_title },
+            top_text,
+        );
+
+        for (i, mark) in sections.iter().enumerate() {
+            let end = sections.get(i + 1).map_or(text.len(), |next| next.start);
+            self.add_document(
+                SearchDocument {
+                    path: path.clone(),
+                    id: Some(mark.id.clone()),
+                    title: mark.title.clone(),
+                },
+                &text[mark.body_start .. end],
+            );
+        }
+    }
+
+    fn add_document(&mut self, document: SearchDocument, text: &str) {
+        let mut scores = BTreeMap::<String, u32>::new();
+        for term in tokenize(&document.title) {
+            *scores.entry(term).or_insert(0) += TITLE_SCORE;
+        }
+        for term in tokenize(text) {
+            *scores.entry(term).or_insert(0) += TEXT_SCORE;
+        }
+        let index = self.documents.len();
+        self.documents.push(document);
+        for (term, score) in scores {
+            self.terms
+                .entry(term)
+                .or_default()
+                .push(Posting { document: index, score });
+        }
+    }
+}
+
+/// Observer collecting the text rendering of a page, its title, and where
+/// sections with IDs start in the text. Other IDs defined in the page (e.g.
+/// bibliography entries) do not start documents.
+#[derive(Debug, Default)]
+struct Collector {
+    text: RefCell<String>,
+    sections: RefCell<Vec<SectionMark>>,
+    title: RefCell<Option<String>>,
+}
+
+/// Where a section with an ID starts in the text collected from a page.
+#[derive(Debug)]
+struct SectionMark {
+    /// Start of the title of the section.
+    start: usize,
+    /// End of the title of the section, i.e. start of its body.
+    body_start: usize,
+    id: Id,
+    title: String,
+}
+
+impl Observer for Collector {
+    fn section(&self, id: &Id, title: &str) {
+        // The title was just rendered, so the text ends with it.
+        let text = self.text.borrow();
+        let body_start = text.len();
+        let start = text.rfind(title).unwrap_or(body_start);
+        self.sections.borrow_mut().push(SectionMark {
+            start,
+            body_start,
+            id: id.clone(),
+            title: title.to_owned(),
+        });
+    }
+
+    fn title(&self, title: &str) {
+        self.title.borrow_mut().get_or_insert_with(|| title.to_owned());
+    }
+}
+
+/// Writer appending to the text of a collector.
+#[derive(Debug)]
+struct CollectorWriter<'collector>(&'collector Collector);
+
+impl fmt::Write for CollectorWriter<'_> {
+    fn write_str(&mut self, input: &str) -> fmt::Result {
+        self.0.text.borrow_mut().push_str(input);
+        Ok(())
+    }
+}
+
+/// Splits text into lowercase terms of alphanumeric characters.
+fn tokenize(text: &str) -> impl Iterator<Item = String> + '_ {
+    text.split(|ch: char| !ch.is_alphanumeric())
+        .filter(|term| term.chars().count() > 1)
+        .map(str::to_lowercase)
+}
+
+/// Path of a page as an URL relative to the root of the site: [`INDEX_NAME`]
+/// is dropped.
+fn url_path(path: &InternalPath) -> String {
+    match path.fragments.split_last() {
+        Some((last, init)) if last.as_str() == INDEX_NAME => {
+            init.iter().map(|fragment| format!("{}/", fragment)).collect()
+        },
+        _ => path.to_string(),
+    }
+}
+
+fn push_json_string(json: &mut String, string: &str) {
+    json.push('"');
+    for ch in string.chars() {
+        match ch {
+            '"' => json.push_str("\\\""),
+            '\\' => json.push_str("\\\\"),
+            '\n' => json.push_str("\\n"),
+            '\r' => json.push_str("\\r"),
+            '\t' => json.push_str("\\t"),
+            '<' => json.push_str("\\u003c"),
+            ch if ch.is_control() => {
+                json.push_str(&format!("\\u{:04x}", u32::from(ch)))
+            },
+            _ => json.push(ch),
+        }
+    }
+    json.push('"');
+}
+
+/// Body of the query script, following the definitions of `root` (relative
+/// URL of the site's root from the script) and `indexPath`. Mirrors
+/// [`SearchIndex::query`].
+const SCRIPT_BODY: &str = r#"var script = document.currentScript;
+var rootUrl = new URL(root, script.src);
+var index = null;
+
+function tokenize(text) {
+    return text.toLowerCase().split(/[^\p{L}\p{N}]+/u).filter(function (term) {
+        return Array.from(term).length > 1;
+    });
+}
+
+function query(text) {
+    var scores = null;
+    tokenize(text).forEach(function (queryTerm) {
+        var termScores = new Map();
+        Object.keys(index.terms).forEach(function (term) {
+            if (term.startsWith(queryTerm)) {
+                index.terms[term].forEach(function (posting) {
+                    var score = termScores.get(posting[0]) || 0;
+                    termScores.set(posting[0], score + posting[1]);
+                });
+            }
+        });
+        if (scores === null) {
+            scores = termScores;
+        } else {
+            var next = new Map();
+            scores.forEach(function (score, document) {
+                if (termScores.has(document)) {
+                    next.set(document, score + termScores.get(document));
+                }
+            });
+            scores = next;
+        }
+    });
+    return Array.from(scores || []).sort(function (left, right) {
+        return right[1] - left[1] || left[0] - right[0];
+    });
+}
+
+function show(results, hits) {
+    results.textContent = "";
+    hits.slice(0, 20).forEach(function (hit) {
+        var document = index.documents[hit[0]];
+        var url = new URL(document.path, rootUrl);
+        if (document.id) {
+            url.hash = document.id;
+        }
+        var item = window.document.createElement("li");
+        item.className = "pedia-search-result";
+        var link = window.document.createElement("a");
+        link.className = "pedia-link";
+        link.href = url.href;
+        link.textContent = document.title || document.path;
+        item.appendChild(link);
+        results.appendChild(item);
+    });
+}
+
+window.addEventListener("DOMContentLoaded", function () {
+    var banner = window.document.getElementById("pedia-banner");
+    if (!banner) {
+        return;
+    }
+    var form = window.document.createElement("form");
+    form.className = "pedia-search";
+    var input = window.document.createElement("input");
+    input.className = "pedia-search-input";
+    input.type = "search";
+    var results = window.document.createElement("ol");
+    results.className = "pedia-search-results";
+    form.appendChild(input);
+    form.appendChild(results);
+    banner.appendChild(form);
+
+    form.addEventListener("submit", function (event) {
+        event.preventDefault();
+    });
+    input.addEventListener("input", function () {
+        var text = input.value;
+        var load = index
+            ? Promise.resolve()
+            : fetch(new URL(indexPath, rootUrl))
+                  .then(function (response) { return response.json(); })
+                  .then(function (json) { index = json; });
+        load.then(function () {
+            if (input.value === text) {
+                show(results, query(text));
+            }
+        });
+    });
+});
+})();
+"#;
+
+#[cfg(test)]
+mod test {
+    use super::SearchIndex;
+    use crate::{
+        component::{
+            block::{bibliography::Bibliography, text::Paragraph, InlineBlock},
+            inline::cite::Source,
+            page::{Page, PageComponent},
+            section::Section,
+        },
+        harray,
+        location::{Id, InternalPath},
+        render::{DynFullComponent, FullRender, Html},
+        site::{Entry, Site},
+    };
+    use std::{collections::BTreeMap, path::PathBuf, sync::Arc};
+
+    fn make_site() -> Site<DynFullComponent<'static, PageComponent>> {
+        let mut site = Site::default();
+        site.root.insert_index(
+            InternalPath::parse("rust"),
+            Entry::Page(
+                Page::new(
+                    InlineBlock("Banner"),
+                    String::from("Rust"),
+                    harray![],
+                    Paragraph("Rust is a programming language."),
+                    harray![
+                        Section {
+                            title: "Ownership",
+                            id: Some(Id::new("ownership")),
+                            body: Paragraph("Values have a single owner."),
+                            children: harray![],
+                        },
+                        Section {
+                            title: "Traits",
+                            id: Some(Id::new("traits")),
+                            body: Paragraph("Traits are like interfaces."),
+                            children: harray![],
+                        },
+                    ],
+                )
+                .into_dyn(),
+            ),
+        );
+        site.root.insert_index(
+            InternalPath::parse("haskell"),
+            Entry::Page(
+                Page::new(
+                    InlineBlock("Banner"),
+                    String::from("Haskell"),
+                    harray![],
+                    Paragraph("Haskell has type classes, like traits."),
+                    harray![],
+                )
+                .into_dyn(),
+            ),
+        );
+        site
+    }
+
+    fn hits(index: &SearchIndex, query: &str) -> Vec<String> {
+        index
+            .query(query)
+            .into_iter()
+            .map(|hit| match &hit.document.id {
+                Some(id) => format!("{}#{}", hit.document.path, id),
+                None => hit.document.path.to_string(),
+            })
+            .collect()
+    }
+
+    #[test]
+    fn documents_and_titles() {
+        let index = SearchIndex::from_site(&make_site()).unwrap();
+        let titles = index
+            .documents
+            .iter()
+            .map(|document| document.title.as_str())
+            .collect::<Vec<_>>();
+        assert_eq!(titles, ["Haskell", "Rust", "Ownership", "Traits"]);
+        assert!(!index.terms.contains_key("banner"));
+    }
+
+    #[test]
+    fn query_ranks_titles_first() {
+        let index = SearchIndex::from_site(&make_site()).unwrap();
+        assert_eq!(
+            hits(&index, "trait"),
+            ["rust/index.html#traits", "haskell/index.html"]
+        );
+        assert_eq!(hits(&index, "single OWNER"), ["rust/index.html#ownership"]);
+        assert!(hits(&index, "rust haskell").is_empty());
+        assert!(hits(&index, "").is_empty());
+    }
+
+    #[test]
+    fn bibliography_entries_not_documents() {
+        let mut site = make_site();
+        let source = Arc::new(Source {
+            key: Id::new("borrow"),
+            authors: vec![String::from("Ferris")],
+            title: String::from("On borrowing"),
+            year: Some(2015),
+            publisher: None,
+            url: None,
+        });
+        site.root.insert_index(
+            InternalPath::parse("papers"),
+            Entry::Page(
+                Page::new(
+                    InlineBlock("Banner"),
+                    String::from("Papers"),
+                    harray![],
+                    Paragraph("Papers about Rust."),
+                    harray![Section {
+                        title: "References",
+                        id: Some(Id::new("references")),
+                        body: Bibliography { sources: Some(vec![source]) },
+                        children: harray![],
+                    }],
+                )
+                .into_dyn(),
+            ),
+        );
+
+        let index = SearchIndex::from_site(&site).unwrap();
+        let titles = index
+            .documents
+            .iter()
+            .map(|document| document.title.as_str())
+            .collect::<Vec<_>>();
+        assert_eq!(
+            titles,
+            ["Haskell", "Papers", "References", "Rust", "Ownership", "Traits"]
+        );
+        assert_eq!(hits(&index, "borrowing"), ["papers/index.html#references"]);
+        assert_eq!(
+            hits(&index, "references"),
+            ["papers/index.html#references"]
+        );
+    }
+
+    #[test]
+    fn written_during_build() {
+        let mut site = make_site();
+        site.search = Some(SearchIndex::from_site(&site).unwrap());
+
+        let mut output = BTreeMap::new();
+        site.build_into(&mut Html, &mut output, &mut PathBuf::new()).unwrap();
+        let json = &output[&InternalPath::parse("search.json")];
+        assert!(String::from_utf8_lossy(json).starts_with(
+            "{\"documents\":[{\"path\":\"haskell/\",\"title\":\"Haskell\"}"
+        ));
+        assert!(output.contains_key(&InternalPath::parse("search.js")));
+    }
+}
@@ -0,0 +1,537 @@
+//! This module provides a local preview HTTP server for a site. Pages are
+//! rendered on each request straight from memory, and resources are read from
+//! the resource directory on each request, so no build directory is written.
+
+use super::{build_page, fs_path, registry::Registry, Entry, Site};
+use crate::{
+    component::{page::PageComponent, Component},
+    location::{Fragment, InternalPath, INDEX_NAME},
+    render::{self, Render},
+};
+use percent_encoding::percent_decode_str;
+use std::{
+    fs,
+    io::{self, BufRead, BufReader, Read, Write},
+    net::{TcpListener, TcpStream},
+    path::Path,
+    time::Duration,
+};
+
+/// Name of the page used as the "not found" page, if present at the root of
+/// the site.
+pub const NOT_FOUND_PAGE: &str = "404.html";
+
+/// Read and write timeout of a connection, so a client sending or reading
+/// nothing does not hold the server forever.
+pub const CONNECTION_TIMEOUT: Duration = Duration::from_secs(10);
+
+/// Maximum length in bytes of the request line and of each header of a
+/// request, so a client cannot make the server buffer without bound.
+pub const MAX_LINE_LENGTH: u64 = 8192;
+
+/// Maximum number of headers of a request.
+pub const MAX_HEADERS: usize = 100;
+
+/// A response of the preview server.
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct Response {
+    /// HTTP status code.
+    pub status: u16,
+    /// Value of the `Content-Type` header.
+    pub content_type: &'static str,
+    /// Value of the `Location` header, for redirections.
+    pub location: Option<String>,
+    /// Body of the response.
+    pub body: Vec<u8>,
+}
+
+impl Response {
+    fn new(status: u16, content_type: &'static str, body: Vec<u8>) -> Self {
+        Self { status, content_type, location: None, body }
+    }
+
+    fn redirect(location: String) -> Self {
+        Self {
+            status: 301,
+            content_type: "text/plain; charset=utf-8",
+            location: Some(location),
+            body: Vec::new(),
+        }
+    }
+
+    /// Reason phrase of the status code.
+    pub fn reason(&self) -> &'static str {
+        match self.status {
+            200 => "OK",
+            301 => "Moved Permanently",
+            400 => "Bad Request",
+            404 => "Not Found",
+            405 => "Method Not Allowed",
+            500 => "Internal Server Error",
+            _ => "Unknown",
+        }
+    }
+
+    /// Writes this response as HTTP/1.1 into the given output, with or
+    /// without the body.
+    pub fn write_to<O>(&self, output: &mut O, with_body: bool) -> io::Result<()>
+    where
+        O: Write + ?Sized,
+    {
+        write!(output, "HTTP/1.1 {} {}\r\n", self.status, self.reason())?;
+        write!(output, "Content-Type: {}\r\n", self.content_type)?;
+        write!(output, "Content-Length: {}\r\n", self.body.len())?;
+        if let Some(location) = &self.location {
+            write!(output, "Location: {}\r\n", location)?;
+        }
+        write!(output, "Connection: close\r\n\r\n")?;
+        if with_body {
+            output.write_all(&self.body)?;
+        }
+        output.flush()
+    }
+}
+
+impl<P> Site<P>
+where
+    P: Component<Kind = PageComponent>,
+{
+    /// Serves this site over HTTP using the given listener, forever.
+    /// Connections are handled one at a time, each one with
+    /// [`CONNECTION_TIMEOUT`] as read and write timeout, and connections
+    /// failing to be accepted are skipped. The site registry is gathered once,
+    /// before serving (see [`Site::prerender`]). See [`Site::respond`] for how
+    /// requests are answered.
+    pub fn serve<W>(
+        &self,
+        format: &mut W,
+        listener: &TcpListener,
+        resource_dir: &Path,
+    ) -> io::Result<()>
+    where
+        W: render::Format + ?Sized,
+        P: Render<W>,
+    {
+        let registry = self.registry(format);
+        for stream in listener.incoming() {
+            // A misbehaving client should not take the server down.
+            let Ok(stream) = stream else {
+                continue;
+            };
+            let timeout = Some(CONNECTION_TIMEOUT);
+            if stream.set_read_timeout(timeout).is_err()
+                || stream.set_write_timeout(timeout).is_err()
+            {
+                continue;
+            }
+            let _ = self.handle(format, &registry, stream, resource_dir);
+        }
+        Ok(())
+    }
+
+    /// Answers a single request for the given (percent-encoded) URL path,
+    /// given the registry of this site (see [`Site::prerender`]).
+    ///
+    /// - A page is rendered with the given format, at the path of its file in
+    ///   the format (see [`InternalPath::for_format`]), e.g. `foo/index.md`
+    ///   for the page `foo/index.html` in Markdown.
+    /// - A directory is resolved to its `index.html` page; directory URLs
+    ///   without a trailing slash are redirected to the URL with it, so
+    ///   relative links work.
+    /// - A resource is read from the resource directory.
+    /// - Anything else yields 404 with the page [`NOT_FOUND_PAGE`] at the
+    ///   root of the site, if any, or a plain message.
+    pub fn respond<W>(
+        &self,
+        format: &mut W,
+        registry: &Registry,
+        url_path: &str,
+        resource_dir: &Path,
+    ) -> Response
+    where
+        W: render::Format + ?Sized,
+        P: Render<W>,
+    {
+        let query_start = url_path.find(['?', '#']).unwrap_or(url_path.len());
+        let (url_path, query) = url_path.split_at(query_start);
+        let Ok(decoded) = percent_decode_str(url_path).decode_utf8() else {
+            return self.not_found(format, registry);
+        };
+        let trimmed = decoded.trim_matches('/');
+        let Ok(path) = InternalPath::try_parse(trimmed) else {
+            return self.not_found(format, registry);
+        };
+
+        if let Some((path, page)) = self.page_for_file(format, &path) {
+            return self.render_page(page, format, registry, &path);
+        }
+        match self.root.get(&path) {
+            Some(Entry::Directory(_))
+                if !path.is_root() && !url_path.ends_with('/') =>
+            {
+                Response::redirect(format!("{}/{}", url_path, query))
+            },
+            Some(Entry::Directory(_)) => match self.root.resolve_page(&path) {
+                Some((path, page)) => {
+                    self.render_page(page, format, registry, &path)
+                },
+                None => self.not_found(format, registry),
+            },
+            Some(Entry::Resource) => {
+                match fs::read(fs_path(resource_dir, &path)) {
+                    Ok(body) => {
+                        Response::new(200, content_type(extension(&path)), body)
+                    },
+                    Err(_) => self.not_found(format, registry),
+                }
+            },
+            // Pages are only found at the path of their file in the format.
+            Some(Entry::Page(_)) | None => self.not_found(format, registry),
+        }
+    }
+
+    /// Finds the page whose file, when rendered in the given format, is at
+    /// the given path, yielding the path of the page in the site along with
+    /// the page.
+    fn page_for_file<W>(
+        &self,
+        format: &W,
+        file_path: &InternalPath,
+    ) -> Option<(InternalPath, &P)>
+    where
+        W: render::Format + ?Sized,
+    {
+        let mut path = file_path.clone();
+        let last = path.fragments.last_mut()?;
+        let name = if last.as_str() == format.index_name() {
+            String::from(INDEX_NAME)
+        } else {
+            let extension = format!(".{}", format.extension());
+            let stem = last.as_str().strip_suffix(&extension)?;
+            format!("{}.html", stem)
+        };
+        *last = Fragment::try_new(name).ok()?;
+        match self.root.get(&path)? {
+            Entry::Page(page) if path.for_format(format) == *file_path => {
+                Some((path, page))
+            },
+            _ => None,
+        }
+    }
+
+    /// Renders a page of this site.
+    fn render_page<W>(
+        &self,
+        page: &P,
+        format: &mut W,
+        registry: &Registry,
+        path: &InternalPath,
+    ) -> Response
+    where
+        W: render::Format + ?Sized,
+        P: Render<W>,
+    {
+        let mut body = Vec::new();
+        let result = build_page(
+            page,
+            format,
+            path,
+            self.citation_style,
+            registry,
+            &mut body,
+        );
+        match result {
+            Ok(()) => {
+                Response::new(200, content_type(format.extension()), body)
+            },
+            Err(_) => Response::new(
+                500,
+                "text/plain; charset=utf-8",
+                b"500 Internal Server Error".to_vec(),
+            ),
+        }
+    }
+
+    fn not_found<W>(&self, format: &mut W, registry: &Registry) -> Response
+    where
+        W: render::Format + ?Sized,
+        P: Render<W>,
+    {
+        let path = InternalPath::root().append(Fragment::new(NOT_FOUND_PAGE));
+        let mut response = match self.root.get(&path) {
+            Some(Entry::Page(page)) => {
+                self.render_page(page, format, registry, &path)
+            },
+            _ => Response::new(
+                404,
+                "text/plain; charset=utf-8",
+                b"404 Not Found".to_vec(),
+            ),
+        };
+        if response.status == 200 {
+            response.status = 404;
+        }
+        response
+    }
+
+    fn handle<W>(
+        &self,
+        format: &mut W,
+        registry: &Registry,
+        mut stream: TcpStream,
+        resource_dir: &Path,
+    ) -> io::Result<()>
+    where
+        W: render::Format + ?Sized,
+        P: Render<W>,
+    {
+        let bad_request = || {
+            Response::new(
+                400,
+                "text/plain; charset=utf-8",
+                b"400 Bad Request".to_vec(),
+            )
+        };
+        let request_line = match read_head(&mut BufReader::new(&mut stream)) {
+            Ok(request_line) => request_line,
+            Err(error) if error.kind() == io::ErrorKind::InvalidData => {
+                return bad_request().write_to(&mut stream, true);
+            },
+            Err(error) => return Err(error),
+        };
+
+        let mut parts = request_line.split_whitespace();
+        let (method, url_path) = match (parts.next(), parts.next()) {
+            (Some(method), Some(url_path)) => (method, url_path),
+            _ => return bad_request().write_to(&mut stream, true),
+        };
+
+        let response = match method {
+            "GET" | "HEAD" => {
+                self.respond(format, registry, url_path, resource_dir)
+            },
+            _ => Response::new(
+                405,
+                "text/plain; charset=utf-8",
+                b"405 Method Not Allowed".to_vec(),
+            ),
+        };
+        response.write_to(&mut stream, method != "HEAD")
+    }
+}
+
+/// Reads the head of a request, i.e. its request line followed by headers up
+/// to an empty line, yielding the request line, since headers are ignored.
+/// Lines longer than [`MAX_LINE_LENGTH`] or more than [`MAX_HEADERS`] headers
+/// yield an invalid data error.
+fn read_head<R>(reader: &mut R) -> io::Result<String>
+where
+    R: BufRead,
+{
+    let mut request_line = String::new();
+    read_line(reader, &mut request_line)?;
+    let mut header = String::new();
+    for _ in 0 ..= MAX_HEADERS {
+        header.clear();
+        if read_line(reader, &mut header)? == 0 || header.trim_end().is_empty()
+        {
+            return Ok(request_line);
+        }
+    }
+    Err(io::Error::new(io::ErrorKind::InvalidData, "too many headers"))
+}
+
+/// Reads a line of a request into the given buffer, yielding the number of
+/// bytes read, or an invalid data error if the line is longer than
+/// [`MAX_LINE_LENGTH`].
+fn read_line<R>(reader: &mut R, line: &mut String) -> io::Result<usize>
+where
+    R: BufRead,
+{
+    let read = reader.by_ref().take(MAX_LINE_LENGTH).read_line(line)?;
+    if read as u64 == MAX_LINE_LENGTH && !line.ends_with('\n') {
+        return Err(io::Error::new(
+            io::ErrorKind::InvalidData,
+            "line too long",
+        ));
+    }
+    Ok(read)
+}
+
+/// Extension of the file at the given path, empty if none.
+fn extension(path: &InternalPath) -> &str {
+    let name = path.fragments.last().map_or("", Fragment::as_str);
+    name.rsplit_once('.').map_or("", |(_, extension)| extension)
+}
+
+/// Guesses the content type of a file from its extension.
+fn content_type(extension: &str) -> &'static str {
+    match extension.to_ascii_lowercase().as_str() {
+        "html" | "htm" => "text/html; charset=utf-8",
+        "css" => "text/css; charset=utf-8",
+        "js" | "mjs" => "text/javascript; charset=utf-8",
+        "json" => "application/json",
+        "xml" => "application/xml",
+        "md" => "text/markdown; charset=utf-8",
+        "txt" => "text/plain; charset=utf-8",
+        "gmi" => "text/gemini; charset=utf-8",
+        "tex" => "application/x-tex; charset=utf-8",
+        "svg" => "image/svg+xml",
+        "png" => "image/png",
+        "jpg" | "jpeg" => "image/jpeg",
+        "gif" => "image/gif",
+        "webp" => "image/webp",
+        "ico" => "image/x-icon",
+        "ogg" | "oga" => "audio/ogg",
+        "mp3" => "audio/mpeg",
+        "wav" => "audio/wav",
+        "woff" => "font/woff",
+        "woff2" => "font/woff2",
+        "pdf" => "application/pdf",
+        _ => "application/octet-stream",
+    }
+}
+
+#[cfg(test)]
+mod test {
+    use super::read_head;
+    use crate::{
+        component::page::PageComponent,
+        location::InternalPath,
+        render::{DynFullComponent, Html, Markdown},
+        site::{
+            registry::Registry,
+            test::{make_page, make_site, temp_dir},
+            Entry,
+            Site,
+        },
+    };
+    use std::{fs, io};
+
+    fn served_site() -> Site<DynFullComponent<'static, PageComponent>> {
+        let mut site = make_site(&[("", "Home"), ("foo bar", "Foo")]);
+        site.root.insert_path(
+            &InternalPath::parse("styles/main.css"),
+            Entry::Resource,
+        );
+        site
+    }
+
+    #[test]
+    fn serves_pages() {
+        let site = served_site();
+        let resources = temp_dir("serve-pages");
+        let registry = Registry::from_site(&site, &mut Html);
+
+        let response = site.respond(&mut Html, &registry, "/", &resources);
+        assert_eq!(response.status, 200);
+        assert_eq!(response.content_type, "text/html; charset=utf-8");
+        assert!(String::from_utf8(response.body).unwrap().contains("Home"));
+
+        let response =
+            site.respond(&mut Html, &registry, "/foo%20bar/?q=1", &resources);
+        assert_eq!(response.status, 200);
+        assert!(String::from_utf8(response.body).unwrap().contains("Foo"));
+
+        let response =
+            site.respond(&mut Html, &registry, "/foo%20bar", &resources);
+        assert_eq!(response.status, 301);
+        assert_eq!(response.location.as_deref(), Some("/foo%20bar/"));
+
+        let response =
+            site.respond(&mut Html, &registry, "/foo%20bar?x=1", &resources);
+        assert_eq!(response.status, 301);
+        assert_eq!(response.location.as_deref(), Some("/foo%20bar/?x=1"));
+    }
+
+    #[test]
+    fn serves_pages_under_format_names() {
+        let site = served_site();
+        let resources = temp_dir("serve-format-names");
+        let mut format = Markdown::default();
+        let registry = Registry::from_site(&site, &mut format);
+
+        let response =
+            site.respond(&mut format, &registry, "/index.md", &resources);
+        assert_eq!(response.status, 200);
+        assert_eq!(response.content_type, "text/markdown; charset=utf-8");
+        assert!(String::from_utf8(response.body).unwrap().contains("Home"));
+
+        let response = site.respond(
+            &mut format,
+            &registry,
+            "/foo%20bar/index.md",
+            &resources,
+        );
+        assert_eq!(response.status, 200);
+        assert!(String::from_utf8(response.body).unwrap().contains("Foo"));
+
+        let response =
+            site.respond(&mut format, &registry, "/foo%20bar/", &resources);
+        assert_eq!(response.status, 200);
+        assert_eq!(response.content_type, "text/markdown; charset=utf-8");
+
+        let response = site.respond(
+            &mut format,
+            &registry,
+            "/foo%20bar/index.html",
+            &resources,
+        );
+        assert_eq!(response.status, 404);
+    }
+
+    #[test]
+    fn serves_resources() {
+        let site = served_site();
+        let resources = temp_dir("serve-resources");
+        let registry = Registry::from_site(&site, &mut Html);
+        fs::create_dir_all(resources.join("styles")).unwrap();
+        fs::write(resources.join("styles/main.css"), "body {}").unwrap();
+
+        let response =
+            site.respond(&mut Html, &registry, "/styles/main.css", &resources);
+        assert_eq!(response.status, 200);
+        assert_eq!(response.content_type, "text/css; charset=utf-8");
+        assert_eq!(response.body, b"body {}");
+
+        fs::remove_dir_all(resources).unwrap();
+    }
+
+    #[test]
+    fn bounded_request_heads() {
+        let head = "GET / HTTP/1.1\r\nHost: localhost\r\n\r\nbody";
+        assert_eq!(
+            read_head(&mut head.as_bytes()).unwrap(),
+            "GET / HTTP/1.1\r\n"
+        );
+
+        let long_line = format!("GET /{} HTTP/1.1\r\n\r\n", "a".repeat(10000));
+        let error = read_head(&mut long_line.as_bytes()).unwrap_err();
+        assert_eq!(error.kind(), io::ErrorKind::InvalidData);
+
+        let many_headers =
+            format!("GET / HTTP/1.1\r\n{}\r\n", "X-Foo: bar\r\n".repeat(101));
+        let error = read_head(&mut many_headers.as_bytes()).unwrap_err();
+        assert_eq!(error.kind(), io::ErrorKind::InvalidData);
+    }
+
+    #[test]
+    fn not_found() {
+        let mut site = served_site();
+        let resources = temp_dir("serve-not-found");
+
+        let registry = Registry::from_site(&site, &mut Html);
+        let response = site.respond(&mut Html, &registry, "/nope", &resources);
+        assert_eq!(response.status, 404);
+        assert_eq!(response.body, b"404 Not Found");
+
+        site.root.insert_path(
+            &InternalPath::parse("404.html"),
+            Entry::Page(make_page("Lost")),
+        );
+        let registry = Registry::from_site(&site, &mut Html);
+        let response = site.respond(&mut Html, &registry, "/nope", &resources);
+        assert_eq!(response.status, 404);
+        assert!(String::from_utf8(response.body).unwrap().contains("Lost"));
+    }
+}
@@ -0,0 +1,308 @@
+//! This module provides generation of a `sitemap.xml` for a site, following
+//! the [sitemaps protocol](https://www.sitemaps.org/protocol.html).
+
+use super::{escape_xml, page_url, registry::Registry, Site};
+use crate::{
+    component::{page::PageComponent, Component},
+    location::InternalPath,
+};
+use std::{collections::HashMap, error::Error, fmt};
+use url::Url;
+
+/// Name of the sitemap file, written at the root of the build output.
+pub const SITEMAP_NAME: &str = "sitemap.xml";
+
+/// Error when an invalid priority is given.
+#[derive(Debug, Clone)]
+pub struct InvalidPriority;
+
+impl fmt::Display for InvalidPriority {
+    fn fmt(&self, fmtr: &mut fmt::Formatter) -> fmt::Result {
+        fmtr.write_str("Priority must be between 0.0 and 1.0")
+    }
+}
+
+impl Error for InvalidPriority {}
+
+/// Priority of a page relative to other pages of the site, between 0.0 and
+/// 1.0, with a precision of thousandths.
+#[derive(Debug, Clone, Copy, PartialEq, Eq, PartialOrd, Ord, Hash)]
+pub struct Priority {
+    thousandths: u16,
+}
+
+impl Priority {
+    /// Creates a priority from a number between 0.0 and 1.0 (inclusive).
+    pub fn try_new(priority: f64) -> Result<Self, InvalidPriority> {
+        if (0.0 ..= 1.0).contains(&priority) {
+            Ok(Self { thousandths: (priority * 1000.0).round() as u16 })
+        } else {
+            Err(InvalidPriority)
+        }
+    }
+
+    /// Creates a priority from a number between 0.0 and 1.0 (inclusive).
+    ///
+    /// # Panic
+    ///
+    /// Panics if the priority is out of range (for a non-panicking version,
+    /// see [`Priority::try_new`]).
+    pub fn new(priority: f64) -> Self {
+        Self::try_new(priority).expect("failed creating Priority")
+    }
+}
+
+impl fmt::Display for Priority {
+    fn fmt(&self, fmtr: &mut fmt::Formatter) -> fmt::Result {
+        let decimals = format!("{:03}", self.thousandths % 1000);
+        let decimals = decimals.trim_end_matches('0');
+        write!(
+            fmtr,
+            "{}.{}",
+            self.thousandths / 1000,
+            if decimals.is_empty() { "0" } else { decimals }
+        )
+    }
+}
+
+/// Optional metadata of a page in the sitemap, taking precedence over the
+/// metadata of the page itself (see [`Site::render_sitemap`]).
+#[derive(Debug, Clone, PartialEq, Eq, Default)]
+pub struct PageMeta {
+    /// Date of last modification, in W3C Datetime format, e.g. `2024-01-31`.
+    pub lastmod: Option<String>,
+    /// Priority of the page relative to the other pages.
+    pub priority: Option<Priority>,
+}
+
+/// Configuration of the sitemap of a site.
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct Sitemap {
+    /// Absolute URL where the root of the site is deployed, e.g.
+    /// `https://example.com/wiki/`. Must be usable as a base (e.g. an HTTP
+    /// URL).
+    pub base: Url,
+    /// Metadata of pages, mapped by their paths (e.g. `foo/index.html`).
+    /// Pages without metadata are listed with their URL only.
+    pub pages: HashMap<InternalPath, PageMeta>,
+}
+
+impl Sitemap {
+    /// Creates a sitemap configuration with the given base URL and no page
+    /// metadata.
+    pub fn new(base: Url) -> Self {
+        Self { base, pages: HashMap::new() }
+    }
+
+    /// Sets the metadata of the page at the given path. Returns the modified
+    /// configuration.
+    pub fn with_page(mut self, path: InternalPath, meta: PageMeta) -> Self {
+        self.pages.insert(path, meta);
+        self
+    }
+
+    /// Absolute URL of a page given its path. A path ending in `index.html`
+    /// becomes the URL of its directory.
+    pub fn url(&self, path: &InternalPath) -> Url {
+        page_url(&self.base, path)
+    }
+}
+
+impl<P> Site<P>
+where
+    P: Component<Kind = PageComponent>,
+{
+    /// Generates the contents of the `sitemap.xml` of this site, listing
+    /// every page with the given configuration, sorted by path. Pages whose
+    /// date of last modification is not configured take it from their
+    /// metadata in the given registry: their update date, or else their
+    /// creation date.
+    pub fn render_sitemap(
+        &self,
+        sitemap: &Sitemap,
+        registry: &Registry,
+    ) -> String {
+        let mut paths = self
+            .root
+            .walk()
+            .filter(|(_, entry)| entry.is_page())
+            .map(|(path, _)| path)
+            .collect::<Vec<_>>();
+        paths.sort();
+
+        let mut xml = String::from(
+            "<?xml version=\"1.0\" encoding=\"UTF-8\"?>\n<urlset \
+             xmlns=\"http://www.sitemaps.org/schemas/sitemap/0.9\">\n",
+        );
+        for path in paths {
+            xml.push_str("<url><loc>");
+            escape_xml(&mut xml, sitemap.url(&path).as_str());
+            xml.push_str("</loc>");
+            let meta = sitemap.pages.get(&path);
+            let lastmod =
+                meta.and_then(|meta| meta.lastmod.as_ref()).or_else(|| {
+                    let metadata = registry.metadata(&path)?;
+                    metadata.updated.as_ref().or(metadata.created.as_ref())
+                });
+            if let Some(lastmod) = lastmod {
+                xml.push_str("<lastmod>");
+                escape_xml(&mut xml, lastmod);
+                xml.push_str("</lastmod>");
+            }
+            if let Some(priority) = meta.and_then(|meta| meta.priority) {
+                xml.push_str(&format!("<priority>{}</priority>", priority));
+            }
+            xml.push_str("</url>\n");
+        }
+        xml.push_str("</urlset>\n");
+        xml
+    }
+}
+
+#[cfg(test)]
+mod test {
+    use super::{PageMeta, Priority, Sitemap};
+    use crate::{
+        component::{
+            block::{text::Paragraph, InlineBlock},
+            page::{Page, PageComponent, PageMetadata},
+        },
+        harray,
+        location::{Fragment, InternalPath},
+        render::{DynFullComponent, FullRender, Html, Text},
+        site::{
+            registry::Registry,
+            test::{make_page, make_site},
+            Entry,
+            Site,
+        },
+    };
+    use std::{collections::BTreeMap, path::PathBuf};
+    use url::Url;
+
+    fn sitemap_site() -> Site<DynFullComponent<'static, PageComponent>> {
+        let mut site = make_site(&[("", "Page"), ("foo bar", "Page")]);
+        site.root.insert_path(
+            &InternalPath::parse("baz.html"),
+            Entry::Page(make_page("Page")),
+        );
+        site.root
+            .insert_path(&InternalPath::parse("main.css"), Entry::Resource);
+        site
+    }
+
+    #[test]
+    fn priority_display() {
+        assert_eq!(Priority::new(0.8).to_string(), "0.8");
+        assert_eq!(Priority::new(1.0).to_string(), "1.0");
+        assert_eq!(Priority::new(0.125).to_string(), "0.125");
+        assert!(Priority::try_new(1.5).is_err());
+    }
+
+    #[test]
+    fn sitemap_urls() {
+        let base = Url::parse("https://example.com/wiki").unwrap();
+        let sitemap = Sitemap::new(base).with_page(
+            InternalPath::parse("baz.html"),
+            PageMeta {
+                lastmod: Some(String::from("2024-01-31")),
+                priority: Some(Priority::new(0.5)),
+            },
+        );
+        let xml = sitemap_site().render_sitemap(&sitemap, &Registry::new());
+        assert_eq!(
+            xml,
+            "<?xml version=\"1.0\" encoding=\"UTF-8\"?>\n<urlset \
+             xmlns=\"http://www.sitemaps.org/schemas/sitemap/0.9\">\n\
+             <url><loc>https://example.com/wiki/baz.html</loc>\
+             <lastmod>2024-01-31</lastmod><priority>0.5</priority></url>\n\
+             <url><loc>https://example.com/wiki/foo%20bar/</loc></url>\n\
+             <url><loc>https://example.com/wiki/</loc></url>\n\
+             </urlset>\n"
+        );
+    }
+
+    #[test]
+    fn sitemap_in_build() {
+        let mut site = sitemap_site();
+        site.root.entries.remove(&Fragment::new("main.css"));
+        site.sitemap =
+            Some(Sitemap::new(Url::parse("https://example.com/").unwrap()));
+
+        let mut output = BTreeMap::new();
+        site.build_into(&mut Html, &mut output, &mut PathBuf::new()).unwrap();
+        let xml = &output[&InternalPath::parse("sitemap.xml")];
+        assert!(String::from_utf8_lossy(xml)
+            .contains("<loc>https://example.com/</loc>"));
+
+        let mut output = BTreeMap::new();
+        site.build_into(&mut Text::default(), &mut output, &mut PathBuf::new())
+            .unwrap();
+        assert!(!output.contains_key(&InternalPath::parse("sitemap.xml")));
+    }
+
+    #[test]
+    fn lastmod_from_metadata() {
+        let mut site = sitemap_site();
+        site.root.entries.remove(&Fragment::new("main.css"));
+        site.prerender = true;
+        let page = Page {
+            metadata: Some(PageMetadata {
+                created: Some(String::from("2024-01-01")),
+                updated: Some(String::from("2024-02-01")),
+                ..PageMetadata::default()
+            }),
+            ..Page::new(
+                InlineBlock("Banner"),
+                String::from("Page"),
+                harray![],
+                Paragraph("Hello"),
+                harray![],
+            )
+        };
+        site.root.entries.insert(
+            Fragment::new("baz.html"),
+            Entry::Page(page.clone().into_dyn()),
+        );
+        site.root.entries.insert(
+            Fragment::new("qux.html"),
+            Entry::Page(
+                Page {
+                    metadata: Some(PageMetadata {
+                        created: Some(String::from("2024-01-01")),
+                        ..PageMetadata::default()
+                    }),
+                    ..page
+                }
+                .into_dyn(),
+            ),
+        );
+        site.sitemap = Some(
+            Sitemap::new(Url::parse("https://example.com/").unwrap())
+                .with_page(
+                    InternalPath::parse("index.html"),
+                    PageMeta {
+                        lastmod: Some(String::from("2024-03-01")),
+                        priority: None,
+                    },
+                ),
+        );
+
+        let mut output = BTreeMap::new();
+        site.build_into(&mut Html, &mut output, &mut PathBuf::new()).unwrap();
+        let xml = &output[&InternalPath::parse("sitemap.xml")];
+        assert_eq!(
+            String::from_utf8_lossy(xml),
+            "<?xml version=\"1.0\" encoding=\"UTF-8\"?>\n<urlset \
+             xmlns=\"http://www.sitemaps.org/schemas/sitemap/0.9\">\n\
+             <url><loc>https://example.com/baz.html</loc>\
+             <lastmod>2024-02-01</lastmod></url>\n\
+             <url><loc>https://example.com/foo%20bar/</loc></url>\n\
+             <url><loc>https://example.com/</loc>\
+             <lastmod>2024-03-01</lastmod></url>\n\
+             <url><loc>https://example.com/qux.html</loc>\
+             <lastmod>2024-01-01</lastmod></url>\n\
+             </urlset>\n"
+        );
+    }
+}
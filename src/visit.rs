@@ -0,0 +1,432 @@
+//! This module exports items related to visiting components, i.e. walking over
+//! a tree of components without rendering it.
+
+use crate::{
+    component::{
+        block::{media::Image, table::CellAttrs, toc::TocEntry},
+        inline::{cite::Citation, media::Audio},
+        page::PageMetadata,
+        Component,
+        ComponentKind,
+    },
+    hseq::coproduct::{Cocons, Conil},
+    location::{Id, InternalLoc, InternalPath, Location},
+    render::Context,
+};
+use std::{rc::Rc, sync::Arc};
+
+/// What a visitor wants to do after entering a node.
+#[derive(Debug, Clone, Copy, PartialEq, Eq, PartialOrd, Ord, Hash, Default)]
+pub enum Flow {
+    /// Visit the children of the node.
+    #[default]
+    Continue,
+    /// Skip the children of the node, but still leave the node.
+    Skip,
+}
+
+/// A node of a component tree, as seen by a visitor. Each builtin component
+/// has its own variant, carrying its data other than its child components.
+/// Containers (e.g. arrays, vectors, coproducts) are transparent and do not
+/// yield nodes.
+#[derive(Debug, Clone, Copy)]
+#[non_exhaustive]
+pub enum Node<'comp> {
+    /// Plain text.
+    Text(&'comp str),
+    /// A location rendered by itself.
+    Location(&'comp Location),
+    /// An internal location rendered by itself.
+    InternalLoc(&'comp InternalLoc),
+    /// An internal path rendered by itself.
+    InternalPath(&'comp InternalPath),
+    /// An ID rendered by itself.
+    Id(&'comp Id),
+    /// Bold text.
+    Bold,
+    /// Italic text.
+    Italic,
+    /// Preformatted text.
+    Preformatted,
+    /// A link to the given location.
+    Link(&'comp Location),
+    /// A link to the given internal location, labelled with the title of its
+    /// target.
+    AutoLink(&'comp InternalLoc),
+    /// Audio.
+    Audio(&'comp Audio),
+    /// A footnote, wrapping the footnote's contents.
+    Footnote,
+    /// A citation.
+    Cite(&'comp Citation),
+    /// A math formula, given as TeX.
+    Math(&'comp str),
+    /// Inline components wrapped as a block.
+    InlineBlock,
+    /// A paragraph.
+    Paragraph,
+    /// An unordered list.
+    UnorderedList,
+    /// An ordered list.
+    OrderedList,
+    /// An image.
+    Image(&'comp Image),
+    /// A figure, whose image is given.
+    Figure(&'comp Image),
+    /// A table.
+    Table,
+    /// A table with a caption.
+    CaptionedTable,
+    /// A row of a table.
+    Row,
+    /// A cell of a table.
+    Cell(&'comp CellAttrs),
+    /// A section with an optional ID.
+    Section(Option<&'comp Id>),
+    /// Title of a section, wrapping the title's components.
+    Title,
+    /// A list of the footnotes of the page.
+    FootnoteList,
+    /// A bibliography.
+    Bibliography,
+    /// A block of code in the given language.
+    CodeBlock(&'comp str),
+    /// An infobox.
+    Infobox,
+    /// A group of rows of an infobox, wrapping the group's heading and rows.
+    InfoboxGroup,
+    /// A row of an infobox, wrapping the row's label and content.
+    InfoboxRow,
+    /// Breadcrumbs of the page.
+    Breadcrumbs,
+    /// A table of contents, whose entries are given.
+    TableOfContents(&'comp [TocEntry]),
+    /// A stylesheet asset.
+    Stylesheet(&'comp Location),
+    /// A script asset.
+    Script(&'comp Location),
+    /// A page with the given title.
+    Page(&'comp str),
+    /// Metadata of the page being visited.
+    PageMetadata(&'comp PageMetadata),
+    /// A component not provided by this crate, described by a name.
+    Other(&'comp str),
+}
+
+/// A visitor of component trees. Every node is entered, then its children are
+/// visited (unless the visitor asks for skipping them), and then it is left.
+/// The context given along with the node carries the component kind of the
+/// node, as well other data, such as the section level.
+pub trait Visitor {
+    /// Enters the given node, before its children are visited.
+    fn enter<K>(&mut self, _node: Node, _ctx: Context<K>) -> Flow
+    where
+        K: ComponentKind + ?Sized,
+    {
+        Flow::Continue
+    }
+
+    /// Leaves the given node, after its children are visited.
+    fn leave<K>(&mut self, _node: Node, _ctx: Context<K>)
+    where
+        K: ComponentKind + ?Sized,
+    {
+    }
+}
+
+impl<V> Visitor for &mut V
+where
+    V: Visitor + ?Sized,
+{
+    fn enter<K>(&mut self, node: Node, ctx: Context<K>) -> Flow
+    where
+        K: ComponentKind + ?Sized,
+    {
+        (**self).enter(node, ctx)
+    }
+
+    fn leave<K>(&mut self, node: Node, ctx: Context<K>)
+    where
+        K: ComponentKind + ?Sized,
+    {
+        (**self).leave(node, ctx)
+    }
+}
+
+/// General visiting trait. A component implements this trait when it can be
+/// walked over by a [`Visitor`], as opposed to being rendered.
+pub trait Visit: Component {
+    /// Visits this component and its children using the given visitor and
+    /// the context of this component.
+    fn visit<V>(&self, visitor: &mut V, ctx: Context<Self::Kind>)
+    where
+        V: Visitor + ?Sized;
+}
+
+/// Visits a node: enters it, visits its children through the given function
+/// (unless the visitor skips them), and then leaves it. Helper for
+/// implementing [`Visit`].
+pub fn visit_node<K, V, F>(
+    visitor: &mut V,
+    node: Node,
+    ctx: Context<K>,
+    children: F,
+) where
+    K: ComponentKind + ?Sized,
+    V: Visitor + ?Sized,
+    F: FnOnce(&mut V),
+{
+    if visitor.enter(node, ctx) == Flow::Continue {
+        children(visitor);
+    }
+    visitor.leave(node, ctx);
+}
+
+impl<C> Visit for Conil<C>
+where
+    C: ComponentKind,
+{
+    fn visit<V>(&self, _visitor: &mut V, _ctx: Context<Self::Kind>)
+    where
+        V: Visitor + ?Sized,
+    {
+    }
+}
+
+impl<H, T> Visit for Cocons<H, T>
+where
+    H: Visit,
+    T: Visit<Kind = H::Kind>,
+{
+    fn visit<V>(&self, visitor: &mut V, ctx: Context<Self::Kind>)
+    where
+        V: Visitor + ?Sized,
+    {
+        match self {
+            Cocons::Head(head) => head.visit(visitor, ctx),
+            Cocons::Tail(tail) => tail.visit(visitor, ctx),
+        }
+    }
+}
+
+impl<T> Visit for &T
+where
+    T: Visit + ?Sized,
+{
+    fn visit<V>(&self, visitor: &mut V, ctx: Context<Self::Kind>)
+    where
+        V: Visitor + ?Sized,
+    {
+        (**self).visit(visitor, ctx)
+    }
+}
+
+impl<T> Visit for &mut T
+where
+    T: Visit + ?Sized,
+{
+    fn visit<V>(&self, visitor: &mut V, ctx: Context<Self::Kind>)
+    where
+        V: Visitor + ?Sized,
+    {
+        (**self).visit(visitor, ctx)
+    }
+}
+
+impl<T> Visit for Box<T>
+where
+    T: Visit + ?Sized,
+{
+    fn visit<V>(&self, visitor: &mut V, ctx: Context<Self::Kind>)
+    where
+        V: Visitor + ?Sized,
+    {
+        (**self).visit(visitor, ctx)
+    }
+}
+
+impl<T> Visit for Rc<T>
+where
+    T: Visit + ?Sized,
+{
+    fn visit<V>(&self, visitor: &mut V, ctx: Context<Self::Kind>)
+    where
+        V: Visitor + ?Sized,
+    {
+        (**self).visit(visitor, ctx)
+    }
+}
+
+impl<T> Visit for Arc<T>
+where
+    T: Visit + ?Sized,
+{
+    fn visit<V>(&self, visitor: &mut V, ctx: Context<Self::Kind>)
+    where
+        V: Visitor + ?Sized,
+    {
+        (**self).visit(visitor, ctx)
+    }
+}
+
+impl<T, const N: usize> Visit for [T; N]
+where
+    T: Visit,
+{
+    fn visit<V>(&self, visitor: &mut V, ctx: Context<Self::Kind>)
+    where
+        V: Visitor + ?Sized,
+    {
+        for element in self {
+            element.visit(visitor, ctx);
+        }
+    }
+}
+
+impl<T> Visit for [T]
+where
+    T: Visit,
+{
+    fn visit<V>(&self, visitor: &mut V, ctx: Context<Self::Kind>)
+    where
+        V: Visitor + ?Sized,
+    {
+        for element in self {
+            element.visit(visitor, ctx);
+        }
+    }
+}
+
+impl<T> Visit for Vec<T>
+where
+    T: Visit,
+{
+    fn visit<V>(&self, visitor: &mut V, ctx: Context<Self::Kind>)
+    where
+        V: Visitor + ?Sized,
+    {
+        for element in self {
+            element.visit(visitor, ctx);
+        }
+    }
+}
+
+impl<A, B> Visit for (A, B)
+where
+    A: Visit,
+    B: Visit<Kind = A::Kind>,
+{
+    fn visit<V>(&self, visitor: &mut V, ctx: Context<Self::Kind>)
+    where
+        V: Visitor + ?Sized,
+    {
+        self.0.visit(visitor, ctx);
+        self.1.visit(visitor, ctx);
+    }
+}
+
+#[cfg(test)]
+mod test {
+    use super::{Flow, Node, Visit, Visitor};
+    use crate::{
+        component::{
+            asset::Stylesheet,
+            block::{list::UnorderedList, text::Paragraph, InlineBlock},
+            inline::text::{Bold, Link},
+            page::{Page, PageComponent},
+            section::Section,
+            ComponentKind,
+        },
+        harray,
+        location::{Id, InternalPath, Location},
+        render::Context,
+    };
+
+    #[derive(Debug, Default)]
+    struct Collector {
+        texts: Vec<String>,
+        links: Vec<Location>,
+        sections: Vec<(u32, Option<Id>)>,
+        depth: usize,
+    }
+
+    impl Visitor for Collector {
+        fn enter<K>(&mut self, node: Node, ctx: Context<K>) -> Flow
+        where
+            K: ComponentKind + ?Sized,
+        {
+            self.depth += 1;
+            match node {
+                Node::Text(text) => self.texts.push(text.to_owned()),
+                Node::Link(location) => self.links.push(location.clone()),
+                Node::Section(id) => {
+                    self.sections.push((ctx.section_level(), id.cloned()))
+                },
+                Node::Bold => return Flow::Skip,
+                _ => (),
+            }
+            Flow::Continue
+        }
+
+        fn leave<K>(&mut self, _node: Node, _ctx: Context<K>)
+        where
+            K: ComponentKind + ?Sized,
+        {
+            self.depth -= 1;
+        }
+    }
+
+    #[test]
+    fn visit_page() {
+        let page = Page::new(
+            InlineBlock("Banner"),
+            String::from("Title"),
+            [Stylesheet { location: Location::internal("main.css") }],
+            harray![
+                Paragraph(harray![
+                    "Hello, ",
+                    Link { target: "world", location: Location::internal("w") },
+                    Bold("skipped"),
+                ]),
+                UnorderedList(harray![InlineBlock("item")]),
+            ],
+            harray![Section {
+                title: "Section",
+                id: Some(Id::new("section")),
+                body: Paragraph("Body"),
+                children: harray![Section {
+                    title: "Subsection",
+                    id: None,
+                    body: Paragraph("Sub body"),
+                    children: harray![],
+                }],
+            }],
+        );
+
+        let mut collector = Collector::default();
+        page.visit(
+            &mut collector,
+            Context::new(&InternalPath::default(), &PageComponent),
+        );
+
+        assert_eq!(collector.depth, 0);
+        assert_eq!(
+            collector.texts,
+            [
+                "Banner",
+                "Hello, ",
+                "world",
+                "item",
+                "Section",
+                "Body",
+                "Subsection",
+                "Sub body"
+            ]
+        );
+        assert_eq!(collector.links, [Location::internal("w")]);
+        assert_eq!(
+            collector.sections,
+            [(0, Some(Id::new("section"))), (1, None)]
+        );
+    }
+}